@@ -1,17 +1,47 @@
-use self::{events::EventsMeta, fields::ComponentField, props::PropsMeta, state::StateMeta};
+pub use self::function::FnComponentMeta;
+
+use self::{
+    events::EventsMeta, fields::ComponentField, generics::verify_generics, props::PropsMeta,
+    state::StateMeta,
+};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use std::mem;
 use syn::{
-    parse::{Error, Result as ParseResult},
-    Attribute, Ident, ItemStruct, Visibility,
+    parse::{Error, Parse, ParseStream, Result as ParseResult},
+    Attribute, Generics, Ident, ItemStruct, Visibility,
 };
 
 mod events;
 mod fields;
+mod function;
+mod generics;
 mod props;
 mod state;
 
+/// The arguments passed to the `#[component]` attribute.
+#[derive(Default)]
+pub struct ComponentArgs {
+    /// Whether the component is an error boundary, i.e. `#[component(boundary)]`.
+    boundary: bool,
+}
+
+impl Parse for ComponentArgs {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        if input.is_empty() {
+            return Ok(ComponentArgs::default());
+        }
+        let arg: Ident = input.parse()?;
+        if arg != "boundary" || !input.is_empty() {
+            return Err(Error::new(
+                arg.span(),
+                "`#[component]` only supports the `boundary` argument.",
+            ));
+        }
+        Ok(ComponentArgs { boundary: true })
+    }
+}
+
 /// All the necessary metadata taken from the struct declaration to construct
 /// a working Component.
 pub struct ComponentMeta {
@@ -21,25 +51,24 @@ pub struct ComponentMeta {
     vis: Visibility,
     /// Ident of component.
     ident: Ident,
+    /// Generic parameters of the component.
+    generics: Generics,
     /// Props metadata if any prop fields.
     props_meta: PropsMeta,
     /// State metadata if any state fields.
     state_meta: StateMeta,
     /// Events metadata if any events declaration.
     events_meta: EventsMeta,
+    /// Arguments passed to the `#[component]` attribute.
+    args: ComponentArgs,
 }
 
 impl ComponentMeta {
-    pub fn parse(mut item: ItemStruct) -> ParseResult<ComponentMeta> {
+    pub fn parse(mut item: ItemStruct, args: ComponentArgs) -> ParseResult<ComponentMeta> {
         // Remove `#[component]` attribute.
         Self::filter_out_component_attribute(&mut item);
 
-        if item.generics != Default::default() {
-            return Err(Error::new(
-                item.ident.span(),
-                "Generic parameters not allowed on a component.",
-            ));
-        }
+        verify_generics(&item.generics)?;
 
         let (props_meta, state_meta) = ComponentField::parse_into_prop_and_state_meta(&mut item)?;
         let events_meta = EventsMeta::parse(&mut item)?;
@@ -48,9 +77,11 @@ impl ComponentMeta {
             attrs: item.attrs,
             vis: item.vis,
             ident: item.ident,
+            generics: item.generics,
             props_meta,
             state_meta,
             events_meta,
+            args,
         })
     }
 
@@ -91,6 +122,8 @@ impl ComponentMeta {
         let attrs = &self.attrs;
         let ident = &self.ident;
         let vis = &self.vis;
+        let generics = &self.generics;
+        let where_clause = &self.generics.where_clause;
 
         if self.props_meta.fields.is_empty()
             && self.state_meta.fields.is_empty()
@@ -98,7 +131,7 @@ impl ComponentMeta {
         {
             quote! {
                 #(#attrs)*
-                #vis struct #ident;
+                #vis struct #ident #generics #where_clause;
             }
         } else {
             let state_fields = self.state_meta.to_struct_fields();
@@ -108,7 +141,7 @@ impl ComponentMeta {
 
             quote! {
                 #(#attrs)*
-                #vis struct #ident {
+                #vis struct #ident #generics #where_clause {
                     #(#state_fields ,)*
                     #(#props_fields ,)*
                     #status_field
@@ -122,12 +155,7 @@ impl ComponentMeta {
         if self.props_meta.fields.is_empty() && self.state_meta.fields.is_empty() {
             quote!()
         } else {
-            let state_ty = if self.state_meta.fields.is_empty() {
-                quote!(())
-            } else {
-                let ident = &self.state_meta.ident;
-                quote!(#ident)
-            };
+            let state_ty = self.get_state_type();
             quote! {
                 __status__: std::rc::Rc<std::cell::RefCell<ruukh::component::Status<#state_ty>>>,
             }
@@ -138,9 +166,9 @@ impl ComponentMeta {
         if self.events_meta.events.is_empty() {
             quote!()
         } else {
-            let ident = &self.events_meta.ident;
+            let events_ty = self.get_events_type();
             quote! {
-                __events__: #ident,
+                __events__: #events_ty,
             }
         }
     }
@@ -150,7 +178,8 @@ impl ComponentMeta {
             quote!(())
         } else {
             let ident = &self.props_meta.ident;
-            quote!(#ident)
+            let (_, ty_generics, _) = self.props_meta.generics.split_for_impl();
+            quote!(#ident #ty_generics)
         }
     }
 
@@ -159,7 +188,8 @@ impl ComponentMeta {
             quote!(())
         } else {
             let ident = &self.state_meta.ident;
-            quote!(#ident)
+            let (_, ty_generics, _) = self.state_meta.generics.split_for_impl();
+            quote!(#ident #ty_generics)
         }
     }
 
@@ -168,12 +198,14 @@ impl ComponentMeta {
             quote!(())
         } else {
             let ident = &self.events_meta.ident;
-            quote!(#ident)
+            let (_, ty_generics, _) = self.events_meta.generics.split_for_impl();
+            quote!(#ident #ty_generics)
         }
     }
 
     fn impl_component_trait_on_component_struct(&self) -> TokenStream {
         let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
         let props_type = &self.get_props_type();
         let state_type = &self.get_state_type();
         let events_type = &self.get_events_type();
@@ -189,13 +221,20 @@ impl ComponentMeta {
         let refresh_state_body = self.impl_fn_refresh_state_body(state_field_idents);
         let status_body = self.impl_fn_status_body();
         let set_state_body = self.impl_fn_set_state_body(state_field_idents);
+        let boundary = if self.args.boundary {
+            quote!(const BOUNDARY: bool = true;)
+        } else {
+            quote!()
+        };
 
         quote! {
-            impl Component for #ident {
+            impl #impl_generics Component for #ident #ty_generics #where_clause {
                 type Props = #props_type;
                 type State = #state_type;
                 type Events = #events_type;
 
+                #boundary
+
                 fn init(
                     __props__: Self::Props,
                     __events__: Self::Events,
@@ -389,3 +428,19 @@ impl ComponentMeta {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_component_args() {
+        let args: ComponentArgs = syn::parse_str("").unwrap();
+        assert!(!args.boundary);
+
+        let args: ComponentArgs = syn::parse_str("boundary").unwrap();
+        assert!(args.boundary);
+
+        assert!(syn::parse_str::<ComponentArgs>("border").is_err());
+    }
+}
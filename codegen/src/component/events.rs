@@ -1,12 +1,14 @@
 use self::parser::{EventDeclaration, EventDeclarations};
+use super::generics::used_generics;
 use crate::suffix::{EVENT_PROPS_SUFFIX, EVENT_SUFFIX};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use std::mem;
 use syn::{
     parse::{Error, Result as ParseResult},
+    parse_quote,
     spanned::Spanned,
-    Attribute, FnArg, Ident, ItemStruct, Pat, ReturnType, Type, Visibility,
+    Attribute, FnArg, Generics, Ident, ItemStruct, Pat, ReturnType, Type, Visibility,
 };
 
 mod parser;
@@ -23,6 +25,10 @@ pub struct EventsMeta {
     pub component_ident: Ident,
     /// Visibility of the component.
     pub vis: Visibility,
+    /// Generic parameters of the component.
+    pub component_generics: Generics,
+    /// Generic parameters of the component used by the events.
+    pub generics: Generics,
     /// All the event declarations on the component.
     pub events: Vec<EventMeta>,
 }
@@ -41,6 +47,8 @@ impl EventsMeta {
             .collect();
         let mut event_metas: Vec<EventMeta> = event_metas?.into_iter().flatten().collect();
         event_metas.sort_by(|l, r| l.ident.cmp(&r.ident));
+        let types = event_metas.iter().map(EventMeta::fn_type);
+        let generics = used_generics(&component.generics, &quote!(#(#types)*));
 
         Ok(EventsMeta {
             ident: Ident::new(
@@ -53,6 +61,8 @@ impl EventsMeta {
             ),
             component_ident: component.ident.clone(),
             vis: component.vis.clone(),
+            component_generics: component.generics.clone(),
+            generics,
             events: event_metas,
         })
     }
@@ -80,6 +90,8 @@ impl EventsMeta {
     fn _create_events_and_event_props_struct_and_macro(&self) -> TokenStream {
         let ident = &self.ident;
         let vis = &self.vis;
+        let generics = &self.generics;
+        let where_clause = &self.generics.where_clause;
         let fields = self.expand_events_with(EventMeta::to_struct_field);
         let event_names = &self.expand_events_with(EventMeta::to_event_name);
 
@@ -87,7 +99,7 @@ impl EventsMeta {
         let events_macro = self.create_events_macro(event_names);
 
         quote! {
-            #vis struct #ident {
+            #vis struct #ident #generics #where_clause {
                 #(#fields),*
             }
 
@@ -180,16 +192,29 @@ impl EventsMeta {
         let gen_fields = self.expand_events_with(EventMeta::to_event_prop_field);
         let event_conversion =
             self.expand_events_with(EventMeta::impl_event_conversion_from_event_prop);
-        let event_wrappers =
-            self.expand_events_with(|e| e.impl_event_wrapper(&self.component_ident));
+        let event_wrappers = self.expand_events_with(|e| {
+            e.impl_event_wrapper(&self.component_ident, &self.component_generics)
+        });
+
+        // The event props are generic over the render context as well.
+        let mut props_generics = self.generics.clone();
+        props_generics.params = Some(parse_quote!(RCTX: Render))
+            .into_iter()
+            .chain(self.generics.params.iter().cloned())
+            .collect();
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let (props_impl_generics, props_ty_generics, where_clause) =
+            props_generics.split_for_impl();
 
         quote! {
-            #vis struct #event_props_ident<RCTX: Render> {
+            #vis struct #event_props_ident #props_generics #where_clause {
                 #(#gen_fields),*
             }
 
-            impl<RCTX: Render> ruukh::component::FromEventProps<RCTX> for #ident {
-                type From = #event_props_ident<RCTX>;
+            impl #props_impl_generics ruukh::component::FromEventProps<RCTX>
+                for #ident #ty_generics #where_clause
+            {
+                type From = #event_props_ident #props_ty_generics;
 
                 fn from(
                     __rctx_events__: Self::From,
@@ -440,14 +465,15 @@ impl EventMeta {
         }
     }
 
-    fn impl_event_wrapper(&self, component_ident: &Ident) -> TokenStream {
+    fn impl_event_wrapper(&self, component_ident: &Ident, generics: &Generics) -> TokenStream {
         let ident = &self.ident;
         let arg_fields = self.to_arg_fields();
         let arg_idents = self.to_arg_idents();
         let ret_type = self.to_return_type();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
         quote! {
-            impl #component_ident {
+            impl #impl_generics #component_ident #ty_generics #where_clause {
                 fn #ident (&self, #(#arg_fields),*) #ret_type {
                     (self.__events__.#ident)(#(#arg_idents),*)
                 }
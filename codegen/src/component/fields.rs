@@ -21,6 +21,9 @@ pub struct ComponentField {
     pub ident: Ident,
     pub ty: Type,
     pub field_type: FieldType,
+    /// Whether the value passed for the prop is converted with `Into`, like
+    /// for a `&str` argument of a function component kept as a `String`.
+    pub converts_into: bool,
 }
 
 /// Type of field.
@@ -84,6 +87,7 @@ impl ComponentField {
             ident: field.ident.unwrap(),
             ty: field.ty,
             field_type,
+            converts_into: false,
         })
     }
 
@@ -193,6 +197,14 @@ impl ComponentField {
         }
     }
 
+    pub fn to_value_for_macro(&self) -> TokenStream {
+        if self.converts_into {
+            quote!(Into::into($val))
+        } else {
+            quote!($val)
+        }
+    }
+
     pub fn to_default_argument_for_macro(&self) -> TokenStream {
         let ident = &self.ident;
         if let AttrArg {
@@ -0,0 +1,361 @@
+use super::{
+    events::EventsMeta, fields::ComponentField, generics::verify_generics, props::PropsMeta,
+    ComponentArgs,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Error, Result as ParseResult},
+    parse_quote,
+    spanned::Spanned,
+    ArgCaptured, Attribute, Block, FnArg, Generics, Ident, ItemFn, ItemStruct, Pat, ReturnType,
+    Type, TypeReference, Visibility,
+};
+
+/// The metadata of a function component, i.e. a component written as a plain
+/// function of its props which renders the markup.
+///
+/// The arguments are kept as the props of the component, which has neither
+/// a state nor a status.
+pub struct FnComponentMeta {
+    /// Attributes on the function.
+    attrs: Vec<Attribute>,
+    /// Visibility specifier on the function.
+    vis: Visibility,
+    /// Ident of the function, which is the ident of the component.
+    ident: Ident,
+    /// Generic parameters of the function.
+    generics: Generics,
+    /// The arguments of the function.
+    args: Vec<FnComponentArg>,
+    /// The body of the function, which renders the markup.
+    block: Box<Block>,
+    /// Props metadata of the arguments.
+    props_meta: PropsMeta,
+    /// Events metadata, which is always void.
+    events_meta: EventsMeta,
+}
+
+/// An argument of a function component.
+struct FnComponentArg {
+    /// The pattern the argument is bound to.
+    pat: Pat,
+    /// Ident of the prop field which keeps the argument.
+    ident: Ident,
+    /// The type of the argument as declared.
+    ty: Type,
+    /// Whether the argument is taken by reference.
+    by_ref: bool,
+}
+
+impl FnComponentMeta {
+    pub fn parse(item: ItemFn, args: ComponentArgs) -> ParseResult<FnComponentMeta> {
+        if args.boundary {
+            return Err(Error::new(
+                item.ident.span(),
+                "A function component cannot be an error boundary.",
+            ));
+        }
+        verify_generics(&item.decl.generics)?;
+        match item.decl.output {
+            ReturnType::Type(..) => (),
+            ReturnType::Default => {
+                return Err(Error::new(
+                    item.ident.span(),
+                    "A function component must return `Markup<Self>`.",
+                ))
+            }
+        }
+
+        let mut args = item
+            .decl
+            .inputs
+            .iter()
+            .map(FnComponentArg::parse)
+            .collect::<ParseResult<Vec<_>>>()?;
+        // The props are passed in the order of their names by `html!`.
+        args.sort_by(|l, r| l.ident.cmp(&r.ident));
+
+        let (props_meta, events_meta) = Self::parse_props_and_events(&item, &args)?;
+
+        Ok(FnComponentMeta {
+            attrs: item.attrs,
+            vis: item.vis,
+            ident: item.ident,
+            generics: item.decl.generics,
+            args,
+            block: item.block,
+            props_meta,
+            events_meta,
+        })
+    }
+
+    /// Parses the arguments as the prop fields of a struct, like the ones of
+    /// a struct component.
+    fn parse_props_and_events(
+        item: &ItemFn,
+        args: &[FnComponentArg],
+    ) -> ParseResult<(PropsMeta, EventsMeta)> {
+        let vis = &item.vis;
+        let ident = &item.ident;
+        let generics = &item.decl.generics;
+        let where_clause = &item.decl.generics.where_clause;
+        let fields = args.iter().map(|arg| {
+            let ident = &arg.ident;
+            let ty = arg.stored_type();
+            quote!(#vis #ident: #ty)
+        });
+        let mut component: ItemStruct = parse_quote! {
+            #vis struct #ident #generics #where_clause {
+                #(#fields),*
+            }
+        };
+
+        let (mut props_meta, _) = ComponentField::parse_into_prop_and_state_meta(&mut component)?;
+        for (field, arg) in props_meta.fields.iter_mut().zip(args.iter()) {
+            field.converts_into = arg.is_str();
+        }
+        let events_meta = EventsMeta::parse(&mut component)?;
+        Ok((props_meta, events_meta))
+    }
+
+    pub fn expand(&self) -> TokenStream {
+        let attrs = &self.attrs;
+        let vis = &self.vis;
+        let ident = &self.ident;
+        let generics = &self.generics;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let block = &self.block;
+
+        let props_struct = self.props_meta.create_props_struct_and_macro();
+        let events_macro = self
+            .events_meta
+            .create_events_and_event_props_struct_and_macro();
+        let field_idents = &self.props_meta.to_field_idents();
+        let field_idents2 = field_idents;
+        let field_idents3 = field_idents;
+        let field_idents4 = field_idents;
+        let struct_fields = self.props_meta.to_struct_fields();
+        let bindings: Vec<_> = self.args.iter().map(FnComponentArg::to_binding).collect();
+
+        let component_struct = if self.args.is_empty() {
+            quote! {
+                #(#attrs)*
+                #vis struct #ident #generics #where_clause;
+            }
+        } else {
+            quote! {
+                #(#attrs)*
+                #vis struct #ident #generics #where_clause {
+                    #(#struct_fields),*
+                }
+            }
+        };
+        let props_type = if self.args.is_empty() {
+            quote!(())
+        } else {
+            let props_ident = &self.props_meta.ident;
+            let (_, props_ty_generics, _) = self.props_meta.generics.split_for_impl();
+            quote!(#props_ident #props_ty_generics)
+        };
+        let (props_arg, props_updation) = if self.args.is_empty() {
+            (quote!(_), quote!(None))
+        } else {
+            let updation = quote! {
+                use std::mem;
+
+                let mut updated = false;
+                #(
+                    if self.#field_idents != __props__.#field_idents2 {
+                        mem::swap(&mut self.#field_idents3, &mut __props__.#field_idents4);
+                        updated = true;
+                    }
+                )*
+                if updated {
+                    Some(__props__)
+                } else {
+                    None
+                }
+            };
+            (quote!(mut __props__), updation)
+        };
+
+        quote! {
+            #component_struct
+
+            #props_struct
+
+            #events_macro
+
+            impl #impl_generics Component for #ident #ty_generics #where_clause {
+                type Props = #props_type;
+                type State = ();
+                type Events = ();
+
+                fn init(
+                    __props__: Self::Props,
+                    _: Self::Events,
+                    _: ruukh::component::Status<Self::State>,
+                ) -> Self {
+                    #ident {
+                        #(#field_idents: __props__.#field_idents2),*
+                    }
+                }
+
+                fn update(
+                    &mut self,
+                    #props_arg: Self::Props,
+                    _: Self::Events,
+                ) -> Option<Self::Props> {
+                    #props_updation
+                }
+
+                fn refresh_state(&mut self) {}
+
+                fn status(&self) -> Option<&std::rc::Rc<
+                                        std::cell::RefCell<
+                                            ruukh::component::Status<
+                                                Self::State>>>>
+                {
+                    None
+                }
+
+                fn set_state(&self, mut mutator: impl FnMut(&mut Self::State)) {
+                    mutator(&mut ());
+                }
+            }
+
+            impl #impl_generics Lifecycle for #ident #ty_generics #where_clause {}
+
+            impl #impl_generics Render for #ident #ty_generics #where_clause {
+                fn render(&self) -> Markup<Self> {
+                    #(#bindings)*
+                    #block
+                }
+            }
+        }
+    }
+}
+
+impl FnComponentArg {
+    fn parse(arg: &FnArg) -> ParseResult<FnComponentArg> {
+        let (pat, ty) = match arg {
+            FnArg::Captured(ArgCaptured { ref pat, ref ty, .. }) => (pat, ty),
+            _ => {
+                return Err(Error::new(
+                    arg.span(),
+                    "A function component only takes its props as arguments.",
+                ))
+            }
+        };
+        let ident = match pat {
+            Pat::Ident(ref pat_ident) if pat_ident.subpat.is_none() => pat_ident.ident.clone(),
+            _ => {
+                return Err(Error::new(
+                    pat.span(),
+                    "The arguments of a function component must be named.",
+                ))
+            }
+        };
+        let by_ref = match ty {
+            Type::Reference(TypeReference {
+                ref lifetime,
+                ref mutability,
+                ..
+            }) => {
+                if mutability.is_some() {
+                    return Err(Error::new(
+                        ty.span(),
+                        "A function component cannot mutate its props.",
+                    ));
+                }
+                // A `'static` reference is kept as it is.
+                lifetime.is_none()
+            }
+            _ => false,
+        };
+        Ok(FnComponentArg {
+            pat: pat.clone(),
+            ident,
+            ty: ty.clone(),
+            by_ref,
+        })
+    }
+
+    /// Whether the argument is a `&str`, which is kept as a `String`.
+    fn is_str(&self) -> bool {
+        match self.ty {
+            Type::Reference(TypeReference { ref elem, .. }) if self.by_ref => {
+                quote!(#elem).to_string() == "str"
+            }
+            _ => false,
+        }
+    }
+
+    /// The type of the prop field which keeps the argument.
+    fn stored_type(&self) -> TokenStream {
+        match self.ty {
+            _ if self.is_str() => quote!(String),
+            Type::Reference(TypeReference { ref elem, .. }) if self.by_ref => quote!(#elem),
+            ref ty => quote!(#ty),
+        }
+    }
+
+    /// Binds the argument to its prop field within the render. The arguments
+    /// taken by value are cloned.
+    fn to_binding(&self) -> TokenStream {
+        let pat = &self.pat;
+        let ident = &self.ident;
+        let ty = &self.ty;
+        if self.by_ref {
+            quote! {
+                let #pat: #ty = &self.#ident;
+            }
+        } else {
+            quote! {
+                let #pat: #ty = self.#ident.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_function_component() {
+        let item: ItemFn = syn::parse_str(
+            r#"fn Badge(text: &str, color: Color, count: &u32) -> Markup<Self> {
+                html! { "Badge" }
+            }"#,
+        ).unwrap();
+        let meta = FnComponentMeta::parse(item, ComponentArgs::default()).unwrap();
+
+        let idents: Vec<_> = meta.args.iter().map(|arg| arg.ident.to_string()).collect();
+        assert_eq!(idents, vec!["color", "count", "text"]);
+        let stored: Vec<_> = meta
+            .args
+            .iter()
+            .map(|arg| arg.stored_type().to_string())
+            .collect();
+        assert_eq!(stored, vec!["Color", "u32", "String"]);
+        let converted: Vec<_> = meta
+            .props_meta
+            .fields
+            .iter()
+            .map(|field| field.converts_into)
+            .collect();
+        assert_eq!(converted, vec![false, false, true]);
+    }
+
+    #[test]
+    fn should_not_parse_function_component_without_markup() {
+        let item: ItemFn = syn::parse_str("fn Badge(text: &str) {}").unwrap();
+        assert!(FnComponentMeta::parse(item, ComponentArgs::default()).is_err());
+
+        let item: ItemFn =
+            syn::parse_str("fn Badge(text: &mut String) -> Markup<Self> {}").unwrap();
+        assert!(FnComponentMeta::parse(item, ComponentArgs::default()).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{
+    parse::{Error, Result as ParseResult},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    GenericParam, Generics, Ident, WhereClause, WherePredicate,
+};
+
+/// Verifies that the component only has type parameters. Lifetimes are of
+/// no use as a component is `'static`.
+pub fn verify_generics(generics: &Generics) -> ParseResult<()> {
+    for param in generics.params.iter() {
+        match param {
+            GenericParam::Type(_) => (),
+            _ => {
+                return Err(Error::new(
+                    param.span(),
+                    "Only type parameters are allowed on a component.",
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Keeps only the type parameters of the component which are used within
+/// the `tokens`, along with the where predicates on them. So that the types
+/// generated for the component are generic only over what they hold.
+pub fn used_generics(generics: &Generics, tokens: &TokenStream) -> Generics {
+    let (used, unused): (Vec<_>, Vec<_>) = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(ref ty) => Some(ty),
+            _ => None,
+        }).partition(|ty| mentions(tokens.clone(), &ty.ident));
+
+    let params: Punctuated<GenericParam, _> = used
+        .iter()
+        .map(|ty| GenericParam::Type((*ty).clone()))
+        .collect();
+
+    let where_clause = generics.where_clause.as_ref().map(|where_clause| {
+        let predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|predicate| {
+                let tokens = match predicate {
+                    WherePredicate::Type(ref ty) => ty.bounded_ty.clone().into_token_stream(),
+                    _ => return false,
+                };
+                used.iter().any(|ty| mentions(tokens.clone(), &ty.ident))
+                    && !unused.iter().any(|ty| mentions(tokens.clone(), &ty.ident))
+            }).cloned()
+            .collect();
+        WhereClause {
+            where_token: where_clause.where_token,
+            predicates,
+        }
+    });
+
+    Generics {
+        lt_token: if params.is_empty() {
+            None
+        } else {
+            generics.lt_token
+        },
+        gt_token: if params.is_empty() {
+            None
+        } else {
+            generics.gt_token
+        },
+        params,
+        where_clause,
+    }
+}
+
+/// Whether the ident is mentioned anywhere within the tokens.
+fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|tree| match tree {
+        TokenTree::Ident(ref found) => found == ident,
+        TokenTree::Group(ref group) => mentions(group.stream(), ident),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use quote::quote;
+    use syn::ItemStruct;
+
+    #[test]
+    fn should_keep_only_the_used_generics() {
+        let item: ItemStruct = syn::parse_str(
+            "struct List<T: Clone, U> where T: PartialEq, U: Default, Vec<U>: Clone {}",
+        ).unwrap();
+
+        let generics = used_generics(&item.generics, &quote!(Vec<T>));
+        assert_eq!(
+            generics.into_token_stream().to_string(),
+            quote!(<T: Clone>).to_string()
+        );
+        let where_clause = used_generics(&item.generics, &quote!(Option<U>)).where_clause;
+        assert_eq!(
+            where_clause.into_token_stream().to_string(),
+            quote!(where U: Default, Vec<U>: Clone).to_string()
+        );
+        assert!(used_generics(&item.generics, &quote!(i32)).params.is_empty());
+    }
+
+    #[test]
+    fn should_not_allow_lifetimes() {
+        let item: ItemStruct = syn::parse_str("struct List<'a, T> {}").unwrap();
+        assert!(verify_generics(&item.generics).is_err());
+    }
+}
@@ -1,8 +1,8 @@
-use super::fields::ComponentField;
+use super::{fields::ComponentField, generics::used_generics};
 use crate::suffix::PROPS_SUFFIX;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Ident, ItemStruct, Visibility};
+use syn::{Generics, Ident, ItemStruct, Visibility};
 
 /// Stores the list of prop fields.
 pub struct PropsMeta {
@@ -12,12 +12,16 @@ pub struct PropsMeta {
     pub component_ident: Ident,
     /// Visiblilty of the component.
     pub vis: Visibility,
+    /// Generic parameters of the component used by the props.
+    pub generics: Generics,
     /// List of prop fields.
     pub fields: Vec<ComponentField>,
 }
 
 impl PropsMeta {
     pub fn parse(component: &ItemStruct, fields: Vec<ComponentField>) -> PropsMeta {
+        let types = fields.iter().map(|field| &field.ty);
+        let generics = used_generics(&component.generics, &quote!(#(#types)*));
         PropsMeta {
             ident: Ident::new(
                 &format!("{}{}", component.ident, PROPS_SUFFIX),
@@ -25,6 +29,7 @@ impl PropsMeta {
             ),
             component_ident: component.ident.clone(),
             vis: component.vis.clone(),
+            generics,
             fields,
         }
     }
@@ -58,12 +63,14 @@ impl PropsMeta {
     fn _create_props_struct_and_macro(&self) -> TokenStream {
         let ident = &self.ident;
         let vis = &self.vis;
+        let generics = &self.generics;
+        let where_clause = &self.generics.where_clause;
         let fields = self.expand_fields_with(ComponentField::to_struct_field);
 
         let props_macro = self.create_props_macro();
 
         quote! {
-            #vis struct #ident {
+            #vis struct #ident #generics #where_clause {
                 #(#fields),*
             }
 
@@ -77,6 +84,7 @@ impl PropsMeta {
         let comp_ident = &self.component_ident;
 
         let field_idents = self.expand_fields_with(ComponentField::to_ident);
+        let field_vals = self.expand_fields_with(ComponentField::to_value_for_macro);
         let field_default_vals =
             self.expand_fields_with(ComponentField::to_default_argument_for_macro);
         let mut next_idents = field_idents.clone();
@@ -87,8 +95,8 @@ impl PropsMeta {
         let match_hands = field_idents
             .iter()
             .zip(next_idents.iter())
-            .zip(field_default_vals.iter())
-            .map(|((cur, next), default)| {
+            .zip(field_vals.iter().zip(field_default_vals.iter()))
+            .map(|((cur, next), (val, default))| {
                 quote!{
                     (
                         @#cur
@@ -97,7 +105,7 @@ impl PropsMeta {
                     ) => {
                         #internal_macro_ident!(
                             @#next
-                            arguments = [{ $($args)* [#cur = $val] }]
+                            arguments = [{ $($args)* [#cur = #val] }]
                             tokens = [{ $($rest)* }]
                         );
                     },
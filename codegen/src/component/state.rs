@@ -1,24 +1,29 @@
-use super::fields::ComponentField;
+use super::{fields::ComponentField, generics::used_generics};
 use crate::suffix::STATE_SUFFIX;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Ident, ItemStruct};
+use syn::{Generics, Ident, ItemStruct};
 
 /// Stores the list of state fields.
 pub struct StateMeta {
     /// Ident of state struct.
     pub ident: Ident,
+    /// Generic parameters of the component used by the state.
+    pub generics: Generics,
     /// List of state fields.
     pub fields: Vec<ComponentField>,
 }
 
 impl StateMeta {
     pub fn parse(component: &ItemStruct, fields: Vec<ComponentField>) -> StateMeta {
+        let types = fields.iter().map(|field| &field.ty);
+        let generics = used_generics(&component.generics, &quote!(#(#types)*));
         StateMeta {
             ident: Ident::new(
                 &format!("{}{}", component.ident, STATE_SUFFIX),
                 Span::call_site(),
             ),
+            generics,
             fields,
         }
     }
@@ -44,16 +49,18 @@ impl StateMeta {
     pub fn create_state_struct(&self) -> TokenStream {
         if !self.fields.is_empty() {
             let ident = &self.ident;
+            let generics = &self.generics;
+            let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
             let fields = self.expand_fields_with(ComponentField::to_struct_field);
             let def_fields =
                 self.expand_fields_with(ComponentField::to_field_assignment_as_default);
 
             quote! {
-                struct #ident {
+                struct #ident #generics #where_clause {
                     #(#fields),*
                 }
 
-                impl Default for #ident {
+                impl #impl_generics Default for #ident #ty_generics #where_clause {
                     fn default() -> Self {
                         #ident {
                             #(#def_fields),*
@@ -2,7 +2,7 @@
 //!
 //! ROOT -> ITEM ITEM
 //!
-//! ITEM -> ELEMENT ITEM | EXPR_BLOCK ITEM | TEXT ITEM | EPS
+//! ITEM -> ELEMENT ITEM | EXPR_BLOCK ITEM | TEXT ITEM | IF ITEM | FOR ITEM | MATCH ITEM | EPS
 //!
 //! ELEMENT ->
 //! <TAGNAME ATTRIBUTES>
@@ -15,27 +15,53 @@
 //!
 //! EXPR_BLOCK -> { EXPR }
 //!
+//! IF -> if CONDITION { ROOT } ELSE
+//!
+//! CONDITION -> EXPR | let PATS = EXPR
+//!
+//! ELSE -> else IF | else { ROOT } | EPS
+//!
+//! FOR -> for PAT in EXPR { ROOT }
+//!
+//! MATCH -> match EXPR { ARMS }
+//!
+//! ARMS -> PATS GUARD => { ROOT } OPTIONAL_COMMA ARMS | PATS GUARD => ITEM , ARMS | EPS
+//!
+//! GUARD -> if EXPR | EPS
+//!
 //! TAGNAME -> DASHED_IDENT
 //!
 //! ATTRIBUTES -> ATTRIBUTE ATTRIBUTES | EPS
 //!
-//! ATTRIBUTE -> OPTIONAL_AT DASHED_IDENT = { EXPR }
+//! ATTRIBUTE -> ATTRIBUTE_NAME = { EXPR } | @ DASHED_IDENT MODIFIERS = { EXPR } |
+//! . IDENT = { EXPR } | bind:IDENT = { EXPR } | slot
 //!
-//! OPTIONAL_AT -> @ | EPS
+//! MODIFIERS -> . IDENT MODIFIERS | EPS
+//!
+//! ATTRIBUTE_NAME -> PREFIX:DASHED_IDENT | DASHED_IDENT
+//!
+//! PREFIX -> xlink | xml | xmlns
 //!
 //! DASHED_IDENT -> IDENT-DASHED_IDENT | IDENT
 //!
-//! N.B. EPS is Epsilon and IDENT & EXPR are Rust constructs.
+//! N.B. EPS is Epsilon and IDENT, EXPR, PAT & PATS are Rust constructs.
 
-use self::element::{HtmlElement, KeyAttribute};
+use self::{
+    control::{HtmlFor, HtmlIf, HtmlMatch},
+    element::{HtmlElement, KeyAttribute},
+};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{
-    parse::{Parse, ParseStream, Result as ParseResult},
+    parse::{Error, Parse, ParseStream, Result as ParseResult},
     token, Block as RustExpressionBlock, LitStr, Token,
 };
 
+mod bind;
+mod control;
 mod element;
+mod events;
+mod foreign;
 mod kw;
 
 pub struct HtmlRoot {
@@ -46,6 +72,14 @@ pub struct HtmlRoot {
 
 impl Parse for HtmlRoot {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        let ungrouped_items = HtmlRoot::parse_items(input)?;
+        HtmlRoot::group(ungrouped_items)
+    }
+}
+
+impl HtmlRoot {
+    /// Parses the items until an end tag is encountered.
+    pub fn parse_items(input: ParseStream<'_>) -> ParseResult<Vec<HtmlItem>> {
         let mut ungrouped_items: Vec<HtmlItem> = vec![];
         while !input.is_empty() {
             // Encounters an end tag.
@@ -54,6 +88,18 @@ impl Parse for HtmlRoot {
             }
             ungrouped_items.push(input.parse()?);
         }
+        Ok(ungrouped_items)
+    }
+
+    /// Groups the items into a root. The slots, if any, must already be
+    /// taken out by the component they are passed into.
+    pub fn group(ungrouped_items: Vec<HtmlItem>) -> ParseResult<Self> {
+        if let Some(slot) = ungrouped_items.iter().filter_map(HtmlItem::slot).next() {
+            return Err(Error::new(
+                slot.span,
+                "A slot is only allowed as a child of a component.",
+            ));
+        }
 
         let flat_len = ungrouped_items.len();
         let mut keyed_only = true;
@@ -100,9 +146,7 @@ impl Parse for HtmlRoot {
             keyed_only,
         })
     }
-}
 
-impl HtmlRoot {
     pub fn expand(&self) -> TokenStream {
         let expanded: Vec<_> = self.items.iter().map(|i| i.expand()).collect();
         if self.flat_len == 0 {
@@ -129,20 +173,23 @@ pub enum HtmlItems {
 }
 
 impl HtmlItems {
+    fn items(&self) -> &[HtmlItem] {
+        match self {
+            HtmlItems::Keyed(ref items) | HtmlItems::Unkeyed(ref items) => items,
+        }
+    }
+
     fn expand(&self) -> TokenStream {
         match self {
             HtmlItems::Keyed(ref items) => {
                 let expanded: Vec<_> = items.iter().map(HtmlItem::expand).collect();
                 let capacity = expanded.len();
                 quote! {
-                    ruukh::vdom::VNode::from(ruukh::vdom::vlist::VList::from({
-                        let mut map = ruukh::reexports::IndexMap::with_capacity_and_hasher(
-                            #capacity,
-                            ruukh::reexports::FnvBuildHasher::default()
-                        );
-                        #(map.insert(#expanded);)*
-                        map
-                    }))
+                    ruukh::vdom::VNode::from({
+                        let mut list = ruukh::vdom::vlist::VList::keyed(#capacity);
+                        #(list.insert(#expanded);)*
+                        list
+                    })
                 }
             }
             HtmlItems::Unkeyed(ref items) => {
@@ -159,6 +206,9 @@ pub enum HtmlItem {
     Element(Box<HtmlElement>),
     ExpressionBlock(RustExpressionBlock),
     Text(Text),
+    If(Box<HtmlIf>),
+    For(Box<HtmlFor>),
+    Match(Box<HtmlMatch>),
 }
 
 impl Parse for HtmlItem {
@@ -170,6 +220,12 @@ impl Parse for HtmlItem {
             Ok(HtmlItem::ExpressionBlock(input.parse()?))
         } else if lookahead1.peek(LitStr) {
             Ok(HtmlItem::Text(input.parse()?))
+        } else if lookahead1.peek(Token![if]) {
+            Ok(HtmlItem::If(Box::new(input.parse()?)))
+        } else if lookahead1.peek(Token![for]) {
+            Ok(HtmlItem::For(Box::new(input.parse()?)))
+        } else if lookahead1.peek(Token![match]) {
+            Ok(HtmlItem::Match(Box::new(input.parse()?)))
         } else {
             Err(lookahead1.error())
         }
@@ -196,6 +252,9 @@ impl HtmlItem {
                     ruukh::vdom::VNode::from(ruukh::vdom::vtext::VText::text(#string))
                 }
             }
+            HtmlItem::If(ref if_) => if_.expand(),
+            HtmlItem::For(ref for_) => for_.expand(),
+            HtmlItem::Match(ref match_) => match_.expand(),
         };
 
         if let Some(key) = self.key() {
@@ -214,6 +273,13 @@ impl HtmlItem {
             _ => None,
         }
     }
+
+    fn slot(&self) -> Option<&kw::slot> {
+        match self {
+            HtmlItem::Element(ref el) => el.slot(),
+            _ => None,
+        }
+    }
 }
 
 pub struct Text {
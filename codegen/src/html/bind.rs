@@ -0,0 +1,197 @@
+//! The two-way binding of the inputs to the state, i.e. `bind:value={field}`
+//! and `bind:checked={field}` along with the `bind:error={hook}` of a value
+//! which fails to parse.
+use super::kw;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    braced,
+    parse::{Error, Parse, ParseStream, Result as ParseResult},
+    Expr, Ident, Token,
+};
+
+/// A single `bind:*` attribute.
+pub struct BindAttribute {
+    pub kind: BindKind,
+    pub value: Expr,
+    pub span: Span,
+}
+
+pub enum BindKind {
+    Value,
+    Checked,
+    Error,
+}
+
+impl Parse for BindAttribute {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        let bind: kw::bind = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ident: Ident = input.parse()?;
+        let kind = match ident.to_string().as_str() {
+            "value" => BindKind::Value,
+            "checked" => BindKind::Checked,
+            "error" => BindKind::Error,
+            name => {
+                return Err(Error::new(
+                    ident.span(),
+                    format!(
+                        "Unknown binding `bind:{}`. Expected one of bind:value, \
+                         bind:checked or bind:error.",
+                        name
+                    ),
+                ))
+            }
+        };
+        input.parse::<Token![=]>()?;
+        let content;
+        braced!(content in input);
+        let value = content.parse()?;
+        Ok(BindAttribute {
+            kind,
+            value,
+            span: bind.span,
+        })
+    }
+}
+
+/// The bindings of an element.
+#[derive(Default)]
+pub struct Binding {
+    /// The state field the value is bound to.
+    pub value: Option<Ident>,
+    /// The state field the checkedness is bound to.
+    pub checked: Option<Ident>,
+    /// The hook invoked with the value which fails to parse.
+    pub error: Option<Expr>,
+}
+
+impl Binding {
+    /// Collects the `bind:*` attributes of an element.
+    pub fn new(attributes: Vec<BindAttribute>) -> ParseResult<Binding> {
+        let mut binding = Binding::default();
+        let mut error_span = None;
+        for attr in attributes {
+            let duplicate = match attr.kind {
+                BindKind::Value => binding
+                    .value
+                    .replace(field(attr.value, attr.span)?)
+                    .is_some(),
+                BindKind::Checked => binding
+                    .checked
+                    .replace(field(attr.value, attr.span)?)
+                    .is_some(),
+                BindKind::Error => {
+                    error_span = Some(attr.span);
+                    binding.error.replace(attr.value).is_some()
+                }
+            };
+            if duplicate {
+                return Err(Error::new(attr.span, "Duplicate binding."));
+            }
+        }
+        if let (Some(span), None) = (error_span, &binding.value) {
+            return Err(Error::new(
+                span,
+                "`bind:error` is only allowed along with `bind:value`.",
+            ));
+        }
+        Ok(binding)
+    }
+
+    /// The properties which show the state.
+    pub fn expand_properties(&self) -> Vec<TokenStream> {
+        let mut properties = vec![];
+        if let Some(ref field) = self.value {
+            properties.push(quote! {
+                ruukh::vdom::velement::Property::new(
+                    "value",
+                    ruukh::bind::BindValue::to_property(&self.#field)
+                )
+            });
+        }
+        if let Some(ref field) = self.checked {
+            properties.push(quote! {
+                ruukh::vdom::velement::Property::new("checked", self.#field)
+            });
+        }
+        properties
+    }
+
+    /// The listeners which write the input back into the state.
+    pub fn expand_listeners(&self) -> Vec<TokenStream> {
+        let mut listeners = vec![];
+        if let Some(ref field) = self.value {
+            let on_error = match self.error {
+                Some(ref hook) => quote!(#hook),
+                None => quote!(|_, _| {}),
+            };
+            listeners.push(quote! {
+                ruukh::bind::value::<Self, _>(
+                    |state, value| state.#field = value,
+                    #on_error
+                )
+            });
+        }
+        if let Some(ref field) = self.checked {
+            listeners.push(quote! {
+                ruukh::bind::checked::<Self>(|state, checked| state.#field = checked)
+            });
+        }
+        listeners
+    }
+}
+
+/// The state field a binding names.
+fn field(expr: Expr, span: Span) -> ParseResult<Ident> {
+    match expr {
+        Expr::Path(ref path) if path.qself.is_none() && path.path.segments.len() == 1 => {
+            Ok(path.path.segments[0].ident.clone())
+        }
+        _ => Err(Error::new(span, "Expected the name of a state field.")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_binding(attrs: &[&str]) -> ParseResult<Binding> {
+        let attrs = attrs
+            .iter()
+            .map(|attr| syn::parse_str(attr))
+            .collect::<ParseResult<Vec<_>>>()?;
+        Binding::new(attrs)
+    }
+
+    #[test]
+    fn should_parse_bindings() {
+        let binding =
+            parse_binding(&["bind:value={age}", "bind:error={Self::on_invalid_age}"]).unwrap();
+        assert_eq!(binding.value.unwrap(), "age");
+        assert!(binding.checked.is_none());
+        assert!(binding.error.is_some());
+    }
+
+    #[test]
+    fn should_expand_bindings() {
+        let binding = parse_binding(&["bind:checked={done}"]).unwrap();
+        assert_eq!(
+            binding.expand_properties()[0].to_string(),
+            quote!(ruukh::vdom::velement::Property::new("checked", self.done)).to_string()
+        );
+        assert!(
+            binding.expand_listeners()[0]
+                .to_string()
+                .contains("ruukh :: bind :: checked")
+        );
+    }
+
+    #[test]
+    fn should_not_parse_invalid_bindings() {
+        assert!(parse_binding(&["bind:text={name}"]).is_err());
+        assert!(parse_binding(&["bind:value={self.name}"]).is_err());
+        assert!(parse_binding(&["bind:value={name}", "bind:value={age}"]).is_err());
+        assert!(parse_binding(&["bind:error={Self::on_invalid}"]).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+//! The control-flow items of the html! macro, i.e. `if`, `for` and `match`.
+use super::{HtmlItem, HtmlRoot};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::{
+    braced,
+    parse::{Error, Parse, ParseStream, Result as ParseResult},
+    punctuated::Punctuated,
+    token, Expr, Pat, Token,
+};
+
+/// An `if` item along with its `else if` and `else` branches.
+///
+/// `if COND { ROOT } else if COND { ROOT } else { ROOT }`
+pub struct HtmlIf {
+    pub condition: Condition,
+    pub then_branch: HtmlRoot,
+    pub else_branch: Option<ElseBranch>,
+}
+
+/// The condition of an `if`, which may also be an `if let`.
+pub enum Condition {
+    Expr(Expr),
+    Let(Punctuated<Pat, Token![|]>, Expr),
+}
+
+pub enum ElseBranch {
+    If(Box<HtmlIf>),
+    Else(HtmlRoot),
+}
+
+impl Parse for HtmlIf {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        input.parse::<Token![if]>()?;
+        let condition = if input.peek(Token![let]) {
+            input.parse::<Token![let]>()?;
+            let pats = parse_pats(input)?;
+            input.parse::<Token![=]>()?;
+            Condition::Let(pats, parse_expr_until(input, |input| input.peek(token::Brace))?)
+        } else {
+            Condition::Expr(parse_expr_until(input, |input| input.peek(token::Brace))?)
+        };
+        let then_branch = parse_braced_root(input)?;
+
+        let else_branch = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            if input.peek(Token![if]) {
+                Some(ElseBranch::If(Box::new(input.parse()?)))
+            } else {
+                Some(ElseBranch::Else(parse_braced_root(input)?))
+            }
+        } else {
+            None
+        };
+
+        Ok(HtmlIf {
+            condition,
+            then_branch,
+            else_branch,
+        })
+    }
+}
+
+impl HtmlIf {
+    pub fn expand(&self) -> TokenStream {
+        let condition = match self.condition {
+            Condition::Expr(ref expr) => quote!(#expr),
+            Condition::Let(ref pats, ref expr) => quote!(let #pats = #expr),
+        };
+        let then_branch = self.then_branch.expand();
+        // A missing else branch renders nothing.
+        let else_branch = match self.else_branch {
+            Some(ElseBranch::If(ref if_)) => if_.expand(),
+            Some(ElseBranch::Else(ref root)) => {
+                let expanded = root.expand();
+                quote!({ #expanded })
+            }
+            None => quote!({ ruukh::vdom::VNode::None }),
+        };
+        quote! {
+            if #condition {
+                #then_branch
+            } else #else_branch
+        }
+    }
+}
+
+/// A `for` item which renders its body for every item of the iterator.
+///
+/// `for PAT in EXPR { ROOT }`
+///
+/// If the body is keyed, the keys are carried over to the list rendered by
+/// the loop.
+pub struct HtmlFor {
+    pub pat: Pat,
+    pub iter: Expr,
+    pub body: HtmlRoot,
+}
+
+impl Parse for HtmlFor {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        input.parse::<Token![for]>()?;
+        let pat = input.parse()?;
+        input.parse::<Token![in]>()?;
+        let iter = parse_expr_until(input, |input| input.peek(token::Brace))?;
+        let body = parse_braced_root(input)?;
+        Ok(HtmlFor { pat, iter, body })
+    }
+}
+
+impl HtmlFor {
+    pub fn expand(&self) -> TokenStream {
+        let pat = &self.pat;
+        let iter = &self.iter;
+        if self.body.flat_len != 0 && self.body.keyed_only {
+            let entries: Vec<_> = self
+                .body
+                .items
+                .iter()
+                .flat_map(|items| items.items())
+                .map(HtmlItem::expand)
+                .collect();
+            quote! {
+                ruukh::vdom::VNode::from({
+                    let mut list = ruukh::vdom::vlist::VList::keyed(0);
+                    for #pat in #iter {
+                        #(list.insert(#entries);)*
+                    }
+                    list
+                })
+            }
+        } else {
+            let body = self.body.expand();
+            quote! {
+                ruukh::vdom::VNode::from(ruukh::vdom::vlist::VList::from({
+                    let mut list = vec![];
+                    for #pat in #iter {
+                        list.push(#body);
+                    }
+                    list
+                }))
+            }
+        }
+    }
+}
+
+/// A `match` item which renders the body of the matched arm.
+///
+/// `match EXPR { PAT => { ROOT }, PAT if GUARD => ITEM, ... }`
+pub struct HtmlMatch {
+    pub expr: Expr,
+    pub arms: Vec<MatchArm>,
+}
+
+pub struct MatchArm {
+    pub pats: Punctuated<Pat, Token![|]>,
+    pub guard: Option<Expr>,
+    pub body: HtmlRoot,
+}
+
+impl Parse for HtmlMatch {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        input.parse::<Token![match]>()?;
+        let expr = parse_expr_until(input, |input| input.peek(token::Brace))?;
+
+        let content;
+        braced!(content in input);
+        let mut arms = vec![];
+        while !content.is_empty() {
+            arms.push(content.parse()?);
+        }
+        Ok(HtmlMatch { expr, arms })
+    }
+}
+
+impl Parse for MatchArm {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        let pats = parse_pats(input)?;
+        let guard = if input.peek(Token![if]) {
+            input.parse::<Token![if]>()?;
+            Some(parse_expr_until(input, |input| input.peek(Token![=>]))?)
+        } else {
+            None
+        };
+        input.parse::<Token![=>]>()?;
+        // The body is either a block of markup or a single item. As in Rust,
+        // the comma after a block is optional.
+        let braced = input.peek(token::Brace);
+        let body = if braced {
+            parse_braced_root(input)?
+        } else {
+            HtmlRoot::group(vec![input.parse()?])?
+        };
+        if braced {
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        } else if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(MatchArm { pats, guard, body })
+    }
+}
+
+impl HtmlMatch {
+    pub fn expand(&self) -> TokenStream {
+        let expr = &self.expr;
+        let arms = self.arms.iter().map(|arm| {
+            let pats = &arm.pats;
+            let guard = arm.guard.as_ref().map(|guard| quote!(if #guard));
+            let body = arm.body.expand();
+            quote! {
+                #pats #guard => { #body }
+            }
+        });
+        quote! {
+            match #expr {
+                #(#arms)*
+            }
+        }
+    }
+}
+
+/// Parses the markup within braces.
+fn parse_braced_root(input: ParseStream<'_>) -> ParseResult<HtmlRoot> {
+    let content;
+    braced!(content in input);
+    content.parse()
+}
+
+/// Parses the patterns separated by `|`, with an optional leading `|`.
+fn parse_pats(input: ParseStream<'_>) -> ParseResult<Punctuated<Pat, Token![|]>> {
+    if input.peek(Token![|]) {
+        input.parse::<Token![|]>()?;
+    }
+    let mut pats = Punctuated::new();
+    loop {
+        pats.push_value(input.parse()?);
+        if !input.peek(Token![|]) {
+            break;
+        }
+        pats.push_punct(input.parse()?);
+    }
+    Ok(pats)
+}
+
+/// Parses an expression which ends right before `end` is encountered, like
+/// the condition of an `if` which is followed by its braced body.
+fn parse_expr_until(
+    input: ParseStream<'_>,
+    end: impl Fn(ParseStream<'_>) -> bool,
+) -> ParseResult<Expr> {
+    let span = input.cursor().span();
+    let mut tokens = TokenStream::new();
+    while !input.is_empty() && !end(input) {
+        tokens.extend(Some(input.parse::<TokenTree>()?));
+    }
+    if tokens.is_empty() {
+        return Err(Error::new(span, "Expected an expression."));
+    }
+    syn::parse2(tokens)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_if_else_chain() {
+        let if_: HtmlIf = syn::parse_str(
+            r#"if count == 0 {
+                "None"
+            } else if let Some(name) = names.first() {
+                <span>{ name }</span>
+            } else {
+                "Many"
+            }"#,
+        ).unwrap();
+
+        match if_.else_branch {
+            Some(ElseBranch::If(ref else_if)) => {
+                assert!(matches!(else_if.condition, Condition::Let(..)));
+                assert!(match else_if.else_branch {
+                    Some(ElseBranch::Else(ref root)) => root.flat_len == 1,
+                    _ => false,
+                });
+            }
+            _ => panic!("Expected an else if branch"),
+        }
+    }
+
+    #[test]
+    fn should_expand_missing_else_to_none() {
+        let if_: HtmlIf = syn::parse_str(r#"if visible { "Shown" }"#).unwrap();
+        let expanded = if_.expand().to_string();
+        assert!(expanded.contains(&quote!(else { ruukh::vdom::VNode::None }).to_string()));
+    }
+
+    #[test]
+    fn should_parse_for_with_keyed_body() {
+        let for_: HtmlFor = syn::parse_str(
+            r#"for item in self.items.iter() {
+                <li key={item.id}>{ &item.name }</li>
+            }"#,
+        ).unwrap();
+        assert!(for_.body.keyed_only);
+
+        let expanded = for_.expand().to_string();
+        assert!(expanded.contains("VList :: keyed"));
+        assert!(expanded.contains(&quote!(ruukh::vdom::Key::new(item.id)).to_string()));
+    }
+
+    #[test]
+    fn should_parse_for_with_unkeyed_body() {
+        let for_: HtmlFor = syn::parse_str(
+            r#"for (index, item) in items.iter().enumerate() {
+                { index }": "{ item }
+            }"#,
+        ).unwrap();
+        assert!(!for_.body.keyed_only);
+        assert!(for_.expand().to_string().contains("list . push"));
+    }
+
+    #[test]
+    fn should_parse_match_arms() {
+        let match_: HtmlMatch = syn::parse_str(
+            r#"match status {
+                Status::Loading => <span>"Loading"</span>,
+                Status::Failed(ref err) | Status::Aborted(ref err) if err.is_fatal() => {
+                    "Failed: "{ err }
+                }
+                _ => {}
+            }"#,
+        ).unwrap();
+
+        assert_eq!(match_.arms.len(), 3);
+        assert_eq!(match_.arms[1].pats.len(), 2);
+        assert!(match_.arms[1].guard.is_some());
+        assert_eq!(match_.arms[2].body.flat_len, 0);
+    }
+
+    #[test]
+    fn should_not_parse_if_without_condition() {
+        assert!(syn::parse_str::<HtmlIf>(r#"if { "Empty" }"#).is_err());
+    }
+}
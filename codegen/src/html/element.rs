@@ -1,15 +1,19 @@
+use super::bind::{BindAttribute, Binding};
+use super::events::{event_type, EventModifier};
+use super::foreign::{is_camel_case_attribute, is_camel_case_tag, ATTRIBUTE_PREFIXES};
 use super::kw;
-use super::HtmlRoot;
+use super::{HtmlItem, HtmlRoot};
 use crate::suffix::{EVENT_SUFFIX, PROPS_SUFFIX};
 use heck::{CamelCase, KebabCase, SnakeCase};
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::{
     braced,
+    ext::IdentExt,
     parse::{Error, Parse, ParseStream, Result as ParseResult},
     punctuated::Punctuated,
     spanned::Spanned,
-    token, Token, {Expr, Ident},
+    token, AngleBracketedGenericArguments, Token, {Expr, Ident},
 };
 
 pub enum HtmlElement {
@@ -41,32 +45,68 @@ impl HtmlElement {
             HtmlElement::SelfClosing(ref el) => el.key(),
         }
     }
+
+    pub fn slot(&self) -> Option<&kw::slot> {
+        match self {
+            HtmlElement::Normal(ref el) => el.opening_tag.slot.as_ref(),
+            HtmlElement::SelfClosing(ref el) => el.tag.slot.as_ref(),
+        }
+    }
+
+    /// The name of the prop the element is passed as, when it is a slot.
+    fn slot_name(&self) -> String {
+        let tag_name = match self {
+            HtmlElement::Normal(ref el) => &el.opening_tag.tag_name,
+            HtmlElement::SelfClosing(ref el) => &el.tag.tag_name,
+        };
+        match tag_name {
+            TagName::Tag { ref name, .. } => name.to_snake_case(),
+            TagName::Component { .. } => unreachable!("A component cannot be a slot."),
+        }
+    }
 }
 
 pub struct NormalHtmlElement {
     pub opening_tag: OpeningTag,
     pub child: Box<HtmlRoot>,
+    /// The elements passed as named slots into the component.
+    pub slots: Vec<HtmlElement>,
     pub closing_tag: ClosingTag,
 }
 
 impl Parse for NormalHtmlElement {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
         let opening_tag: OpeningTag = input.parse()?;
-        let child: HtmlRoot = input.parse()?;
+        let mut items = HtmlRoot::parse_items(input)?;
         let closing_tag: ClosingTag = input.parse()?;
 
-        if opening_tag.tag_name.is_component() && child.flat_len != 0 {
-            return Err(Error::new(
-                closing_tag.tag_name.span(),
-                "Component children are not supported right now.",
-            ));
+        let mut slots: Vec<HtmlElement> = vec![];
+        if opening_tag.tag_name.is_component() {
+            let (slot_items, child_items) = items
+                .into_iter()
+                .partition::<Vec<_>, _>(|item| item.slot().is_some());
+            items = child_items;
+            for item in slot_items {
+                if let HtmlItem::Element(element) = item {
+                    let name = element.slot_name();
+                    if name == "children" || slots.iter().any(|slot| slot.slot_name() == name) {
+                        return Err(Error::new(
+                            element.slot().unwrap().span,
+                            format!("The slot `{}` is passed more than once.", name),
+                        ));
+                    }
+                    slots.push(*element);
+                }
+            }
         }
+        let child = HtmlRoot::group(items)?;
 
         let not_same = match (&opening_tag.tag_name, &closing_tag.tag_name) {
             (TagName::Tag { name: ref op, .. }, TagName::Tag { name: ref cl, .. }) => op != cl,
-            (TagName::Component { ident: ref op }, TagName::Component { ident: ref cl }) => {
-                op != cl
-            }
+            (
+                TagName::Component { ident: ref op, .. },
+                TagName::Component { ident: ref cl, .. },
+            ) => op != cl,
             _ => true,
         };
 
@@ -80,6 +120,7 @@ impl Parse for NormalHtmlElement {
         Ok(NormalHtmlElement {
             opening_tag,
             child: Box::new(child),
+            slots,
             closing_tag,
         })
     }
@@ -87,8 +128,25 @@ impl Parse for NormalHtmlElement {
 
 impl NormalHtmlElement {
     fn expand(&self) -> TokenStream {
-        let child_expanded = self.child.expand();
-        self.opening_tag.expand_with(&child_expanded)
+        if !self.opening_tag.tag_name.is_component() {
+            let child_expanded = self.child.expand();
+            return self.opening_tag.expand_with(&child_expanded);
+        }
+
+        let mut passed: Vec<_> = self
+            .slots
+            .iter()
+            .map(|slot| {
+                let slot_expanded = slot.expand();
+                let markup = quote! {
+                    ruukh::vdom::VNode::from(#slot_expanded)
+                };
+                (slot.slot_name(), markup)
+            }).collect();
+        if self.child.flat_len != 0 {
+            passed.push(("children".to_string(), self.child.expand()));
+        }
+        self.opening_tag.expand_component_with(passed)
     }
 
     pub fn key(&self) -> Option<&KeyAttribute> {
@@ -122,21 +180,30 @@ pub struct OpeningTag {
     pub lt: Token![<],
     pub tag_name: TagName,
     pub key: Option<KeyAttribute>,
+    pub slot: Option<kw::slot>,
     pub prop_attributes: Vec<HtmlAttribute>,
     pub event_attributes: Vec<HtmlAttribute>,
+    pub property_attributes: Vec<HtmlAttribute>,
+    pub binding: Binding,
     pub gt: Token![>],
 }
 
 impl Parse for OpeningTag {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
         let lt = input.parse()?;
-        let tag_name = input.parse()?;
+        let tag_name: TagName = input.parse()?;
         let mut key = None;
+        let mut slot: Option<kw::slot> = None;
 
         let mut attributes: Vec<HtmlAttribute> = vec![];
+        let mut binds: Vec<BindAttribute> = vec![];
         while !input.peek(Token![>]) {
             if input.peek(kw::key) {
                 key = Some(input.parse()?);
+            } else if kw::is_slot(input) {
+                slot = Some(input.parse()?);
+            } else if kw::is_bind(input) {
+                binds.push(input.parse()?);
             } else {
                 attributes.push(input.parse()?);
             }
@@ -144,9 +211,20 @@ impl Parse for OpeningTag {
 
         let gt = input.parse()?;
 
-        let (mut prop_attributes, mut event_attributes) = attributes
-            .into_iter()
-            .partition::<Vec<_>, _>(|attr| attr.at.is_none());
+        check_component_attributes(&tag_name, &attributes, &binds)?;
+        let binding = Binding::new(binds)?;
+
+        if let Some(ref slot) = slot {
+            if tag_name.is_component() {
+                return Err(Error::new(
+                    slot.span,
+                    "Only an html element can be passed as a slot.",
+                ));
+            }
+        }
+
+        let (mut prop_attributes, mut event_attributes, property_attributes) =
+            split_attributes(attributes);
 
         prop_attributes.sort_by(|l, r| l.key.name.cmp(&r.key.name));
         event_attributes.sort_by(|l, r| l.key.name.cmp(&r.key.name));
@@ -155,8 +233,11 @@ impl Parse for OpeningTag {
             lt,
             tag_name,
             key,
+            slot,
             prop_attributes,
             event_attributes,
+            property_attributes,
+            binding,
             gt,
         })
     }
@@ -171,11 +252,14 @@ impl OpeningTag {
                     .iter()
                     .map(|p| p.expand_as_prop_attribute().unwrap())
                     .collect();
-                let event_attributes: Vec<_> = self
+                let mut event_attributes: Vec<_> = self
                     .event_attributes
                     .iter()
                     .map(|e| e.expand_as_event_attribute().unwrap())
                     .collect();
+                event_attributes.extend(self.binding.expand_listeners());
+
+                let properties = expand_properties(&self.property_attributes, &self.binding);
 
                 quote! {
                     ruukh::vdom::velement::VElement::new(
@@ -183,32 +267,53 @@ impl OpeningTag {
                         vec![#(#prop_attributes),*],
                         vec![#(#event_attributes),*],
                         #child
-                    )
+                    )#properties
                 }
             }
-            TagName::Component { ref ident } => {
-                let prop_attributes: Vec<_> = self
-                    .prop_attributes
-                    .iter()
-                    .map(|p| p.expand_as_named_arg())
-                    .collect();
+            TagName::Component { .. } => {
+                unreachable!("The children of a component are passed as its props.")
+            }
+        }
+    }
 
-                let event_attributes: Vec<_> = self
-                    .event_attributes
-                    .iter()
-                    .map(|e| e.expand_as_named_arg())
-                    .collect();
+    fn expand_component_with(&self, passed: Vec<(String, TokenStream)>) -> TokenStream {
+        let (ident, generics) = match self.tag_name {
+            TagName::Component {
+                ref ident,
+                ref generics,
+            } => (ident, generics),
+            TagName::Tag { .. } => unreachable!("Only a component is passed markup."),
+        };
 
-                let props_ident = Ident::new(&format!("{}{}", ident, PROPS_SUFFIX), ident.span());
-                let event_ident = Ident::new(&format!("{}{}", ident, EVENT_SUFFIX), ident.span());
-                let span = ident.span();
-                quote_spanned!{span=>
-                    ruukh::vdom::vcomponent::VComponent::new::<#ident>(
-                        #props_ident!(#(#prop_attributes),*),
-                        #event_ident!(#(#event_attributes),*),
-                    )
-                }
-            }
+        let mut named_args: Vec<_> = self
+            .prop_attributes
+            .iter()
+            .map(|p| (p.key.name.to_snake_case(), p.expand_as_named_arg()))
+            .collect();
+        named_args.extend(passed.into_iter().map(|(name, markup)| {
+            let key = Ident::new(&name, Span::call_site());
+            let named_arg = quote! {
+                #key: ruukh::vdom::vslot::Children::new(#markup)
+            };
+            (name, named_arg)
+        }));
+        named_args.sort_by(|l, r| l.0.cmp(&r.0));
+        let prop_attributes: Vec<_> = named_args.into_iter().map(|(_, arg)| arg).collect();
+
+        let event_attributes: Vec<_> = self
+            .event_attributes
+            .iter()
+            .map(|e| e.expand_as_named_arg())
+            .collect();
+
+        let props_ident = Ident::new(&format!("{}{}", ident, PROPS_SUFFIX), ident.span());
+        let event_ident = Ident::new(&format!("{}{}", ident, EVENT_SUFFIX), ident.span());
+        let span = ident.span();
+        quote_spanned!{span=>
+            ruukh::vdom::vcomponent::VComponent::new::<#ident #generics>(
+                #props_ident!(#(#prop_attributes),*),
+                #event_ident!(#(#event_attributes),*),
+            )
         }
     }
 }
@@ -235,8 +340,11 @@ pub struct SelfClosingTag {
     pub lt: Token![<],
     pub tag_name: TagName,
     pub key: Option<KeyAttribute>,
+    pub slot: Option<kw::slot>,
     pub prop_attributes: Vec<HtmlAttribute>,
     pub event_attributes: Vec<HtmlAttribute>,
+    pub property_attributes: Vec<HtmlAttribute>,
+    pub binding: Binding,
     pub slash: Option<Token![/]>,
     pub gt: Token![>],
 }
@@ -246,11 +354,17 @@ impl Parse for SelfClosingTag {
         let lt = input.parse()?;
         let tag_name = input.parse()?;
         let mut key = None;
+        let mut slot = None;
 
         let mut attributes: Vec<HtmlAttribute> = vec![];
+        let mut binds: Vec<BindAttribute> = vec![];
         while !input.peek(Token![/]) && !input.peek(Token![>]) {
             if input.peek(kw::key) {
                 key = Some(input.parse()?);
+            } else if kw::is_slot(input) {
+                slot = Some(input.parse()?);
+            } else if kw::is_bind(input) {
+                binds.push(input.parse()?);
             } else {
                 attributes.push(input.parse()?);
             }
@@ -259,15 +373,21 @@ impl Parse for SelfClosingTag {
         let slash = input.parse()?;
         let gt = input.parse()?;
 
-        let (prop_attributes, event_attributes) =
-            attributes.into_iter().partition(|attr| attr.at.is_none());
+        check_component_attributes(&tag_name, &attributes, &binds)?;
+        let binding = Binding::new(binds)?;
+
+        let (prop_attributes, event_attributes, property_attributes) =
+            split_attributes(attributes);
 
         Ok(SelfClosingTag {
             lt,
             tag_name,
             key,
+            slot,
             prop_attributes,
             event_attributes,
+            property_attributes,
+            binding,
             slash,
             gt,
         })
@@ -283,23 +403,97 @@ impl SelfClosingTag {
                     .iter()
                     .map(|p| p.expand_as_prop_attribute().unwrap())
                     .collect();
-                let event_attributes: Vec<_> = self
+                let mut event_attributes: Vec<_> = self
                     .event_attributes
                     .iter()
                     .map(|e| e.expand_as_event_attribute().unwrap())
                     .collect();
+                event_attributes.extend(self.binding.expand_listeners());
+
+                let properties = expand_properties(&self.property_attributes, &self.binding);
 
                 quote! {
                     ruukh::vdom::velement::VElement::childless(
                         #name,
                         vec![#(#prop_attributes),*],
                         vec![#(#event_attributes),*]
-                    )
+                    )#properties
                 }
             }
-            _ => unreachable!("The spec specified self-closing tags are the only ones allowed."),
+            _ => unreachable!("A component cannot be a self-closing tag."),
+        }
+    }
+}
+
+/// The events of a component are not DOM events, so they cannot be modified,
+/// nor does a component have DOM properties or namespaced attributes.
+fn check_component_attributes(
+    tag_name: &TagName,
+    attributes: &[HtmlAttribute],
+    binds: &[BindAttribute],
+) -> ParseResult<()> {
+    if !tag_name.is_component() {
+        return Ok(());
+    }
+    if let Some(modifier) = attributes.iter().flat_map(|attr| attr.modifiers.first()).next() {
+        return Err(Error::new(
+            modifier.span,
+            "Modifiers are only allowed on the events of an element.",
+        ));
+    }
+    if let Some(dot) = attributes.iter().filter_map(|attr| attr.dot.as_ref()).next() {
+        return Err(Error::new(
+            dot.spans[0],
+            "Properties are only allowed on an element.",
+        ));
+    }
+    if let Some(bind) = binds.first() {
+        return Err(Error::new(
+            bind.span,
+            "Bindings are only allowed on an element.",
+        ));
+    }
+    if let Some(prefix) = attributes.iter().filter_map(|attr| attr.key.prefix.as_ref()).next() {
+        return Err(Error::new(
+            prefix.span(),
+            "Namespaced attributes are only allowed on an element.",
+        ));
+    }
+    Ok(())
+}
+
+/// Splits the attributes into the normal, the event and the property ones.
+fn split_attributes(
+    attributes: Vec<HtmlAttribute>,
+) -> (Vec<HtmlAttribute>, Vec<HtmlAttribute>, Vec<HtmlAttribute>) {
+    let (mut prop_attributes, mut event_attributes, mut property_attributes) =
+        (vec![], vec![], vec![]);
+    for attr in attributes {
+        if attr.at.is_some() {
+            event_attributes.push(attr);
+        } else if attr.dot.is_some() {
+            property_attributes.push(attr);
+        } else {
+            prop_attributes.push(attr);
         }
     }
+    (prop_attributes, event_attributes, property_attributes)
+}
+
+/// Expands the properties, along with the bound ones, into the call which
+/// sets them on an element, if there are any.
+fn expand_properties(property_attributes: &[HtmlAttribute], binding: &Binding) -> TokenStream {
+    let mut properties: Vec<_> = property_attributes
+        .iter()
+        .map(|p| p.expand_as_property_attribute().unwrap())
+        .collect();
+    properties.extend(binding.expand_properties());
+    if properties.is_empty() {
+        return quote!();
+    }
+    quote! {
+        .with_properties(vec![#(#properties),*])
+    }
 }
 
 pub struct KeyAttribute {
@@ -336,7 +530,11 @@ impl KeyAttribute {
 
 pub struct HtmlAttribute {
     pub at: Option<Token![@]>,
+    /// The `.` of a DOM property, like `.value`.
+    pub dot: Option<Token![.]>,
     pub key: AttributeName,
+    /// The modifiers of an event listener, like `.prevent` in `@click.prevent`.
+    pub modifiers: Vec<EventModifier>,
     pub eq: Token![=],
     pub brace: token::Brace,
     pub value: Expr,
@@ -344,15 +542,41 @@ pub struct HtmlAttribute {
 
 impl Parse for HtmlAttribute {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
-        let at = input.parse()?;
-        let key = input.parse()?;
+        let at: Option<Token![@]> = input.parse()?;
+        let dot: Option<Token![.]> = if at.is_none() { input.parse()? } else { None };
+        // The properties are named as they are in the DOM, like `selectedIndex`.
+        let key = if dot.is_some() {
+            let ident: Ident = input.parse()?;
+            AttributeName {
+                name: ident.to_string(),
+                prefix: None,
+            }
+        } else {
+            input.parse()?
+        };
+        match key.prefix {
+            Some(ref prefix) if at.is_some() => {
+                return Err(Error::new(
+                    prefix.span(),
+                    "An event name cannot be namespaced.",
+                ))
+            }
+            _ => {}
+        }
+        let modifiers = if at.is_some() {
+            EventModifier::parse_all(input, &key.name)?
+        } else {
+            vec![]
+        };
         let eq = input.parse()?;
         let content;
         let brace = braced!(content in input);
         let value = content.parse()?;
         Ok(HtmlAttribute {
             at,
+            dot,
             key,
+            modifiers,
             eq,
             brace,
             value,
@@ -362,7 +586,7 @@ impl Parse for HtmlAttribute {
 
 impl HtmlAttribute {
     fn expand_as_prop_attribute(&self) -> Option<TokenStream> {
-        if self.at.is_some() {
+        if self.at.is_some() || self.dot.is_some() {
             return None;
         }
         let key = &self.key.name;
@@ -377,9 +601,22 @@ impl HtmlAttribute {
         self.at?;
         let key = &self.key.name;
         let value = &self.value;
+        let event_type = event_type(key);
+        let modifiers: Vec<_> = self.modifiers.iter().map(EventModifier::expand).collect();
+
+        Some(quote! {
+            ruukh::vdom::velement::EventListener::typed::<#event_type>(#key, Box::new(#value))
+                #(#modifiers)*
+        })
+    }
+
+    fn expand_as_property_attribute(&self) -> Option<TokenStream> {
+        self.dot?;
+        let key = &self.key.name;
+        let value = &self.value;
 
         Some(quote! {
-            ruukh::vdom::velement::EventListener::new(#key, Box::new(#value))
+            ruukh::vdom::velement::Property::new(#key, #value)
         })
     }
 
@@ -394,12 +631,19 @@ impl HtmlAttribute {
 }
 
 pub enum TagName {
-    Tag { name: String, span: Span },
-    Component { ident: Ident },
+    Tag {
+        name: String,
+        span: Span,
+    },
+    Component {
+        ident: Ident,
+        /// The type arguments of a generic component, like `List<Row>`.
+        generics: Option<AngleBracketedGenericArguments>,
+    },
 }
 
 impl TagName {
-    fn is_component(&self) -> bool {
+    pub fn is_component(&self) -> bool {
         match self {
             TagName::Component { .. } => true,
             _ => false,
@@ -409,14 +653,18 @@ impl TagName {
     fn span(&self) -> Span {
         match self {
             TagName::Tag { ref span, .. } => span.clone(),
-            TagName::Component { ref ident } => ident.span(),
+            TagName::Component { ref ident, .. } => ident.span(),
         }
     }
 }
 
 impl Parse for TagName {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
-        let idents = input.call(Punctuated::<Ident, Token![-]>::parse_separated_nonempty)?;
+        // A keyword is a tag too, like the `use` of an svg.
+        let idents = Punctuated::<Ident, Token![-]>::parse_separated_nonempty_with(
+            input,
+            Ident::parse_any,
+        )?;
         let span = idents.span();
         let mut idents = idents.into_iter().collect::<Vec<_>>();
 
@@ -425,8 +673,14 @@ impl Parse for TagName {
             if idents.len() != 1 {
                 return Err(Error::new(span, "no dashes in a component tag allowed."));
             }
+            let generics = if input.peek(Token![<]) {
+                Some(input.parse()?)
+            } else {
+                None
+            };
             return Ok(TagName::Component {
                 ident: idents.swap_remove(0),
+                generics,
             });
         }
 
@@ -437,7 +691,7 @@ impl Parse for TagName {
             .join("-");
 
         let kebab_tag_name = tag_name.to_kebab_case();
-        if tag_name != kebab_tag_name {
+        if tag_name != kebab_tag_name && !is_camel_case_tag(&tag_name) {
             return Err(Error::new(
                 span,
                 &format!("tag name in kebab case only like {}.", kebab_tag_name),
@@ -452,28 +706,56 @@ impl Parse for TagName {
 }
 
 pub struct AttributeName {
+    /// The qualified name, along with the prefix if there is one.
     name: String,
+    /// The prefix of a namespaced attribute, like `xlink` in `xlink:href`.
+    prefix: Option<Ident>,
 }
 
 impl Parse for AttributeName {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
-        let idents = input.call(Punctuated::<Ident, Token![-]>::parse_separated_nonempty)?;
+        let prefix = if input.peek2(Token![:]) {
+            let prefix = input.call(Ident::parse_any)?;
+            if !ATTRIBUTE_PREFIXES.contains(&prefix.to_string().as_str()) {
+                return Err(Error::new(
+                    prefix.span(),
+                    format!(
+                        "Unknown attribute namespace `{}`. Expected one of xlink, xml or xmlns.",
+                        prefix
+                    ),
+                ));
+            }
+            input.parse::<Token![:]>()?;
+            Some(prefix)
+        } else {
+            None
+        };
+
+        // A keyword is an attribute too, like the `in` of an svg filter.
+        let idents = Punctuated::<Ident, Token![-]>::parse_separated_nonempty_with(
+            input,
+            Ident::parse_any,
+        )?;
         let span = idents.span();
-        let name = idents
+        let local_name = idents
             .into_iter()
             .map(|id| id.to_string())
             .collect::<Vec<_>>()
             .join("-");
 
-        let kebab_name = name.to_kebab_case();
-        if name != kebab_name {
+        let kebab_name = local_name.to_kebab_case();
+        if local_name != kebab_name && !is_camel_case_attribute(&local_name) {
             return Err(Error::new(
                 span,
                 &format!("attribute name in kebab case only like {}.", kebab_name),
             ));
         }
 
-        Ok(AttributeName { name })
+        let name = match prefix {
+            Some(ref prefix) => format!("{}:{}", prefix, local_name),
+            None => local_name,
+        };
+        Ok(AttributeName { name, prefix })
     }
 }
 
@@ -496,6 +778,33 @@ mod test {
         let _: NormalHtmlElement = syn::parse_str(r#"<div>"Hello"</div>"#).unwrap();
     }
 
+    #[test]
+    fn should_parse_component_with_children_and_slots() {
+        let parsed: NormalHtmlElement = syn::parse_str(
+            r#"<Card><header slot>"Title"</header>"Body"<br slot></Card>"#,
+        ).unwrap();
+        assert_eq!(parsed.child.flat_len, 1);
+        let slot_names: Vec<_> = parsed.slots.iter().map(HtmlElement::slot_name).collect();
+        assert_eq!(slot_names, vec!["header", "br"]);
+    }
+
+    #[test]
+    fn should_not_parse_slot_outside_of_component() {
+        assert!(syn::parse_str::<NormalHtmlElement>(r#"<div><p slot>"Hi"</p></div>"#).is_err());
+        assert!(syn::parse_str::<NormalHtmlElement>(r#"<Card><Title slot></Title></Card>"#).is_err());
+        assert!(
+            syn::parse_str::<NormalHtmlElement>(r#"<Card><p slot></p><p slot></p></Card>"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn should_parse_slot_attribute_with_value() {
+        let tag: OpeningTag = syn::parse_str(r#"<div slot={"named"}>"#).unwrap();
+        assert!(tag.slot.is_none());
+        assert_eq!(tag.prop_attributes.len(), 1);
+    }
+
     #[test]
     fn should_parse_opening_tag() {
         let _: OpeningTag = syn::parse_str("<div>").unwrap();
@@ -533,10 +842,103 @@ mod test {
         assert!(attr.at.is_some());
     }
 
+    #[test]
+    fn should_parse_event_attribute_with_modifiers() {
+        let attr: HtmlAttribute =
+            syn::parse_str(r#"@keydown.enter.prevent={Self::submit}"#).unwrap();
+        assert_eq!(attr.modifiers.len(), 2);
+
+        let expanded = attr.expand_as_event_attribute().unwrap().to_string();
+        assert!(expanded.ends_with(&quote!(.key("Enter").prevent()).to_string()));
+    }
+
+    #[test]
+    fn should_not_parse_modifiers_on_normal_attribute() {
+        assert!(syn::parse_str::<HtmlAttribute>(r#"name.prevent={"value"}"#).is_err());
+    }
+
+    #[test]
+    fn should_parse_property_attribute() {
+        let tag: SelfClosingTag =
+            syn::parse_str(r#"<input name={"done"} .checked={self.done}>"#).unwrap();
+        assert_eq!(tag.prop_attributes.len(), 1);
+        assert_eq!(tag.property_attributes.len(), 1);
+
+        let expanded = tag.expand().to_string();
+        assert!(expanded.contains(
+            &quote!(.with_properties(vec![
+                ruukh::vdom::velement::Property::new("checked", self.done)
+            ])).to_string()
+        ));
+    }
+
+    #[test]
+    fn should_parse_bindings_of_an_element() {
+        let tag: SelfClosingTag =
+            syn::parse_str(r#"<input bind:value={name} @focus={Self::on_focus}>"#).unwrap();
+        assert!(tag.binding.value.is_some());
+
+        let expanded = tag.expand().to_string();
+        assert!(expanded.contains("ruukh :: bind :: value"));
+        assert!(expanded.contains("with_properties"));
+        assert!(syn::parse_str::<SelfClosingTag>(r#"<Field bind:value={name}/>"#).is_err());
+    }
+
+    #[test]
+    fn should_not_parse_properties_on_components() {
+        assert!(syn::parse_str::<SelfClosingTag>(r#"<Field .value={name}/>"#).is_err());
+    }
+
+    #[test]
+    fn should_not_parse_modifiers_on_component_events() {
+        assert!(syn::parse_str::<SelfClosingTag>(r#"<Button @click.prevent={on_click}/>"#).is_err());
+        assert!(syn::parse_str::<OpeningTag>(r#"<Button @click.stop={on_click}>"#).is_err());
+    }
+
+    #[test]
+    fn should_parse_svg_elements_closed_with_slash() {
+        let parsed: HtmlElement = syn::parse_str(r#"<circle cx={"5"} r={"4"}/>"#).unwrap();
+        match parsed {
+            HtmlElement::SelfClosing(ref el) => assert_eq!(el.tag.prop_attributes.len(), 2),
+            HtmlElement::Normal(_) => panic!("Expected a self-closing element"),
+        }
+
+        let parsed: NormalHtmlElement = syn::parse_str(
+            r##"<svg viewBox={"0 0 10 10"}><circle r={"4"}/><use xlink:href={"#dot"}/></svg>"##,
+        ).unwrap();
+        assert_eq!(parsed.child.flat_len, 2);
+    }
+
+    #[test]
+    fn should_parse_camel_case_svg_names() {
+        let _: NormalHtmlElement = syn::parse_str(
+            r#"<linearGradient gradientUnits={"userSpaceOnUse"}></linearGradient>"#,
+        ).unwrap();
+        let _: SelfClosingTag = syn::parse_str(r#"<feOffset in={"SourceGraphic"}/>"#).unwrap();
+        assert!(syn::parse_str::<SelfClosingTag>(r#"<rect strokeWidth={"2"}/>"#).is_err());
+    }
+
+    #[test]
+    fn should_parse_namespaced_attributes() {
+        let attr: HtmlAttribute = syn::parse_str(r##"xlink:href={"#dot"}"##).unwrap();
+        assert_eq!(attr.key.name, "xlink:href");
+        assert_eq!(
+            attr.expand_as_prop_attribute().unwrap().to_string(),
+            quote!(ruukh::vdom::velement::Attribute::new("xlink:href", "#dot")).to_string()
+        );
+    }
+
+    #[test]
+    fn should_not_parse_unknown_or_misplaced_namespaces() {
+        assert!(syn::parse_str::<HtmlAttribute>(r##"svg:href={"#dot"}"##).is_err());
+        assert!(syn::parse_str::<HtmlAttribute>(r#"@xlink:click={on_click}"#).is_err());
+        assert!(syn::parse_str::<OpeningTag>(r##"<Icon xlink:href={"#dot"}>"##).is_err());
+    }
+
     #[test]
     fn should_parse_single_tag_name() {
         let parsed: TagName = syn::parse_str("Identifier").unwrap();
-        if let TagName::Component { ident } = parsed {
+        if let TagName::Component { ident, .. } = parsed {
             assert_eq!(ident, "Identifier");
         }
     }
@@ -544,8 +946,25 @@ mod test {
     #[test]
     fn should_parse_dashed_tag_name() {
         let parsed: TagName = syn::parse_str("first-second-third").unwrap();
-        if let TagName::Component { ident } = parsed {
+        if let TagName::Component { ident, .. } = parsed {
             assert_eq!(ident, "first-second-third");
         }
     }
+
+    #[test]
+    fn should_parse_generic_component_tag_name() {
+        let parsed: NormalHtmlElement =
+            syn::parse_str(r#"<List<Row> items={rows}></List>"#).unwrap();
+        match parsed.opening_tag.tag_name {
+            TagName::Component {
+                ident,
+                generics: Some(generics),
+            } => {
+                assert_eq!(ident, "List");
+                assert_eq!(generics.args.len(), 1);
+            }
+            _ => panic!("Expected a generic component"),
+        }
+        assert_eq!(parsed.opening_tag.prop_attributes.len(), 1);
+    }
 }
@@ -0,0 +1,325 @@
+//! The types of the DOM events, so that the `@event` listeners are passed
+//! the event with its own type, and the modifiers of the listeners.
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Error, Parse, ParseStream, Result as ParseResult},
+    Ident, Token,
+};
+
+/// The events along with their type, the rest are a plain `Event`.
+const EVENT_TYPES: &[(&str, &[&str])] = &[
+    (
+        "MouseEvent",
+        &[
+            "click",
+            "dblclick",
+            "auxclick",
+            "contextmenu",
+            "mousedown",
+            "mouseup",
+            "mousemove",
+            "mouseover",
+            "mouseout",
+            "mouseenter",
+            "mouseleave",
+        ],
+    ),
+    ("KeyboardEvent", &["keydown", "keyup", "keypress"]),
+    // The `input` events are dispatched as a plain `Event` by scripts often
+    // enough, so only the `beforeinput` events are typed.
+    ("InputEvent", &["beforeinput"]),
+    ("FocusEvent", &["focus", "blur", "focusin", "focusout"]),
+    ("WheelEvent", &["wheel"]),
+    (
+        "DragEvent",
+        &[
+            "drag",
+            "dragstart",
+            "dragend",
+            "dragenter",
+            "dragleave",
+            "dragover",
+            "drop",
+        ],
+    ),
+    (
+        "TouchEvent",
+        &["touchstart", "touchend", "touchmove", "touchcancel"],
+    ),
+    (
+        "PointerEvent",
+        &[
+            "pointerdown",
+            "pointerup",
+            "pointermove",
+            "pointerover",
+            "pointerout",
+            "pointerenter",
+            "pointerleave",
+            "pointercancel",
+            "gotpointercapture",
+            "lostpointercapture",
+        ],
+    ),
+    (
+        "CompositionEvent",
+        &["compositionstart", "compositionupdate", "compositionend"],
+    ),
+    ("ClipboardEvent", &["copy", "cut", "paste"]),
+    (
+        "AnimationEvent",
+        &["animationstart", "animationend", "animationiteration"],
+    ),
+    (
+        "TransitionEvent",
+        &[
+            "transitionrun",
+            "transitionstart",
+            "transitionend",
+            "transitioncancel",
+        ],
+    ),
+];
+
+/// The type of the event with the name. The events which are not known,
+/// along with the ones like `submit` which the DOM fires as a plain `Event`,
+/// are an `Event`.
+pub fn event_type(name: &str) -> TokenStream {
+    let ty = EVENT_TYPES
+        .iter()
+        .find(|(_, names)| names.contains(&name))
+        .map_or("Event", |(ty, _)| ty);
+    let ty = Ident::new(ty, Span::call_site());
+    quote! {
+        ruukh::event::#ty
+    }
+}
+
+/// Whether the DOM fires the events with the name as a `KeyboardEvent`.
+fn is_keyboard_event(name: &str) -> bool {
+    EVENT_TYPES
+        .iter()
+        .any(|(ty, names)| *ty == "KeyboardEvent" && names.contains(&name))
+}
+
+/// The keys usable as a modifier along with their `KeyboardEvent.key`.
+const KEY_MODIFIERS: &[(&str, &str)] = &[
+    ("enter", "Enter"),
+    ("esc", "Escape"),
+    ("escape", "Escape"),
+    ("tab", "Tab"),
+    ("space", " "),
+    ("up", "ArrowUp"),
+    ("down", "ArrowDown"),
+    ("left", "ArrowLeft"),
+    ("right", "ArrowRight"),
+    ("delete", "Delete"),
+    ("backspace", "Backspace"),
+];
+
+/// A modifier of an event listener, like `prevent` in `@click.prevent`.
+pub struct EventModifier {
+    pub kind: ModifierKind,
+    pub span: Span,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ModifierKind {
+    Prevent,
+    Stop,
+    Capture,
+    Passive,
+    Once,
+    Key(&'static str),
+}
+
+impl Parse for EventModifier {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        input.parse::<Token![.]>()?;
+        let ident: Ident = input.parse()?;
+        let name = ident.to_string();
+        let kind = match name.as_str() {
+            "prevent" => ModifierKind::Prevent,
+            "stop" => ModifierKind::Stop,
+            "capture" => ModifierKind::Capture,
+            "passive" => ModifierKind::Passive,
+            "once" => ModifierKind::Once,
+            _ => match KEY_MODIFIERS.iter().find(|(modifier, _)| *modifier == name) {
+                Some((_, key)) => ModifierKind::Key(key),
+                None => {
+                    return Err(Error::new(
+                        ident.span(),
+                        format!(
+                            "Unknown event modifier `{}`. Expected one of prevent, stop, \
+                             capture, passive, once or a key like enter.",
+                            name
+                        ),
+                    ))
+                }
+            },
+        };
+        Ok(EventModifier {
+            kind,
+            span: ident.span(),
+        })
+    }
+}
+
+impl EventModifier {
+    /// Parses the modifiers following the name of the event.
+    pub fn parse_all(input: ParseStream<'_>, event: &str) -> ParseResult<Vec<EventModifier>> {
+        let mut modifiers: Vec<EventModifier> = vec![];
+        while input.peek(Token![.]) {
+            let modifier: EventModifier = input.parse()?;
+            if modifiers.iter().any(|m| m.kind == modifier.kind) {
+                return Err(Error::new(modifier.span, "Duplicate event modifier."));
+            }
+            modifiers.push(modifier);
+        }
+
+        let has = |kind| modifiers.iter().any(|m| m.kind == kind);
+        if has(ModifierKind::Prevent) && has(ModifierKind::Passive) {
+            return Err(Error::new(
+                modifiers[0].span,
+                "A passive listener cannot prevent the default action.",
+            ));
+        }
+        let keys: Vec<_> = modifiers
+            .iter()
+            .filter(|m| matches!(m.kind, ModifierKind::Key(_)))
+            .collect();
+        if let Some(key) = keys.first() {
+            if keys.len() > 1 {
+                return Err(Error::new(
+                    key.span,
+                    "Only a single key modifier is allowed on a listener.",
+                ));
+            }
+            if !is_keyboard_event(event) {
+                return Err(Error::new(
+                    key.span,
+                    "Key modifiers are only allowed on the keyboard events, i.e. keydown, \
+                     keyup or keypress.",
+                ));
+            }
+            // The DOM removes a `once` listener on the first event, whichever
+            // the key, while the key is only checked by the listener itself.
+            if has(ModifierKind::Once) {
+                return Err(Error::new(
+                    key.span,
+                    "A listener with a key modifier cannot be `once`.",
+                ));
+            }
+        }
+        Ok(modifiers)
+    }
+
+    /// Expands into the builder method applying the modifier on an
+    /// `EventListener`.
+    pub fn expand(&self) -> TokenStream {
+        match self.kind {
+            ModifierKind::Prevent => quote!(.prevent()),
+            ModifierKind::Stop => quote!(.stop()),
+            ModifierKind::Capture => quote!(.capture()),
+            ModifierKind::Passive => quote!(.passive()),
+            ModifierKind::Once => quote!(.once()),
+            ModifierKind::Key(key) => quote!(.key(#key)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_type_the_known_events() {
+        assert_eq!(
+            event_type("click").to_string(),
+            quote!(ruukh::event::MouseEvent).to_string()
+        );
+        assert_eq!(
+            event_type("keydown").to_string(),
+            quote!(ruukh::event::KeyboardEvent).to_string()
+        );
+        assert_eq!(
+            event_type("dragover").to_string(),
+            quote!(ruukh::event::DragEvent).to_string()
+        );
+    }
+
+    #[test]
+    fn should_fall_back_to_event() {
+        assert_eq!(
+            event_type("submit").to_string(),
+            quote!(ruukh::event::Event).to_string()
+        );
+        assert_eq!(
+            event_type("input").to_string(),
+            quote!(ruukh::event::Event).to_string()
+        );
+        assert_eq!(
+            event_type("my-custom-event").to_string(),
+            quote!(ruukh::event::Event).to_string()
+        );
+    }
+
+    fn parse_modifiers_of(event: &str, modifiers: &str) -> ParseResult<Vec<EventModifier>> {
+        syn::parse::Parser::parse_str(
+            |input: ParseStream<'_>| EventModifier::parse_all(input, event),
+            modifiers,
+        )
+    }
+
+    fn parse_modifiers(modifiers: &str) -> ParseResult<Vec<EventModifier>> {
+        parse_modifiers_of("keydown", modifiers)
+    }
+
+    #[test]
+    fn should_parse_modifiers() {
+        let modifiers = parse_modifiers(".prevent.stop.capture.enter").unwrap();
+        let kinds: Vec<_> = modifiers.into_iter().map(|m| m.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ModifierKind::Prevent,
+                ModifierKind::Stop,
+                ModifierKind::Capture,
+                ModifierKind::Key("Enter"),
+            ]
+        );
+
+        let modifiers = parse_modifiers_of("click", ".stop.once").unwrap();
+        assert_eq!(modifiers.len(), 2);
+    }
+
+    #[test]
+    fn should_not_parse_once_along_with_a_key() {
+        assert!(parse_modifiers(".enter.once").is_err());
+        assert!(parse_modifiers(".once.enter").is_err());
+    }
+
+    #[test]
+    fn should_not_parse_keys_on_non_keyboard_events() {
+        assert!(parse_modifiers_of("click", ".enter").is_err());
+        assert!(parse_modifiers_of("input", ".prevent.esc").is_err());
+        assert!(parse_modifiers_of("keyup", ".esc").is_ok());
+    }
+
+    #[test]
+    fn should_not_parse_unknown_modifiers() {
+        assert!(parse_modifiers(".prevnt").is_err());
+    }
+
+    #[test]
+    fn should_not_parse_prevent_on_passive_listeners() {
+        assert!(parse_modifiers(".passive.prevent").is_err());
+    }
+
+    #[test]
+    fn should_not_parse_duplicate_modifiers() {
+        assert!(parse_modifiers(".stop.stop").is_err());
+        assert!(parse_modifiers(".enter.esc").is_err());
+    }
+}
@@ -0,0 +1,132 @@
+//! The names of the SVG and MathML elements and attributes which are not in
+//! kebab case, along with the namespaces of the prefixed attributes.
+
+/// The tags in camel case, like `linearGradient`.
+const CAMEL_CASE_TAGS: &[&str] = &[
+    "altGlyph",
+    "altGlyphDef",
+    "altGlyphItem",
+    "animateColor",
+    "animateMotion",
+    "animateTransform",
+    "clipPath",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "foreignObject",
+    "glyphRef",
+    "linearGradient",
+    "radialGradient",
+    "textPath",
+];
+
+/// The attributes in camel case, like `viewBox`.
+const CAMEL_CASE_ATTRIBUTES: &[&str] = &[
+    "attributeName",
+    "attributeType",
+    "baseFrequency",
+    "baseProfile",
+    "calcMode",
+    "clipPathUnits",
+    "definitionURL",
+    "diffuseConstant",
+    "edgeMode",
+    "filterUnits",
+    "glyphRef",
+    "gradientTransform",
+    "gradientUnits",
+    "kernelMatrix",
+    "kernelUnitLength",
+    "keyPoints",
+    "keySplines",
+    "keyTimes",
+    "lengthAdjust",
+    "limitingConeAngle",
+    "markerHeight",
+    "markerUnits",
+    "markerWidth",
+    "maskContentUnits",
+    "maskUnits",
+    "numOctaves",
+    "pathLength",
+    "patternContentUnits",
+    "patternTransform",
+    "patternUnits",
+    "pointsAtX",
+    "pointsAtY",
+    "pointsAtZ",
+    "preserveAlpha",
+    "preserveAspectRatio",
+    "primitiveUnits",
+    "refX",
+    "refY",
+    "repeatCount",
+    "repeatDur",
+    "requiredExtensions",
+    "requiredFeatures",
+    "specularConstant",
+    "specularExponent",
+    "spreadMethod",
+    "startOffset",
+    "stdDeviation",
+    "stitchTiles",
+    "surfaceScale",
+    "systemLanguage",
+    "tableValues",
+    "targetX",
+    "targetY",
+    "textLength",
+    "viewBox",
+    "viewTarget",
+    "xChannelSelector",
+    "yChannelSelector",
+    "zoomAndPan",
+];
+
+/// The prefixes of the attributes in a namespace, like `xlink:href`.
+pub const ATTRIBUTE_PREFIXES: &[&str] = &["xlink", "xml", "xmlns"];
+
+/// Whether the tag is a known one in camel case.
+pub fn is_camel_case_tag(name: &str) -> bool {
+    CAMEL_CASE_TAGS.contains(&name)
+}
+
+/// Whether the attribute is a known one in camel case.
+pub fn is_camel_case_attribute(name: &str) -> bool {
+    CAMEL_CASE_ATTRIBUTES.contains(&name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_know_the_camel_case_names() {
+        assert!(is_camel_case_tag("linearGradient"));
+        assert!(!is_camel_case_tag("lineargradient"));
+        assert!(is_camel_case_attribute("viewBox"));
+        assert!(!is_camel_case_attribute("strokeWidth"));
+    }
+}
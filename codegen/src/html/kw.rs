@@ -1,7 +1,11 @@
 //! Custom keywords used in the parser.
+use super::element::TagName;
+use proc_macro2::TokenTree;
 use syn::{custom_keyword, parse::ParseStream, Token};
 
+custom_keyword!(bind);
 custom_keyword!(key);
+custom_keyword!(slot);
 
 macro_rules! custom_keywords {
     ($($ident:ident),*) => {
@@ -21,10 +25,47 @@ macro_rules! is_self_closing {
     };
 }
 
+/// Whether a self-closing tag follows. It is either one of the void tags of
+/// html or an element closed with `/>`, like the `<circle/>` of an svg.
 pub fn is_self_closing(inp: ParseStream<'_>) -> bool {
     inp.peek(Token![<])
         && (is_self_closing!(
             inp is
             [area, base, br, col, embed, hr, img, input, link, meta, param, source, track, wbr]
-        ))
+        ) || is_closed_with_slash(inp))
+}
+
+/// Whether the tag which follows ends with `/>`. A component is never
+/// self-closing.
+fn is_closed_with_slash(inp: ParseStream<'_>) -> bool {
+    let fork = inp.fork();
+    if fork.parse::<Token![<]>().is_err() {
+        return false;
+    }
+    match fork.parse::<TagName>() {
+        Ok(ref tag_name) if !tag_name.is_component() => {}
+        _ => return false,
+    }
+    // The values of the attributes are within braces, so the first `>`
+    // outside of them closes the tag.
+    while !fork.is_empty() {
+        if fork.peek(Token![/]) && fork.peek2(Token![>]) {
+            return true;
+        }
+        if fork.peek(Token![>]) || fork.parse::<TokenTree>().is_err() {
+            return false;
+        }
+    }
+    false
+}
+
+/// Whether the `slot` flag follows, rather than an attribute named `slot`.
+pub fn is_slot(inp: ParseStream<'_>) -> bool {
+    inp.peek(slot) && !inp.peek2(Token![=]) && !inp.peek2(Token![-])
+}
+
+/// Whether a binding like `bind:value` follows, rather than an attribute
+/// named `bind`.
+pub fn is_bind(inp: ParseStream<'_>) -> bool {
+    inp.peek(bind) && inp.peek2(Token![:])
 }
@@ -6,8 +6,10 @@
 //! This lib defines `#[component]`, `#[derive(Lifecycle)]` and `html!` macros.
 extern crate proc_macro;
 
-use crate::{component::ComponentMeta, html::HtmlRoot};
-use proc_macro2::Span;
+use crate::{
+    component::{ComponentArgs, ComponentMeta, FnComponentMeta},
+    html::HtmlRoot,
+};
 use quote::quote;
 use syn::{parse::Error, parse_macro_input, spanned::Spanned, DeriveInput, Item};
 
@@ -27,10 +29,11 @@ mod suffix;
 #[proc_macro_derive(Lifecycle)]
 pub fn derive_lifecycle(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let ident = input.ident;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let expanded = quote! {
-        impl Lifecycle for #ident {}
+        impl #impl_generics Lifecycle for #ident #ty_generics #where_clause {}
     };
 
     expanded.into()
@@ -78,6 +81,44 @@ pub fn derive_lifecycle(input: proc_macro::TokenStream) -> proc_macro::TokenStre
 /// state field. If a `#[state]` or `#[state(default)]` is specified then the
 /// `Default` value of the field is used. If you want to provide a more
 /// specific value, then pass it by using `#[state(default = val)]` attribute.
+///
+/// A stateless component may be written as a function of its props instead,
+/// which renders the markup. It has no lifecycle and is not re-rendered
+/// unless its props change. The props taken by value are cloned for every
+/// render, while `&str` props are kept as `String`.
+/// # Example
+/// ```ignore,compile_fail
+/// #[component]
+/// fn Badge(text: &str, color: Color) -> Markup<Self> {
+///     html! {
+///         <span class={color.class()}>{ text }</span>
+///     }
+/// }
+/// ```
+///
+/// A component may be generic over types, which are carried through to its
+/// props, state and events types when they use them.
+/// # Example
+/// ```ignore,compile_fail
+/// #[component]
+/// struct List<T: Display + PartialEq + 'static> {
+///     items: Vec<T>,
+/// }
+/// ```
+/// The types are inferred from the props passed in `html!`, or they may be
+/// given as `<List<Row> items={rows}></List>`.
+///
+/// A component may be declared as an error boundary with
+/// `#[component(boundary)]`. It captures the errors of the components
+/// rendered within it in its `error_captured` lifecycle.
+/// # Example
+/// ```ignore,compile_fail
+/// #[component(boundary)]
+/// struct Boundary {
+///     #[state]
+///     failed: bool,
+/// }
+/// ```
 #[proc_macro_attribute]
 #[cfg_attr(
     feature = "cargo-clippy",
@@ -87,23 +128,20 @@ pub fn component(
     metadata: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    if !metadata.is_empty() {
-        return Error::new(
-            Span::call_site(),
-            "`#[component]` does not support attribute arguments.",
-        ).to_compile_error()
-        .into();
-    }
-
+    let args = parse_macro_input!(metadata as ComponentArgs);
     let input = parse_macro_input!(input as Item);
 
     let expanded = match input {
-        Item::Struct(struct_) => ComponentMeta::parse(struct_)
+        Item::Struct(struct_) => ComponentMeta::parse(struct_, args)
+            .map(|s| s.expand())
+            .unwrap_or_else(|e| e.to_compile_error()),
+        Item::Fn(fn_) => FnComponentMeta::parse(fn_, args)
             .map(|s| s.expand())
             .unwrap_or_else(|e| e.to_compile_error()),
-        _ => {
-            Error::new(input.span(), "Only structs are allowed to be Component").to_compile_error()
-        }
+        _ => Error::new(
+            input.span(),
+            "Only structs and functions are allowed to be Component",
+        ).to_compile_error(),
     };
 
     expanded.into()
@@ -126,11 +164,14 @@ pub fn component(
 /// ```
 ///
 /// ## Self-closing tags
-/// Only html specified self-closing tags can be self-closing tags.
+/// The html specified void tags are self-closing, with or without the `/`.
+/// Any other element, like an svg one, is self-closing when it ends with
+/// `/>`. A component cannot be self-closing.
 ///
 /// ```ignore,compile_fail
 /// html! {
 ///     <br>
+///     <circle r={"4"}/>
 /// }
 /// ```
 ///
@@ -149,6 +190,20 @@ pub fn component(
 /// }
 /// ```
 ///
+/// ## Component children
+/// The markup between the tags of a component is passed as its `children`
+/// prop, and an element marked as a `slot` is passed as the prop named after
+/// its tag. Both props are of the type `ruukh::vdom::vslot::Children`.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <Card>
+///         <header slot>"Title"</header>
+///         "Body of the card."
+///     </Card>
+/// }
+/// ```
+///
 /// ## List of tags
 /// ```ignore,compile_fail
 /// html! {
@@ -173,6 +228,143 @@ pub fn component(
 ///     "There are "{ count }" people."
 /// }
 /// ```
+///
+/// A `Vec` of markup, or of markup along with its key, is rendered as a list.
+/// So are the iterators of the `map`, `filter_map`, `filter`, `chain`, `rev`,
+/// `skip` and `take` adapters, along with the ones of a `Vec` or an `Option`.
+/// Any other iterator is to be collected into a `Markup` first. The key of
+/// the single element rendered by an item, like the `tr` below, keys the item.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <table>
+///         { self.rows.iter().map(|row| html! {
+///             <tr key={row.id}>{ &row.name }</tr>
+///         }) }
+///     </table>
+///     { self.by_id.values().map(Row::render).collect::<Markup<Self>>() }
+/// }
+/// ```
+///
+/// ## Event listeners
+/// The listener of a known DOM event is passed the event with its own type,
+/// like a `MouseEvent` for `@click` or a `KeyboardEvent` for `@keydown`. The
+/// rest of them, along with `@input`, are passed an `Event`. See
+/// `ruukh::event` for the types.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <button @click={|this: &Self, event: MouseEvent| this.clicked(event.button())}>
+///         "Click"
+///     </button>
+/// }
+/// ```
+///
+/// The modifiers after the event name change how it is listened to:
+/// `.prevent` and `.stop` prevent the default action and stop the propagation
+/// before the listener is invoked, `.capture`, `.passive` and `.once` are
+/// the options of the DOM listener, and a key like `.enter`, `.esc`, `.tab`,
+/// `.space`, `.up`, `.down`, `.left`, `.right`, `.delete` or `.backspace`
+/// only invokes the listener on that key. They are only allowed on elements.
+/// A key is only allowed on the keyboard events, and never along with
+/// `.once`, as the DOM would stop listening on the first key of any kind.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <form @submit.prevent={Self::on_submit}>
+///         <input @keydown.enter.stop={Self::on_enter}/>
+///     </form>
+///     <div @scroll.passive={Self::on_scroll}></div>
+/// }
+/// ```
+///
+/// ## DOM properties
+/// A `.` before the name sets a DOM property rather than an attribute. The
+/// property is compared against its live value in the DOM, so that a
+/// controlled input is set back to the state once the user has changed it.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <input .value={&self.name} @input={Self::on_input}/>
+///     <select .selectedIndex={self.selected}>{ self.options() }</select>
+///     <input .checked={self.done} .indeterminate={self.partial}/>
+/// }
+/// ```
+///
+/// ## Bindings
+/// `bind:value={field}` binds the value of an input to a `#[state]` field of
+/// the component, and `bind:checked={field}` binds whether it is checked. The
+/// value is a `String`, a number, or an `Option` of them which is `None` when
+/// the input is empty. A checked is a `bool`. The value which fails to parse
+/// is passed to the `bind:error` hook.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <input bind:value={name}/>
+///     <input bind:value={age} bind:error={Self::on_invalid_age}/>
+///     <input bind:checked={subscribed}/>
+/// }
+/// ```
+///
+/// ## SVG and MathML
+/// The elements within an `svg` or a `math` are created in its namespace, up
+/// until a `foreignObject` whose content is html again. Their camel case
+/// names, like `linearGradient` or `viewBox`, are kept as is, and the
+/// attributes prefixed with `xlink:`, `xml:` or `xmlns:` are set in their
+/// namespace.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <svg viewBox={"0 0 10 10"}>
+///         <defs>
+///             <circle id={"dot"} r={"4"}/>
+///         </defs>
+///         <use xlink:href={"#dot"} x={"5"} y={"5"}/>
+///     </svg>
+/// }
+/// ```
+///
+/// ## Conditionals
+/// A branch which renders nothing, including a missing `else`, renders no
+/// markup.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     if let Some(ref name) = self.name {
+///         "Hello "{ name }"!"
+///     } else if self.anonymous {
+///         "Hello stranger!"
+///     }
+/// }
+/// ```
+///
+/// ## Loops
+/// If the markup within a loop is keyed, the list it renders is keyed too.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <ul>
+///         for user in self.users.iter() {
+///             <li key={user.id}>{ &user.name }</li>
+///         }
+///     </ul>
+/// }
+/// ```
+///
+/// ## Matches
+/// An arm renders either a block of markup or a single item.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     match self.status {
+///         Status::Loading => <span>"Loading..."</span>,
+///         Status::Failed(ref err) => {
+///             "Failed with "{ err }
+///         }
+///         Status::Done => {}
+///     }
+/// }
+/// ```
 #[proc_macro]
 pub fn html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let parsed = parse_macro_input!(input as HtmlRoot);
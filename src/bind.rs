@@ -0,0 +1,215 @@
+//! Two-way binding of the form inputs to the state of a component.
+//!
+//! `bind:value={field}` on an input binds its value to the `#[state]` field,
+//! while `bind:checked={field}` binds whether a checkbox is checked. The input
+//! shows the value in the state and the value the user enters is written back
+//! into the state.
+//!
+//! ```ignore,compile_fail
+//! #[component]
+//! #[derive(Lifecycle)]
+//! struct Profile {
+//!     #[state]
+//!     name: String,
+//!     #[state]
+//!     age: Option<u8>,
+//!     #[state]
+//!     subscribed: bool,
+//! }
+//!
+//! impl Render for Profile {
+//!     fn render(&self) -> Markup<Self> {
+//!         html! {
+//!             <input bind:value={name}/>
+//!             <input bind:value={age} bind:error={Self::on_invalid_age}/>
+//!             <input bind:checked={subscribed}/>
+//!         }
+//!     }
+//! }
+//!
+//! impl Profile {
+//!     fn on_invalid_age(&self, error: BindError) {
+//!         // Show the error to the user.
+//!     }
+//! }
+//! ```
+//!
+//! A value which fails to parse, like a letter typed in a number field, is
+//! not written into the state. Instead, it is passed to the `bind:error` hook,
+//! if there is one.
+
+use crate::{
+    component::{Component, Render},
+    dom::{self, PropertyValue},
+    vdom::velement::EventListener,
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// A value which an input may be bound to with `bind:value`.
+pub trait BindValue: Sized + 'static {
+    /// The value of the input which shows it.
+    fn to_property(&self) -> PropertyValue;
+
+    /// Parses the value the user entered.
+    fn from_value(value: &str) -> Result<Self, String>;
+}
+
+impl BindValue for String {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::String(self.clone())
+    }
+
+    fn from_value(value: &str) -> Result<Self, String> {
+        Ok(value.to_string())
+    }
+}
+
+macro_rules! impl_bind_number {
+    (@impl $t:ty, $this:ident => $to_property:expr) => {
+        impl BindValue for $t {
+            fn to_property(&self) -> PropertyValue {
+                let $this = *self;
+                $to_property
+            }
+
+            fn from_value(value: &str) -> Result<Self, String> {
+                <$t as FromStr>::from_str(value.trim()).map_err(|err| err.to_string())
+            }
+        }
+    };
+    (numbers: $($t:ty),*) => {
+        $(impl_bind_number!(@impl $t, this => PropertyValue::Number(this as f64));)*
+    };
+    (strings: $($t:ty),*) => {
+        $(impl_bind_number!(@impl $t, this => PropertyValue::String(this.to_string()));)*
+    };
+}
+
+impl_bind_number!(numbers: i8, i16, i32, u8, u16, u32, f32, f64);
+// A `f64` cannot hold all of their values, so they are shown as strings.
+impl_bind_number!(strings: i64, isize, u64, usize);
+
+/// An empty input is `None`.
+impl<T: BindValue> BindValue for Option<T> {
+    fn to_property(&self) -> PropertyValue {
+        match self {
+            Some(ref value) => value.to_property(),
+            None => PropertyValue::String(String::new()),
+        }
+    }
+
+    fn from_value(value: &str) -> Result<Self, String> {
+        if value.trim().is_empty() {
+            Ok(None)
+        } else {
+            T::from_value(value).map(Some)
+        }
+    }
+}
+
+/// The value entered by the user which could not be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BindError {
+    /// The value as entered.
+    pub value: String,
+    /// Why it could not be parsed.
+    pub message: String,
+}
+
+impl Display for BindError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid value `{}`: {}", self.value, self.message)
+    }
+}
+
+/// Creates the listener of `bind:value`, which writes the parsed value of the
+/// input into the state.
+pub fn value<COMP: Render, T: BindValue>(
+    write: impl Fn(&mut <COMP as Component>::State, T) + 'static,
+    on_error: impl Fn(&COMP, BindError) + 'static,
+) -> EventListener<COMP> {
+    EventListener::new(
+        "input",
+        Box::new(move |this: &COMP, _| {
+            let value = match live_property("value") {
+                Some(PropertyValue::String(value)) => value,
+                _ => String::new(),
+            };
+            match T::from_value(&value) {
+                Ok(parsed) => write_state(this, &write, parsed),
+                Err(message) => on_error(this, BindError { value, message }),
+            }
+        }),
+    )
+}
+
+/// Creates the listener of `bind:checked`, which writes whether the input is
+/// checked into the state.
+pub fn checked<COMP: Render>(
+    write: impl Fn(&mut <COMP as Component>::State, bool) + 'static,
+) -> EventListener<COMP> {
+    EventListener::new(
+        "change",
+        Box::new(move |this: &COMP, _| {
+            let checked = live_property("checked") == Some(PropertyValue::Bool(true));
+            write_state(this, &write, checked);
+        }),
+    )
+}
+
+fn write_state<COMP: Render, T>(
+    this: &COMP,
+    write: &impl Fn(&mut <COMP as Component>::State, T),
+    value: T,
+) {
+    // The mutator may be invoked more than once, yet the value is moved in
+    // only once.
+    let mut value = Some(value);
+    this.set_state(|state| {
+        if let Some(value) = value.take() {
+            write(state, value);
+        }
+    });
+}
+
+/// Gets the live property of the element the listener is on.
+fn live_property(name: &str) -> Option<PropertyValue> {
+    let (dom, el) = dom::current_listener()?;
+    dom.get_property(&el, name)
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_bound_values() {
+        assert_eq!(String::from_value(" Ruukh "), Ok(" Ruukh ".to_string()));
+        assert_eq!(u8::from_value(" 42"), Ok(42));
+        assert!(u8::from_value("-1").is_err());
+        assert_eq!(f64::from_value("1."), Ok(1.0));
+        assert_eq!(Option::<i32>::from_value(""), Ok(None));
+        assert_eq!(Option::<i32>::from_value("7"), Ok(Some(7)));
+        assert!(Option::<i32>::from_value("seven").is_err());
+    }
+
+    #[test]
+    fn should_show_bound_values() {
+        assert_eq!(
+            "Ruukh".to_string().to_property(),
+            PropertyValue::String("Ruukh".to_string())
+        );
+        assert_eq!(5u8.to_property(), PropertyValue::Number(5.0));
+        assert_eq!(
+            (u64::max_value() - 1).to_property(),
+            PropertyValue::String("18446744073709551614".to_string())
+        );
+        assert_eq!(
+            None::<u8>.to_property(),
+            PropertyValue::String(String::new())
+        );
+    }
+}
@@ -60,7 +60,8 @@
 //! Note: Docs on component macros are located
 //! [here](../../ruukh_codegen/index.html).
 
-use crate::{Markup, MessageSender, Shared};
+use crate::{context, error::RenderError, Markup, MessageSender, Shared};
+use std::rc::Rc;
 
 /// Trait to define a component. You do not need to implement this trait. Auto
 /// implement this trait by using `#[component]` on a component struct (which
@@ -105,6 +106,15 @@ pub trait Component: 'static {
     /// name with `State`.
     type State: Default;
 
+    /// Whether the component is an error boundary, i.e. it captures the errors
+    /// of the components rendered within it.
+    ///
+    /// ## Internals
+    ///
+    /// It is set by the `#[component(boundary)]` attribute. See
+    /// [Lifecycle::error_captured](trait.Lifecycle.html#method.error_captured).
+    const BOUNDARY: bool = false;
+
     /// Creates a new component with the props, events and state passed to it.
     ///
     /// ## Internals
@@ -165,6 +175,32 @@ pub trait Component: 'static {
     /// differs from the state fields of the component. If they are different
     /// it then marks the state as dirty.
     fn set_state(&self, mutator: impl FnMut(&mut Self::State));
+
+    /// Provides a value of type `T` to all the components rendered within
+    /// this one, which may read it with [context](#method.context).
+    ///
+    /// It is usually provided in `render`, so that a changed value is provided
+    /// again on re-render. When the value differs from the one previously
+    /// provided, the components which read it are re-rendered.
+    ///
+    /// # Panics
+    /// When the component is neither being rendered nor is in one of its
+    /// lifecycle hooks.
+    fn provide<T: PartialEq + 'static>(&self, value: T) {
+        context::provide(value);
+    }
+
+    /// Reads the value of type `T` provided by the nearest ancestor. See the
+    /// [context](../context/index.html) module.
+    ///
+    /// The component is re-rendered whenever the value it read changes.
+    ///
+    /// # Panics
+    /// When the component is neither being rendered nor is in one of its
+    /// lifecycle hooks.
+    fn context<T: 'static>(&self) -> Option<Rc<T>> {
+        context::inject()
+    }
 }
 
 /// Stores the state of the component along with the flags to identify whether
@@ -241,6 +277,34 @@ pub trait Lifecycle: Component {
 
     /// Invoked when the component is removed from the DOM tree.
     fn destroyed(&self) {}
+
+    /// Invoked on an error boundary, declared with `#[component(boundary)]`,
+    /// when a component within it fails to render or panics (if panics
+    /// unwind).
+    ///
+    /// The failed markup is removed and the boundary is rendered again, so
+    /// that it may render a fallback markup based on the state set here. If
+    /// that fails as well, the error is passed on to the next boundary.
+    ///
+    /// # Example
+    /// ```
+    /// # #![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+    /// # use ruukh::{prelude::*, error::RenderError};
+    /// #
+    /// #[component(boundary)]
+    /// struct Boundary {
+    ///     #[state]
+    ///     failed: bool,
+    /// }
+    ///
+    /// impl Lifecycle for Boundary {
+    ///     fn error_captured(&self, _: &RenderError) {
+    ///         self.set_state(|state| state.failed = true);
+    ///     }
+    /// }
+    /// ```
+    #[allow(unused_variables)]
+    fn error_captured(&self, error: &RenderError) {}
 }
 
 /// Trait to render a view for the component.
@@ -0,0 +1,275 @@
+//! Context to pass values down the component tree without threading them
+//! through the props of every component in between.
+//!
+//! A component provides a value with
+//! [Component::provide](../component/trait.Component.html#method.provide)
+//! and any component rendered within it reads the value with
+//! [Component::context](../component/trait.Component.html#method.context).
+//! Whenever the provider provides a different value, the components which
+//! read it are re-rendered.
+//!
+//! # Example
+//! ```
+//! # #![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+//! # use ruukh::prelude::*;
+//! #
+//! #[derive(PartialEq)]
+//! enum Theme {
+//!     Light,
+//!     Dark,
+//! }
+//!
+//! #[component]
+//! #[derive(Lifecycle)]
+//! struct ThemeProvider {
+//!     #[state]
+//!     dark: bool,
+//! }
+//!
+//! impl Render for ThemeProvider {
+//!     fn render(&self) -> Markup<Self> {
+//!         self.provide(if self.dark { Theme::Dark } else { Theme::Light });
+//!         html! {
+//!             <Toolbar></Toolbar>
+//!         }
+//!     }
+//! }
+//!
+//! #[component]
+//! #[derive(Lifecycle)]
+//! struct Toolbar;
+//!
+//! impl Render for Toolbar {
+//!     fn render(&self) -> Markup<Self> {
+//!         let dark = self.context::<Theme>().map_or(false, |theme| *theme == Theme::Dark);
+//!         html! {
+//!             <div class={ if dark { "dark" } else { "light" } }></div>
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! The context is only available while a component is being rendered or is
+//! in one of its lifecycle hooks. To use it in an event handler, keep what is
+//! needed in the state of the component.
+
+use crate::MessageSender;
+use std::{
+    any::{Any, TypeId},
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::{Rc, Weak},
+};
+
+thread_local! {
+    /// The scopes of the components being rendered, innermost last.
+    static CURRENT: RefCell<Vec<Rc<Scope>>> = const { RefCell::new(vec![]) };
+}
+
+/// The context of a single component, i.e. the values it provides and
+/// whether any value it reads has changed.
+pub(crate) struct Scope {
+    /// The scope of the parent component.
+    parent: Option<Rc<Scope>>,
+    /// The values provided by the component, by their type.
+    provided: RefCell<HashMap<TypeId, Provided>>,
+    /// Whether a value read by the component has changed since its last
+    /// render.
+    dirty: Cell<bool>,
+    /// Whether the component is being walked, so that the components within
+    /// are yet to be walked.
+    walking: Cell<bool>,
+    /// The sender to notify the App when a read value changes.
+    rx_sender: MessageSender,
+}
+
+/// A provided value along with the scopes of the components which read it.
+struct Provided {
+    value: Rc<dyn Any>,
+    consumers: Vec<Weak<Scope>>,
+}
+
+impl Scope {
+    /// Creates the scope of a component which is about to be created within
+    /// the component currently being rendered.
+    pub(crate) fn new(rx_sender: MessageSender) -> Rc<Scope> {
+        Rc::new(Scope {
+            parent: Scope::current(),
+            provided: RefCell::new(HashMap::new()),
+            dirty: Cell::new(false),
+            walking: Cell::new(false),
+            rx_sender,
+        })
+    }
+
+    /// The scope of the component currently being rendered.
+    fn current() -> Option<Rc<Scope>> {
+        CURRENT.with(|current| current.borrow().last().cloned())
+    }
+
+    /// Runs the closure with the `scope` as the current one, so that the
+    /// component may provide and read the context.
+    pub(crate) fn enter<R>(scope: &Rc<Scope>, f: impl FnOnce() -> R) -> R {
+        /// Leaves the scope even if the closure panics.
+        struct Entered;
+
+        impl Drop for Entered {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.borrow_mut().pop());
+            }
+        }
+
+        CURRENT.with(|current| current.borrow_mut().push(scope.clone()));
+        let _entered = Entered;
+        f()
+    }
+
+    /// Runs the closure with the `scope` as the current one while the
+    /// component is walked. The components within which read a value it
+    /// provides anew are re-rendered by this very walk.
+    pub(crate) fn walk<R>(scope: &Rc<Scope>, f: impl FnOnce() -> R) -> R {
+        let was_walking = scope.walking.replace(true);
+        let walked = Scope::enter(scope, f);
+        scope.walking.set(was_walking);
+        walked
+    }
+
+    /// Whether a value read by the component changed since it was last asked.
+    pub(crate) fn take_dirty(&self) -> bool {
+        self.dirty.replace(false)
+    }
+
+    fn provide<T: PartialEq + 'static>(&self, value: T) {
+        let type_id = TypeId::of::<T>();
+        let mut provided = self.provided.borrow_mut();
+        if let Some(existing) = provided.get_mut(&type_id) {
+            if existing.value.downcast_ref::<T>() != Some(&value) {
+                existing.value = Rc::new(value);
+                existing
+                    .consumers
+                    .retain(|consumer| consumer.upgrade().is_some());
+                for consumer in existing.consumers.iter().filter_map(Weak::upgrade) {
+                    consumer.dirty.set(true);
+                    // The consumers are within the component, so an ongoing
+                    // walk of it reaches them anyway.
+                    if !self.walking.get() {
+                        consumer.rx_sender.do_react();
+                    }
+                }
+            }
+            return;
+        }
+        provided.insert(
+            type_id,
+            Provided {
+                value: Rc::new(value),
+                consumers: vec![],
+            },
+        );
+    }
+
+    /// Finds the value of type `T` provided by the nearest ancestor of the
+    /// `consumer` and subscribes the consumer to its changes.
+    fn inject<T: 'static>(consumer: &Rc<Scope>) -> Option<Rc<T>> {
+        let mut scope = consumer.parent.clone();
+        while let Some(ancestor) = scope {
+            if let Some(provided) = ancestor.provided.borrow_mut().get_mut(&TypeId::of::<T>()) {
+                let subscribed = provided.consumers.iter().any(|subscribed| {
+                    subscribed
+                        .upgrade()
+                        .is_some_and(|subscribed| Rc::ptr_eq(&subscribed, consumer))
+                });
+                if !subscribed {
+                    provided.consumers.push(Rc::downgrade(consumer));
+                }
+                return provided.value.clone().downcast::<T>().ok();
+            }
+            scope = ancestor.parent.clone();
+        }
+        None
+    }
+}
+
+/// Provides the value to the components rendered within the current one.
+pub(crate) fn provide<T: PartialEq + 'static>(value: T) {
+    Scope::current()
+        .expect(
+            "The context can only be provided while a component is being rendered or is in \
+             one of its lifecycle hooks.",
+        ).provide(value);
+}
+
+/// Reads the value provided by the nearest ancestor of the current component.
+pub(crate) fn inject<T: 'static>() -> Option<Rc<T>> {
+    let consumer = Scope::current().expect(
+        "The context can only be read while a component is being rendered or is in one of \
+         its lifecycle hooks.",
+    );
+    Scope::inject(&consumer)
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::scheduler::{Scheduler, Strategy};
+    use wasm_bindgen_test::*;
+
+    #[test]
+    fn should_read_the_nearest_provided_value() {
+        let root = Scope::new(MessageSender::void());
+        let leaf = Scope::enter(&root, || {
+            provide(1_u32);
+            provide("root");
+            let middle = Scope::new(MessageSender::void());
+            Scope::enter(&middle, || {
+                provide(2_u32);
+                Scope::new(MessageSender::void())
+            })
+        });
+
+        Scope::enter(&leaf, || {
+            assert_eq!(inject::<u32>(), Some(Rc::new(2)));
+            assert_eq!(inject::<&str>(), Some(Rc::new("root")));
+            assert_eq!(inject::<bool>(), None);
+        });
+    }
+
+    #[test]
+    fn should_mark_consumers_dirty_when_the_value_changes() {
+        let provider = Scope::new(MessageSender::void());
+        let consumer = Scope::enter(&provider, || {
+            provide(1_u32);
+            Scope::new(MessageSender::void())
+        });
+        Scope::enter(&consumer, || inject::<u32>());
+
+        Scope::enter(&provider, || provide(1_u32));
+        assert!(!consumer.take_dirty());
+
+        Scope::enter(&provider, || provide(2_u32));
+        assert!(consumer.take_dirty());
+        assert!(!consumer.take_dirty());
+    }
+
+    #[wasm_bindgen_test]
+    fn should_schedule_a_walk_only_when_provided_outside_of_one() {
+        let scheduler = Scheduler::new(Strategy::AnimationFrame);
+        let sender = || MessageSender(Some(scheduler.clone()));
+        let provider = Scope::new(sender());
+        let consumer = Scope::enter(&provider, || {
+            provide(1_u32);
+            Scope::new(sender())
+        });
+        Scope::enter(&consumer, || inject::<u32>());
+
+        Scope::walk(&provider, || provide(2_u32));
+        assert!(consumer.take_dirty());
+        assert_eq!(scheduler.pending(), 0);
+
+        // Like in the `mounted` hook of a hydrated component.
+        Scope::enter(&provider, || provide(3_u32));
+        assert!(consumer.take_dirty());
+        assert_eq!(scheduler.pending(), 1);
+        scheduler.stop();
+    }
+}
@@ -1,6 +1,295 @@
-use crate::{component::Render, MessageSender, Shared};
+//! The DOM on which the VDOM is patched.
+//!
+//! The VDOM never talks to the DOM directly, instead it goes through a
+//! [DOMBackend](trait.DOMBackend.html). The App runs on the browser with the
+//! [WebDOM](web/struct.WebDOM.html) backend, while the
+//! [MemoryDOM](memory/struct.MemoryDOM.html) backend keeps the DOM in memory
+//! so that the patches can be inspected natively, without a browser.
+
+use self::delegation::Delegator;
+use crate::{component::Render, error::RenderError, MessageSender, Shared};
+use std::{
+    any::Any,
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
 use wasm_bindgen::prelude::JsValue;
-use web_sys::Node;
+use web_sys::Event;
+
+pub mod delegation;
+pub mod memory;
+pub mod web;
+
+/// A node in the DOM of a backend.
+///
+/// The VDOM does not care what the node actually is. It only stores it and
+/// passes it back to the backend which created it.
+#[derive(Clone)]
+pub struct Node(Rc<dyn Any>);
+
+impl Node {
+    /// Wraps a backend specific node.
+    pub fn new<T: Any>(node: T) -> Node {
+        Node(Rc::new(node))
+    }
+
+    /// Gets the backend specific node, if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+}
+
+/// An event listener attached to a node by a backend.
+///
+/// It is handed back to the backend to stop listening.
+pub struct Listener(Box<dyn Any>);
+
+impl Listener {
+    /// Wraps a backend specific listener.
+    pub fn new<T: Any>(listener: T) -> Listener {
+        Listener(Box::new(listener))
+    }
+
+    /// Gets the backend specific listener, if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+}
+
+/// The options an event listener is added with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ListenerOptions {
+    /// Listens to the event while it captures down to the target, rather
+    /// than while it bubbles up.
+    pub capture: bool,
+    /// Promises that the listener never prevents the default action, so that
+    /// the browser need not wait for it, like while scrolling.
+    pub passive: bool,
+    /// Stops listening after the first event.
+    pub once: bool,
+}
+
+/// The value of a DOM property of an element.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    /// A string property, like the `value` of an input.
+    String(String),
+    /// A boolean property, like the `checked` of a checkbox.
+    Bool(bool),
+    /// A number property, like the `selectedIndex` of a select.
+    Number(f64),
+}
+
+/// The namespace of the HTML elements.
+pub const HTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+/// The namespace of the SVG elements, i.e. `svg` and the ones within.
+pub const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+/// The namespace of the MathML elements, i.e. `math` and the ones within.
+pub const MATHML_NAMESPACE: &str = "http://www.w3.org/1998/Math/MathML";
+/// The namespace of the `xlink:*` attributes, like `xlink:href`.
+pub const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+/// The namespace of the `xml:*` attributes, like `xml:lang`.
+pub const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+/// The namespace of the `xmlns:*` attributes, like `xmlns:xlink`.
+pub const XMLNS_NAMESPACE: &str = "http://www.w3.org/2000/xmlns/";
+
+/// The kind of an existing node along with what it holds. Used to verify
+/// the existing DOM while hydrating.
+#[derive(Debug, PartialEq, Eq)]
+pub enum NodeKind {
+    /// An element with its lowercased tag name.
+    Element(String),
+    /// A text node with its content.
+    Text(String),
+    /// A comment node with its content.
+    Comment(String),
+    /// Any other kind of node.
+    Other,
+}
+
+/// Trait to implement a DOM on which the VDOM may be patched.
+///
+/// All the nodes passed to the backend are the ones it created itself or
+/// the one the App is mounted upon.
+pub trait DOMBackend {
+    /// Creates a detached element with the given tag.
+    fn create_element(&self, tag: &str) -> Result<Node, JsValue>;
+
+    /// Creates a detached element with the given tag in the namespace, like
+    /// the SVG one.
+    fn create_element_ns(&self, namespace: &str, tag: &str) -> Result<Node, JsValue>;
+
+    /// Gets the namespace of the element. There is none if it is not an
+    /// element.
+    fn namespace_uri(&self, node: &Node) -> Option<String>;
+
+    /// Creates a detached text node.
+    fn create_text_node(&self, content: &str) -> Result<Node, JsValue>;
+
+    /// Creates a detached comment node.
+    fn create_comment(&self, content: &str) -> Result<Node, JsValue>;
+
+    /// Inserts (or moves) the `node` in the `parent` before the `next` node.
+    /// Appends it at the end when there is no `next` node.
+    fn insert_before(&self, parent: &Node, node: &Node, next: Option<&Node>)
+        -> Result<(), JsValue>;
+
+    /// Removes the `node` from the `parent`.
+    fn remove_child(&self, parent: &Node, node: &Node) -> Result<(), JsValue>;
+
+    /// Replaces the content of a text or a comment node.
+    fn set_text_content(&self, node: &Node, content: &str) -> Result<(), JsValue>;
+
+    /// Sets an attribute on an element.
+    fn set_attribute(&self, el: &Node, name: &str, value: &str) -> Result<(), JsValue>;
+
+    /// Removes an attribute from an element.
+    fn remove_attribute(&self, el: &Node, name: &str) -> Result<(), JsValue>;
+
+    /// Sets an attribute in the namespace on an element, like `xlink:href`.
+    /// The `name` is the qualified one, along with its prefix.
+    fn set_attribute_ns(
+        &self,
+        el: &Node,
+        namespace: &str,
+        name: &str,
+        value: &str,
+    ) -> Result<(), JsValue>;
+
+    /// Removes an attribute in the namespace from an element. The
+    /// `local_name` is the one without its prefix.
+    fn remove_attribute_ns(&self, el: &Node, namespace: &str, local_name: &str)
+        -> Result<(), JsValue>;
+
+    /// Sets a property of an element, like the `value` of an input.
+    fn set_property(&self, el: &Node, name: &str, value: &PropertyValue) -> Result<(), JsValue>;
+
+    /// Gets the live value of a property of an element. There is none if it
+    /// is not a string, a boolean or a number.
+    fn get_property(&self, el: &Node, name: &str) -> Option<PropertyValue>;
+
+    /// Starts listening to the `type_` events on the element.
+    fn add_event_listener(
+        &self,
+        el: &Node,
+        type_: &str,
+        handler: Box<dyn Fn(Event)>,
+        options: ListenerOptions,
+    ) -> Result<Listener, JsValue>;
+
+    /// Stops the listener added by `add_event_listener`.
+    fn remove_event_listener(
+        &self,
+        el: &Node,
+        type_: &str,
+        listener: &Listener,
+    ) -> Result<(), JsValue>;
+
+    /// Gets the first child of the node.
+    fn first_child(&self, node: &Node) -> Option<Node>;
+
+    /// Gets the next sibling of the node.
+    fn next_sibling(&self, node: &Node) -> Option<Node>;
+
+    /// Gets the parent of the node.
+    fn parent_node(&self, node: &Node) -> Option<Node>;
+
+    /// Gets the node the event was dispatched to.
+    fn event_target(&self, event: &Event) -> Option<Node>;
+
+    /// Prevents the default action of the event.
+    fn prevent_default(&self, event: &Event);
+
+    /// Stops the propagation of the event past the element it is on.
+    fn stop_propagation(&self, event: &Event);
+
+    /// Whether the propagation of the event is stopped.
+    fn is_propagation_stopped(&self, event: &Event) -> bool;
+
+    /// Gets the key of a keyboard event, as named by `KeyboardEvent.key`.
+    /// There is none for the other events.
+    fn event_key(&self, event: &Event) -> Option<String>;
+
+    /// Clones the event, so that it may be passed to one more handler.
+    fn clone_event(&self, event: &Event) -> Event;
+
+    /// Tags the element with an id, by which it is recognized when it is the
+    /// target of an event, or one of its ancestors. Used by the delegated
+    /// event listeners.
+    fn set_node_id(&self, el: &Node, id: u32) -> Result<(), JsValue>;
+
+    /// Gets the id the node was tagged with.
+    fn node_id(&self, node: &Node) -> Option<u32>;
+
+    /// Gets the kind of the node.
+    fn node_kind(&self, node: &Node) -> NodeKind;
+
+    /// Splits a text node at the byte `offset` of its content. The node keeps
+    /// the content before the offset and the rest is inserted as a new text
+    /// node right after it, which is returned.
+    fn split_text(&self, node: &Node, offset: usize) -> Result<Node, JsValue>;
+
+    /// Reports a non-fatal problem found while patching.
+    fn warn(&self, message: &str);
+}
+
+thread_local! {
+    /// The DOM along with the element of the listener being invoked.
+    static CURRENT_LISTENER: RefCell<Option<(Weak<dyn DOMBackend>, Node)>> = RefCell::new(None);
+}
+
+/// Invokes the listener on the element, so that it may read the element with
+/// `current_listener`.
+pub(crate) fn listen_on<R>(
+    dom: &Weak<dyn DOMBackend>,
+    el: &Node,
+    listener: impl FnOnce() -> R,
+) -> R {
+    let previous =
+        CURRENT_LISTENER.with(|current| current.replace(Some((dom.clone(), el.clone()))));
+    let result = listener();
+    CURRENT_LISTENER.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
+/// The DOM along with the element of the listener being invoked, if any.
+pub(crate) fn current_listener() -> Option<(Rc<dyn DOMBackend>, Node)> {
+    CURRENT_LISTENER.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .and_then(|(dom, el)| dom.upgrade().map(|dom| (dom, el.clone())))
+    })
+}
+
+/// The handles of a running App which the VDOM requires while patching.
+#[derive(Clone)]
+pub(crate) struct Runtime {
+    /// The DOM on which the VDOM is patched.
+    pub(crate) dom: Rc<dyn DOMBackend>,
+    /// The sender to notify the App of state changes.
+    pub(crate) rx_sender: MessageSender,
+    /// The delegator of the event listeners, when they are delegated to the
+    /// mount element.
+    pub(crate) delegator: Option<Rc<Delegator>>,
+}
+
+impl Runtime {
+    /// Creates a new runtime.
+    pub(crate) fn new(dom: Rc<dyn DOMBackend>, rx_sender: MessageSender) -> Runtime {
+        Runtime {
+            dom,
+            rx_sender,
+            delegator: None,
+        }
+    }
+
+    /// Delegates the event listeners to the element the App is mounted on.
+    pub(crate) fn delegate_to(mut self, root: &Node) -> Runtime {
+        self.delegator = Some(Delegator::new(self.dom.clone(), root.clone()));
+        self
+    }
+}
 
 /// Trait to patch the DOM to reflect the VDOM structure.
 pub(crate) trait DOMPatch
@@ -20,8 +309,8 @@ where
         parent: &Self::Node,
         next: Option<&Self::Node>,
         render_ctx: Shared<Self::RenderContext>,
-        rx_sender: MessageSender,
-    ) -> Result<(), JsValue>;
+        rt: &Runtime,
+    ) -> Result<(), RenderError>;
 
     /// Patches the DOM by diffing the VDOM `Self` with Older VDOM.
     fn patch(
@@ -30,16 +319,39 @@ where
         parent: &Self::Node,
         next: Option<&Self::Node>,
         render_ctx: Shared<Self::RenderContext>,
-        rx_sender: MessageSender,
-    ) -> Result<(), JsValue>;
+        rt: &Runtime,
+    ) -> Result<(), RenderError>;
 
     /// Reappends already existing Node in its correct place to reflect the
     /// current VDOM.
-    fn reorder(&self, parent: &Self::Node, next: Option<&Self::Node>) -> Result<(), JsValue>;
+    fn reorder(
+        &self,
+        parent: &Self::Node,
+        next: Option<&Self::Node>,
+        rt: &Runtime,
+    ) -> Result<(), RenderError>;
 
     /// Removes the VDOM from the actual DOM.
-    fn remove(&self, parent: &Self::Node) -> Result<(), JsValue>;
+    fn remove(&self, parent: &Self::Node, rt: &Runtime) -> Result<(), RenderError>;
 
     /// Gets the node value of the DOM attached VDOM.
     fn node(&self) -> Option<&Node>;
 }
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::{memory::MemoryDOM, web::WebDOM, *};
+
+    /// A runtime to patch on the browser DOM.
+    pub(crate) fn web_runtime() -> Runtime {
+        Runtime::new(Rc::new(WebDOM::new()), crate::message_sender())
+    }
+
+    /// A runtime to patch on an in-memory DOM, along with the DOM itself to
+    /// inspect the patches.
+    pub(crate) fn memory_runtime() -> (Rc<MemoryDOM>, Runtime) {
+        let dom = Rc::new(MemoryDOM::new());
+        let rt = Runtime::new(dom.clone(), MessageSender::void());
+        (dom, rt)
+    }
+}
@@ -0,0 +1,404 @@
+//! Delegation of the event listeners to the element an App is mounted on.
+//!
+//! By default, every `@event` listener of an element is a DOM listener of its
+//! own. With [EventMode::Delegated](enum.EventMode.html), a single DOM listener
+//! per event type is added on the mount element instead. When an event
+//! bubbles up to it, the handlers of the elements from the target up to the
+//! mount element are invoked in order, as if they were listening themselves.
+//! `stopPropagation` stops at the element it was called on and
+//! `current_target_as` gets the element the handler belongs to.
+//!
+//! So, a list with thousands of rows does not allocate a JS closure for every
+//! one of them.
+//!
+//! Only the listeners of the events known to bubble, like `click` or `input`,
+//! are delegated. The listeners of any other event, like `focus` or
+//! `waiting`, and the listeners with options, i.e. `.capture`, `.passive` or
+//! `.once`, are DOM listeners of their own. They are invoked before the
+//! delegated ones, as the event reaches the mount element only after it has
+//! bubbled through them.
+
+use super::{DOMBackend, Listener, ListenerOptions, Node};
+use fnv::FnvHashMap;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::prelude::JsValue;
+use web_sys::Event;
+
+/// How the event listeners of an App are attached to the DOM.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EventMode {
+    /// Every listener is a DOM listener on its own element. This is the
+    /// default.
+    #[default]
+    Direct,
+    /// The listeners are delegated to a single DOM listener per event type on
+    /// the element the App is mounted on.
+    Delegated,
+}
+
+/// The events which bubble up to the mount element, so they may be delegated.
+const BUBBLING: &[&str] = &[
+    "click",
+    "dblclick",
+    "auxclick",
+    "contextmenu",
+    "mousedown",
+    "mouseup",
+    "mousemove",
+    "mouseover",
+    "mouseout",
+    "keydown",
+    "keyup",
+    "keypress",
+    "input",
+    "beforeinput",
+    "change",
+    "submit",
+    "reset",
+    "select",
+    "focusin",
+    "focusout",
+    "wheel",
+    "drag",
+    "dragstart",
+    "dragend",
+    "dragenter",
+    "dragleave",
+    "dragover",
+    "drop",
+    "touchstart",
+    "touchend",
+    "touchmove",
+    "touchcancel",
+    "pointerdown",
+    "pointerup",
+    "pointermove",
+    "pointerover",
+    "pointerout",
+    "pointercancel",
+    "gotpointercapture",
+    "lostpointercapture",
+    "copy",
+    "cut",
+    "paste",
+    "compositionstart",
+    "compositionupdate",
+    "compositionend",
+    "animationstart",
+    "animationend",
+    "animationiteration",
+    "transitionrun",
+    "transitionstart",
+    "transitionend",
+    "transitioncancel",
+];
+
+/// The handler invoked for the events on an element. It is swapped on every
+/// patch, so that whatever invokes it is kept as is.
+pub(crate) type HandlerSlot = Rc<RefCell<Rc<dyn Fn(Event)>>>;
+
+thread_local! {
+    /// The next id to tag an element with. It is shared by all the Apps, so
+    /// that the elements of an App nested in another are never mistaken.
+    static NEXT_ID: Cell<u32> = const { Cell::new(0) };
+}
+
+fn next_id() -> u32 {
+    NEXT_ID.with(|next| {
+        let id = next.get();
+        next.set(id + 1);
+        id
+    })
+}
+
+/// Dispatches the events on the mount element to the handlers of the
+/// elements within.
+pub(crate) struct Delegator {
+    dom: Rc<dyn DOMBackend>,
+    /// The element the App is mounted on.
+    root: Node,
+    /// The handlers of the tagged elements, by the event type. An element
+    /// may have more than a single handler of a type, like the one of a
+    /// `bind:value` along with an `@input` listener.
+    handlers: RefCell<HashMap<String, FnvHashMap<u32, Vec<HandlerSlot>>>>,
+    /// The DOM listeners on the mount element, by the event type.
+    root_listeners: RefCell<HashMap<String, Listener>>,
+    /// A handle to itself to be invoked by the DOM listeners.
+    this: RefCell<Weak<Delegator>>,
+}
+
+impl Delegator {
+    /// Creates a delegator for the App mounted on the root.
+    pub(crate) fn new(dom: Rc<dyn DOMBackend>, root: Node) -> Rc<Delegator> {
+        let delegator = Rc::new(Delegator {
+            dom,
+            root,
+            handlers: RefCell::new(HashMap::new()),
+            root_listeners: RefCell::new(HashMap::new()),
+            this: RefCell::new(Weak::new()),
+        });
+        *delegator.this.borrow_mut() = Rc::downgrade(&delegator);
+        delegator
+    }
+
+    /// Whether the listener of the event type with the options is delegated.
+    pub(crate) fn delegates(&self, type_: &str, options: ListenerOptions) -> bool {
+        options == ListenerOptions::default() && BUBBLING.contains(&type_)
+    }
+
+    /// Registers the handler of the `type_` events on the element.
+    pub(crate) fn register(
+        &self,
+        el: &Node,
+        type_: &str,
+        handler: HandlerSlot,
+    ) -> Result<(), JsValue> {
+        let id = self.tag(el)?;
+
+        if !self.root_listeners.borrow().contains_key(type_) {
+            // The mount element may be an element of an outer App, which has
+            // tagged it already.
+            let root = &self.root;
+            self.tag(root)?;
+            let this = self.this.borrow().clone();
+            let event_type = type_.to_string();
+            let listener = self.dom.add_event_listener(
+                root,
+                type_,
+                Box::new(move |event| {
+                    if let Some(this) = this.upgrade() {
+                        this.dispatch(&event_type, event);
+                    }
+                }),
+                ListenerOptions::default(),
+            )?;
+            self.root_listeners
+                .borrow_mut()
+                .insert(type_.to_string(), listener);
+        }
+
+        self.handlers
+            .borrow_mut()
+            .entry(type_.to_string())
+            .or_default()
+            .entry(id)
+            .or_default()
+            .push(handler);
+        Ok(())
+    }
+
+    /// Unregisters the handler of the `type_` events on the element, leaving
+    /// the other handlers of the element as is. The DOM listener on the mount
+    /// element is removed along with the last handler of its type.
+    pub(crate) fn unregister(
+        &self,
+        el: &Node,
+        type_: &str,
+        handler: &HandlerSlot,
+    ) -> Result<(), JsValue> {
+        let id = match self.dom.node_id(el) {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+        let is_last = {
+            let mut handlers = self.handlers.borrow_mut();
+            match handlers.get_mut(type_) {
+                Some(of_type) => {
+                    let is_empty = match of_type.get_mut(&id) {
+                        Some(of_el) => {
+                            of_el.retain(|slot| !Rc::ptr_eq(slot, handler));
+                            of_el.is_empty()
+                        }
+                        None => false,
+                    };
+                    if is_empty {
+                        of_type.remove(&id);
+                    }
+                    of_type.is_empty()
+                }
+                None => false,
+            }
+        };
+        if is_last {
+            self.handlers.borrow_mut().remove(type_);
+            let listener = self.root_listeners.borrow_mut().remove(type_);
+            if let Some(listener) = listener {
+                self.dom
+                    .remove_event_listener(&self.root, type_, &listener)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Invokes the handlers of the elements from the target up to the mount
+    /// element, until the propagation is stopped.
+    fn dispatch(&self, type_: &str, event: Event) {
+        let root_id = self.dom.node_id(&self.root);
+        let mut node = self.dom.event_target(&event);
+        while let Some(current) = node {
+            let id = self.dom.node_id(&current);
+            if id.is_some() && id == root_id {
+                break;
+            }
+            let slots = id.and_then(|id| {
+                self.handlers
+                    .borrow()
+                    .get(type_)
+                    .and_then(|of_type| of_type.get(&id).cloned())
+            });
+            if let Some(slots) = slots {
+                // All the handlers of the element are invoked, as the
+                // propagation stops only after it.
+                for slot in slots {
+                    // Cloned out of the slot, so that the handler may be
+                    // swapped while it is being invoked.
+                    let handler = slot.borrow().clone();
+                    handler(self.dom.clone_event(&event));
+                }
+                if self.dom.is_propagation_stopped(&event) {
+                    break;
+                }
+            }
+            node = self.dom.parent_node(&current);
+        }
+    }
+
+    /// Gets the id the element is tagged with, tagging it first if it is not.
+    fn tag(&self, el: &Node) -> Result<u32, JsValue> {
+        match self.dom.node_id(el) {
+            Some(id) => Ok(id),
+            None => {
+                let id = next_id();
+                self.dom.set_node_id(el, id)?;
+                Ok(id)
+            }
+        }
+    }
+
+    /// The count of the handlers of the `type_` events on the element.
+    #[cfg(test)]
+    pub(crate) fn handler_count(&self, el: &Node, type_: &str) -> usize {
+        let id = match self.dom.node_id(el) {
+            Some(id) => id,
+            None => return 0,
+        };
+        self.handlers
+            .borrow()
+            .get(type_)
+            .and_then(|of_type| of_type.get(&id))
+            .map_or(0, Vec::len)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::dom::memory::MemoryDOM;
+
+    fn handler() -> HandlerSlot {
+        Rc::new(RefCell::new(Rc::new(|_: Event| {})))
+    }
+
+    #[test]
+    fn should_add_a_single_listener_per_event_type() {
+        let dom = Rc::new(MemoryDOM::new());
+        let root = dom.container();
+        let delegator = Delegator::new(dom.clone(), root.clone());
+        let first = dom.create_element("button").unwrap();
+        let second = dom.create_element("button").unwrap();
+
+        let (first_click, second_click, second_keydown) = (handler(), handler(), handler());
+        delegator
+            .register(&first, "click", first_click.clone())
+            .unwrap();
+        delegator
+            .register(&second, "click", second_click.clone())
+            .unwrap();
+        delegator
+            .register(&second, "keydown", second_keydown.clone())
+            .unwrap();
+
+        assert_eq!(dom.listener_count(&root, "click"), 1);
+        assert_eq!(dom.listener_count(&root, "keydown"), 1);
+        assert_eq!(dom.listener_count(&first, "click"), 0);
+        assert_ne!(dom.node_id(&first), dom.node_id(&second));
+
+        delegator.unregister(&first, "click", &first_click).unwrap();
+        assert_eq!(dom.listener_count(&root, "click"), 1);
+        delegator.unregister(&second, "click", &second_click).unwrap();
+        assert_eq!(dom.listener_count(&root, "click"), 0);
+        assert_eq!(dom.listener_count(&root, "keydown"), 1);
+    }
+
+    #[test]
+    fn should_keep_every_handler_of_the_same_type_on_an_element() {
+        let dom = Rc::new(MemoryDOM::new());
+        let root = dom.container();
+        let delegator = Delegator::new(dom.clone(), root.clone());
+        let button = dom.create_element("button").unwrap();
+        let (first, second) = (handler(), handler());
+
+        delegator.register(&button, "click", first.clone()).unwrap();
+        delegator.register(&button, "click", second.clone()).unwrap();
+        assert_eq!(delegator.handler_count(&button, "click"), 2);
+
+        delegator.unregister(&button, "click", &first).unwrap();
+        assert_eq!(delegator.handler_count(&button, "click"), 1);
+        assert_eq!(dom.listener_count(&root, "click"), 1);
+
+        delegator.unregister(&button, "click", &second).unwrap();
+        assert_eq!(delegator.handler_count(&button, "click"), 0);
+        assert_eq!(dom.listener_count(&root, "click"), 0);
+    }
+
+    #[test]
+    fn should_not_delegate_listeners_with_options_or_non_bubbling_events() {
+        let dom = Rc::new(MemoryDOM::new());
+        let delegator = Delegator::new(dom.clone(), dom.container());
+
+        assert!(delegator.delegates("click", ListenerOptions::default()));
+        assert!(delegator.delegates("input", ListenerOptions::default()));
+        for type_ in &["focus", "invalid", "waiting", "close", "my-custom-event"] {
+            assert!(!delegator.delegates(type_, ListenerOptions::default()));
+        }
+        assert!(!delegator.delegates(
+            "click",
+            ListenerOptions {
+                once: true,
+                ..Default::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn should_keep_the_id_of_a_mount_element_of_an_outer_app() {
+        let dom = Rc::new(MemoryDOM::new());
+        let container = dom.container();
+        let mount = dom.create_element("div").unwrap();
+        let button = dom.create_element("button").unwrap();
+        dom.insert_before(&container, &mount, None).unwrap();
+        dom.insert_before(&mount, &button, None).unwrap();
+        let outer = Delegator::new(dom.clone(), container.clone());
+        let inner = Delegator::new(dom.clone(), mount.clone());
+        let invoked = Rc::new(RefCell::new(vec![]));
+        let recording = |name: &'static str| -> HandlerSlot {
+            let invoked = invoked.clone();
+            Rc::new(RefCell::new(Rc::new(move |_: Event| {
+                invoked.borrow_mut().push(name)
+            })))
+        };
+
+        outer.register(&mount, "click", recording("outer")).unwrap();
+        let mount_id = dom.node_id(&mount);
+        inner.register(&button, "click", recording("inner")).unwrap();
+
+        assert_eq!(dom.node_id(&mount), mount_id);
+        assert_eq!(outer.handler_count(&mount, "click"), 1);
+        dom.dispatch(&button, "click");
+        assert_eq!(*invoked.borrow(), vec!["inner", "outer"]);
+    }
+}
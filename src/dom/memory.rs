@@ -0,0 +1,795 @@
+//! An in-memory DOM backend.
+//!
+//! It is a bare bones DOM which only keeps the tree of nodes along with their
+//! attributes and listeners. Every mutation on it is counted, so that the
+//! patches of the VDOM can be tested natively with `cargo test`.
+//!
+//! The events cannot be created natively, so the listeners are passed a
+//! placeholder `Event`. It is only to be handed back to the DOM, which knows
+//! the event it stands for while it is being dispatched.
+
+use super::{
+    DOMBackend, Listener, ListenerOptions, Node, NodeKind, PropertyValue, HTML_NAMESPACE,
+};
+use crate::{
+    ssr::{escape_attribute, escape_comment, escape_text},
+    vdom::velement::VOID_TAGS,
+};
+use indexmap::IndexMap;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::{prelude::JsValue, JsCast};
+use web_sys::Event;
+
+/// A DOM which lives in memory.
+///
+/// # Example
+/// ```
+/// use ruukh::dom::{memory::MemoryDOM, DOMBackend};
+///
+/// let dom = MemoryDOM::new();
+/// let container = dom.container();
+/// let text = dom.create_text_node("Hello World!").unwrap();
+/// dom.insert_before(&container, &text, None).unwrap();
+///
+/// assert_eq!(dom.inner_html(&container), "Hello World!");
+/// assert_eq!(dom.mutations().inserted, 1);
+/// ```
+#[derive(Default)]
+pub struct MemoryDOM {
+    nodes: RefCell<Vec<MemoryNode>>,
+    mutations: Cell<Mutations>,
+    warnings: RefCell<Vec<String>>,
+    next_listener: Cell<usize>,
+    /// The events being dispatched, the innermost one last.
+    dispatching: RefCell<Vec<Dispatch>>,
+}
+
+/// The count of mutations done on a `MemoryDOM`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Mutations {
+    /// Nodes created.
+    pub created: usize,
+    /// Nodes inserted or moved.
+    pub inserted: usize,
+    /// Nodes removed.
+    pub removed: usize,
+    /// Text/Comment contents replaced.
+    pub texts_set: usize,
+    /// Attributes set.
+    pub attributes_set: usize,
+    /// Attributes removed.
+    pub attributes_removed: usize,
+    /// Properties set.
+    pub properties_set: usize,
+    /// Event listeners added.
+    pub listeners_added: usize,
+    /// Event listeners removed.
+    pub listeners_removed: usize,
+}
+
+impl Mutations {
+    /// Total count of all the mutations.
+    pub fn total(&self) -> usize {
+        self.created
+            + self.inserted
+            + self.removed
+            + self.texts_set
+            + self.attributes_set
+            + self.attributes_removed
+            + self.properties_set
+            + self.listeners_added
+            + self.listeners_removed
+    }
+}
+
+/// The index of the node in the `MemoryDOM`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct NodeId(usize);
+
+/// The index of the listener in the `MemoryDOM`.
+struct ListenerId(usize);
+
+/// An event being dispatched on a `MemoryDOM`.
+struct Dispatch {
+    target: NodeId,
+    key: Option<String>,
+    /// Whether the listener being invoked is passive.
+    passive: bool,
+    propagation_stopped: bool,
+    default_prevented: bool,
+}
+
+/// A listener on an element, with its event type and id.
+type MemoryListener = (String, usize, ListenerOptions, Rc<dyn Fn(Event)>);
+
+struct MemoryNode {
+    kind: MemoryNodeKind,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    /// The id it is tagged with.
+    tag_id: Option<u32>,
+}
+
+enum MemoryNodeKind {
+    Element {
+        tag: String,
+        namespace: String,
+        /// The values of the attributes along with their namespace, by their
+        /// qualified name.
+        attributes: IndexMap<String, (String, Option<String>)>,
+        properties: IndexMap<String, PropertyValue>,
+        listeners: Vec<MemoryListener>,
+    },
+    Text(String),
+    Comment(String),
+}
+
+impl MemoryDOM {
+    /// Creates an empty DOM.
+    pub fn new() -> MemoryDOM {
+        Default::default()
+    }
+
+    /// Creates a `div` to mount the VDOM on. It is not counted as a mutation.
+    pub fn container(&self) -> Node {
+        let id = self.push(MemoryNodeKind::Element {
+            tag: "div".to_string(),
+            namespace: HTML_NAMESPACE.to_string(),
+            attributes: IndexMap::new(),
+            properties: IndexMap::new(),
+            listeners: vec![],
+        });
+        Node::new(id)
+    }
+
+    /// Gets the count of mutations done since creation or the last reset.
+    pub fn mutations(&self) -> Mutations {
+        self.mutations.get()
+    }
+
+    /// Resets the count of mutations.
+    pub fn reset_mutations(&self) {
+        self.mutations.set(Mutations::default());
+    }
+
+    /// Gets all the warnings reported so far.
+    pub fn warnings(&self) -> Vec<String> {
+        self.warnings.borrow().clone()
+    }
+
+    /// Gets the markup of all the children of the node.
+    pub fn inner_html(&self, node: &Node) -> String {
+        let nodes = self.nodes.borrow();
+        let mut html = String::new();
+        for child in nodes[id(node).0].children.iter() {
+            Self::write_html(&nodes, *child, &mut html);
+        }
+        html
+    }
+
+    /// Gets the count of listeners on the element for the given event type.
+    pub fn listener_count(&self, el: &Node, type_: &str) -> usize {
+        match self.nodes.borrow()[id(el).0].kind {
+            MemoryNodeKind::Element { ref listeners, .. } => {
+                listeners.iter().filter(|(ty, ..)| ty == type_).count()
+            }
+            _ => 0,
+        }
+    }
+
+    /// Gets the namespace of the attribute with the qualified name on the
+    /// element, if it is in one.
+    pub fn attribute_namespace(&self, el: &Node, name: &str) -> Option<String> {
+        match self.nodes.borrow()[id(el).0].kind {
+            MemoryNodeKind::Element { ref attributes, .. } => {
+                attributes.get(name).and_then(|(_, namespace)| namespace.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the options of the listeners on the element for the given event
+    /// type.
+    pub fn listener_options(&self, el: &Node, type_: &str) -> Vec<ListenerOptions> {
+        match self.nodes.borrow()[id(el).0].kind {
+            MemoryNodeKind::Element { ref listeners, .. } => listeners
+                .iter()
+                .filter(|(ty, ..)| ty == type_)
+                .map(|(_, _, options, _)| *options)
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Dispatches an event of the type on the node, as `dispatchEvent` does.
+    /// The event is captured down from the root to the node and then bubbles
+    /// back up to the root, invoking the listeners on its way. Returns `false`
+    /// if a listener prevented its default action.
+    pub fn dispatch(&self, target: &Node, type_: &str) -> bool {
+        self.dispatch_event(target, type_, None)
+    }
+
+    /// Dispatches a keyboard event of the type with the key, like "Enter", on
+    /// the node. See [dispatch](#method.dispatch).
+    pub fn dispatch_key(&self, target: &Node, type_: &str, key: &str) -> bool {
+        self.dispatch_event(target, type_, Some(key.to_string()))
+    }
+
+    fn dispatch_event(&self, target: &Node, type_: &str, key: Option<String>) -> bool {
+        let target = id(target);
+        let mut path = vec![target];
+        while let Some(parent) = self.nodes.borrow()[path[path.len() - 1].0].parent {
+            path.push(parent);
+        }
+
+        self.dispatching.borrow_mut().push(Dispatch {
+            target,
+            key,
+            passive: false,
+            propagation_stopped: false,
+            default_prevented: false,
+        });
+        let capturing = path.iter().rev().map(|node| (*node, true));
+        let bubbling = path.iter().map(|node| (*node, false));
+        for (node, capture) in capturing.chain(bubbling) {
+            self.invoke_listeners(node, type_, capture);
+            if self.with_dispatch(|dispatch| dispatch.propagation_stopped) {
+                break;
+            }
+        }
+        let dispatch = self.dispatching.borrow_mut().pop().unwrap();
+        !dispatch.default_prevented
+    }
+
+    fn invoke_listeners(&self, node: NodeId, type_: &str, capture: bool) {
+        // Cloned out, as the listeners may mutate the DOM.
+        let listeners: Vec<_> = match self.nodes.borrow()[node.0].kind {
+            MemoryNodeKind::Element { ref listeners, .. } => listeners
+                .iter()
+                .filter(|(ty, _, options, _)| ty == type_ && options.capture == capture)
+                .map(|(_, id, options, handler)| (*id, *options, handler.clone()))
+                .collect(),
+            _ => vec![],
+        };
+        for (listener_id, options, handler) in listeners {
+            // A listener removed by the ones before it is not invoked.
+            let is_listening = match self.nodes.borrow_mut()[node.0].kind {
+                MemoryNodeKind::Element {
+                    ref mut listeners, ..
+                } => {
+                    let is_listening = listeners.iter().any(|(_, id, ..)| *id == listener_id);
+                    if options.once {
+                        listeners.retain(|(_, id, ..)| *id != listener_id);
+                    }
+                    is_listening
+                }
+                _ => false,
+            };
+            if is_listening {
+                self.with_dispatch(|dispatch| dispatch.passive = options.passive);
+                handler(JsValue::UNDEFINED.unchecked_into());
+            }
+        }
+    }
+
+    /// Accesses the innermost event being dispatched.
+    fn with_dispatch<R>(&self, access: impl FnOnce(&mut Dispatch) -> R) -> R {
+        let mut dispatching = self.dispatching.borrow_mut();
+        let dispatch = dispatching
+            .last_mut()
+            .expect("No event is being dispatched on the memory DOM.");
+        access(dispatch)
+    }
+
+    fn write_html(nodes: &[MemoryNode], id: NodeId, html: &mut String) {
+        let node = &nodes[id.0];
+        match node.kind {
+            MemoryNodeKind::Element {
+                ref tag,
+                ref attributes,
+                ..
+            } => {
+                html.push('<');
+                html.push_str(tag);
+                for (name, (value, _)) in attributes.iter() {
+                    html.push_str(&format!(" {}=\"{}\"", name, escape_attribute(value)));
+                }
+                html.push('>');
+                if !VOID_TAGS.contains(&tag.as_str()) {
+                    for child in node.children.iter() {
+                        Self::write_html(nodes, *child, html);
+                    }
+                    html.push_str(&format!("</{}>", tag));
+                }
+            }
+            MemoryNodeKind::Text(ref content) => html.push_str(&escape_text(content)),
+            MemoryNodeKind::Comment(ref content) => {
+                html.push_str(&format!("<!--{}-->", escape_comment(content)))
+            }
+        }
+    }
+
+    fn push(&self, kind: MemoryNodeKind) -> NodeId {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(MemoryNode {
+            kind,
+            parent: None,
+            children: vec![],
+            tag_id: None,
+        });
+        NodeId(nodes.len() - 1)
+    }
+
+    fn mutate(&self, count: impl FnOnce(&mut Mutations)) {
+        let mut mutations = self.mutations.get();
+        count(&mut mutations);
+        self.mutations.set(mutations);
+    }
+
+    fn create_element_in(&self, namespace: &str, tag: &str) -> Node {
+        self.mutate(|m| m.created += 1);
+        Node::new(self.push(MemoryNodeKind::Element {
+            tag: tag.to_string(),
+            namespace: namespace.to_string(),
+            attributes: IndexMap::new(),
+            properties: IndexMap::new(),
+            listeners: vec![],
+        }))
+    }
+
+    fn insert_attribute(&self, el: &Node, name: &str, value: &str, namespace: Option<&str>) {
+        self.mutate(|m| m.attributes_set += 1);
+        if let MemoryNodeKind::Element {
+            ref mut attributes, ..
+        } = self.nodes.borrow_mut()[id(el).0].kind
+        {
+            attributes.insert(
+                name.to_string(),
+                (value.to_string(), namespace.map(str::to_string)),
+            );
+        }
+    }
+
+    fn detach(nodes: &mut [MemoryNode], node: NodeId) {
+        if let Some(parent) = nodes[node.0].parent.take() {
+            nodes[parent.0].children.retain(|child| *child != node);
+        }
+    }
+}
+
+fn id(node: &Node) -> NodeId {
+    *node
+        .downcast_ref::<NodeId>()
+        .expect("The node does not belong to a memory DOM.")
+}
+
+impl DOMBackend for MemoryDOM {
+    fn create_element(&self, tag: &str) -> Result<Node, JsValue> {
+        Ok(self.create_element_in(HTML_NAMESPACE, tag))
+    }
+
+    fn create_element_ns(&self, namespace: &str, tag: &str) -> Result<Node, JsValue> {
+        Ok(self.create_element_in(namespace, tag))
+    }
+
+    fn namespace_uri(&self, node: &Node) -> Option<String> {
+        match self.nodes.borrow()[id(node).0].kind {
+            MemoryNodeKind::Element { ref namespace, .. } => Some(namespace.clone()),
+            _ => None,
+        }
+    }
+
+    fn create_text_node(&self, content: &str) -> Result<Node, JsValue> {
+        self.mutate(|m| m.created += 1);
+        Ok(Node::new(
+            self.push(MemoryNodeKind::Text(content.to_string())),
+        ))
+    }
+
+    fn create_comment(&self, content: &str) -> Result<Node, JsValue> {
+        self.mutate(|m| m.created += 1);
+        Ok(Node::new(
+            self.push(MemoryNodeKind::Comment(content.to_string())),
+        ))
+    }
+
+    fn insert_before(
+        &self,
+        parent: &Node,
+        node: &Node,
+        next: Option<&Node>,
+    ) -> Result<(), JsValue> {
+        self.mutate(|m| m.inserted += 1);
+        let (parent, node) = (id(parent), id(node));
+        let mut nodes = self.nodes.borrow_mut();
+        Self::detach(&mut nodes, node);
+
+        let index = match next {
+            Some(next) => {
+                let next = id(next);
+                nodes[parent.0]
+                    .children
+                    .iter()
+                    .position(|child| *child == next)
+                    .expect("The next node is not a child of the parent.")
+            }
+            None => nodes[parent.0].children.len(),
+        };
+        nodes[parent.0].children.insert(index, node);
+        nodes[node.0].parent = Some(parent);
+        Ok(())
+    }
+
+    fn remove_child(&self, parent: &Node, node: &Node) -> Result<(), JsValue> {
+        self.mutate(|m| m.removed += 1);
+        let node = id(node);
+        let mut nodes = self.nodes.borrow_mut();
+        assert!(
+            nodes[node.0].parent == Some(id(parent)),
+            "The node is not a child of the parent."
+        );
+        Self::detach(&mut nodes, node);
+        Ok(())
+    }
+
+    fn set_text_content(&self, node: &Node, content: &str) -> Result<(), JsValue> {
+        self.mutate(|m| m.texts_set += 1);
+        match self.nodes.borrow_mut()[id(node).0].kind {
+            MemoryNodeKind::Text(ref mut text) | MemoryNodeKind::Comment(ref mut text) => {
+                *text = content.to_string();
+            }
+            MemoryNodeKind::Element { .. } => {
+                panic!("Only the content of a text or a comment node may be set.")
+            }
+        }
+        Ok(())
+    }
+
+    fn set_attribute(&self, el: &Node, name: &str, value: &str) -> Result<(), JsValue> {
+        self.insert_attribute(el, name, value, None);
+        Ok(())
+    }
+
+    fn remove_attribute(&self, el: &Node, name: &str) -> Result<(), JsValue> {
+        self.mutate(|m| m.attributes_removed += 1);
+        if let MemoryNodeKind::Element {
+            ref mut attributes, ..
+        } = self.nodes.borrow_mut()[id(el).0].kind
+        {
+            attributes.retain(|key, _| key != name);
+        }
+        Ok(())
+    }
+
+    fn set_attribute_ns(
+        &self,
+        el: &Node,
+        namespace: &str,
+        name: &str,
+        value: &str,
+    ) -> Result<(), JsValue> {
+        self.insert_attribute(el, name, value, Some(namespace));
+        Ok(())
+    }
+
+    fn remove_attribute_ns(
+        &self,
+        el: &Node,
+        namespace: &str,
+        local_name: &str,
+    ) -> Result<(), JsValue> {
+        self.mutate(|m| m.attributes_removed += 1);
+        if let MemoryNodeKind::Element {
+            ref mut attributes, ..
+        } = self.nodes.borrow_mut()[id(el).0].kind
+        {
+            attributes.retain(|key, (_, of)| {
+                of.as_ref().map(String::as_str) != Some(namespace)
+                    || key.rsplit(':').next() != Some(local_name)
+            });
+        }
+        Ok(())
+    }
+
+    fn set_property(&self, el: &Node, name: &str, value: &PropertyValue) -> Result<(), JsValue> {
+        self.mutate(|m| m.properties_set += 1);
+        if let MemoryNodeKind::Element {
+            ref mut properties, ..
+        } = self.nodes.borrow_mut()[id(el).0].kind
+        {
+            properties.insert(name.to_string(), value.clone());
+        }
+        Ok(())
+    }
+
+    fn get_property(&self, el: &Node, name: &str) -> Option<PropertyValue> {
+        match self.nodes.borrow()[id(el).0].kind {
+            MemoryNodeKind::Element { ref properties, .. } => properties.get(name).cloned(),
+            _ => None,
+        }
+    }
+
+    fn add_event_listener(
+        &self,
+        el: &Node,
+        type_: &str,
+        handler: Box<dyn Fn(Event)>,
+        options: ListenerOptions,
+    ) -> Result<Listener, JsValue> {
+        self.mutate(|m| m.listeners_added += 1);
+        let listener_id = self.next_listener.get();
+        self.next_listener.set(listener_id + 1);
+        if let MemoryNodeKind::Element {
+            ref mut listeners, ..
+        } = self.nodes.borrow_mut()[id(el).0].kind
+        {
+            listeners.push((type_.to_string(), listener_id, options, handler.into()));
+        }
+        Ok(Listener::new(ListenerId(listener_id)))
+    }
+
+    fn remove_event_listener(
+        &self,
+        el: &Node,
+        _: &str,
+        listener: &Listener,
+    ) -> Result<(), JsValue> {
+        self.mutate(|m| m.listeners_removed += 1);
+        let listener_id = listener
+            .downcast_ref::<ListenerId>()
+            .expect("The listener does not belong to a memory DOM.")
+            .0;
+        if let MemoryNodeKind::Element {
+            ref mut listeners, ..
+        } = self.nodes.borrow_mut()[id(el).0].kind
+        {
+            listeners.retain(|(_, id, ..)| *id != listener_id);
+        }
+        Ok(())
+    }
+
+    fn first_child(&self, node: &Node) -> Option<Node> {
+        self.nodes.borrow()[id(node).0]
+            .children
+            .first()
+            .map(|child| Node::new(*child))
+    }
+
+    fn next_sibling(&self, node: &Node) -> Option<Node> {
+        let node = id(node);
+        let nodes = self.nodes.borrow();
+        let parent = nodes[node.0].parent?;
+        let siblings = &nodes[parent.0].children;
+        let index = siblings.iter().position(|child| *child == node)?;
+        siblings.get(index + 1).map(|sibling| Node::new(*sibling))
+    }
+
+    fn parent_node(&self, node: &Node) -> Option<Node> {
+        self.nodes.borrow()[id(node).0].parent.map(Node::new)
+    }
+
+    fn event_target(&self, _: &Event) -> Option<Node> {
+        self.dispatching
+            .borrow()
+            .last()
+            .map(|dispatch| Node::new(dispatch.target))
+    }
+
+    fn prevent_default(&self, _: &Event) {
+        self.with_dispatch(|dispatch| {
+            if !dispatch.passive {
+                dispatch.default_prevented = true;
+            }
+        });
+    }
+
+    fn stop_propagation(&self, _: &Event) {
+        self.with_dispatch(|dispatch| dispatch.propagation_stopped = true);
+    }
+
+    fn is_propagation_stopped(&self, _: &Event) -> bool {
+        self.with_dispatch(|dispatch| dispatch.propagation_stopped)
+    }
+
+    fn event_key(&self, _: &Event) -> Option<String> {
+        self.with_dispatch(|dispatch| dispatch.key.clone())
+    }
+
+    fn clone_event(&self, _: &Event) -> Event {
+        JsValue::UNDEFINED.unchecked_into()
+    }
+
+    fn set_node_id(&self, el: &Node, node_id: u32) -> Result<(), JsValue> {
+        self.nodes.borrow_mut()[id(el).0].tag_id = Some(node_id);
+        Ok(())
+    }
+
+    fn node_id(&self, node: &Node) -> Option<u32> {
+        self.nodes.borrow()[id(node).0].tag_id
+    }
+
+    fn node_kind(&self, node: &Node) -> NodeKind {
+        match self.nodes.borrow()[id(node).0].kind {
+            MemoryNodeKind::Element { ref tag, .. } => NodeKind::Element(tag.to_lowercase()),
+            MemoryNodeKind::Text(ref text) => NodeKind::Text(text.clone()),
+            MemoryNodeKind::Comment(ref text) => NodeKind::Comment(text.clone()),
+        }
+    }
+
+    fn split_text(&self, node: &Node, offset: usize) -> Result<Node, JsValue> {
+        let rest = match self.nodes.borrow_mut()[id(node).0].kind {
+            MemoryNodeKind::Text(ref mut text) => text.split_off(offset),
+            _ => panic!("Only a text node may be split."),
+        };
+        let rest = self.create_text_node(&rest)?;
+        let parent = self.nodes.borrow()[id(node).0].parent.map(Node::new);
+        if let Some(parent) = parent {
+            let next = self.next_sibling(node);
+            self.insert_before(&parent, &rest, next.as_ref())?;
+        }
+        Ok(rest)
+    }
+
+    fn warn(&self, message: &str) {
+        self.warnings.borrow_mut().push(message.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dom::{SVG_NAMESPACE, XLINK_NAMESPACE};
+
+    #[test]
+    fn should_insert_and_move_nodes() {
+        let dom = MemoryDOM::new();
+        let container = dom.container();
+        let first = dom.create_text_node("first").unwrap();
+        let second = dom.create_element("br").unwrap();
+
+        dom.insert_before(&container, &first, None).unwrap();
+        dom.insert_before(&container, &second, None).unwrap();
+        assert_eq!(dom.inner_html(&container), "first<br>");
+
+        dom.insert_before(&container, &second, Some(&first))
+            .unwrap();
+        assert_eq!(dom.inner_html(&container), "<br>first");
+        assert_eq!(dom.mutations().inserted, 3);
+    }
+
+    #[test]
+    fn should_set_and_remove_attributes() {
+        let dom = MemoryDOM::new();
+        let container = dom.container();
+        let el = dom.create_element("div").unwrap();
+        dom.insert_before(&container, &el, None).unwrap();
+
+        dom.set_attribute(&el, "class", "a \"b\"").unwrap();
+        dom.set_attribute(&el, "id", "main").unwrap();
+        dom.remove_attribute(&el, "class").unwrap();
+
+        assert_eq!(dom.inner_html(&container), r#"<div id="main"></div>"#);
+    }
+
+    #[test]
+    fn should_create_elements_and_attributes_in_namespaces() {
+        let dom = MemoryDOM::new();
+        let container = dom.container();
+        let svg = dom.create_element_ns(SVG_NAMESPACE, "svg").unwrap();
+        let link = dom.create_element_ns(SVG_NAMESPACE, "use").unwrap();
+        dom.insert_before(&container, &svg, None).unwrap();
+        dom.insert_before(&svg, &link, None).unwrap();
+
+        dom.set_attribute_ns(&link, XLINK_NAMESPACE, "xlink:href", "#dot")
+            .unwrap();
+        assert_eq!(
+            dom.attribute_namespace(&link, "xlink:href"),
+            Some(XLINK_NAMESPACE.to_string())
+        );
+        assert_eq!(dom.namespace_uri(&link), Some(SVG_NAMESPACE.to_string()));
+        assert_eq!(dom.namespace_uri(&container), Some(HTML_NAMESPACE.to_string()));
+        assert_eq!(
+            dom.inner_html(&container),
+            r##"<svg><use xlink:href="#dot"></use></svg>"##
+        );
+
+        dom.remove_attribute_ns(&link, XLINK_NAMESPACE, "href").unwrap();
+        assert_eq!(dom.inner_html(&container), "<svg><use></use></svg>");
+    }
+
+    #[test]
+    fn should_split_text() {
+        let dom = MemoryDOM::new();
+        let container = dom.container();
+        let text = dom.create_text_node("Hello World!").unwrap();
+        dom.insert_before(&container, &text, None).unwrap();
+
+        let rest = dom.split_text(&text, 6).unwrap();
+
+        assert_eq!(dom.node_kind(&text), NodeKind::Text("Hello ".to_string()));
+        assert_eq!(dom.node_kind(&rest), NodeKind::Text("World!".to_string()));
+        assert_eq!(dom.inner_html(&container), "Hello World!");
+    }
+
+    #[test]
+    fn should_dispatch_events_through_the_listeners() {
+        let dom = Rc::new(MemoryDOM::new());
+        let container = dom.container();
+        let button = dom.create_element("button").unwrap();
+        dom.insert_before(&container, &button, None).unwrap();
+        let invoked = Rc::new(RefCell::new(vec![]));
+        let listen = |el: &Node, name: &'static str, options: ListenerOptions| {
+            let (invoked, memory) = (invoked.clone(), dom.clone());
+            dom.add_event_listener(
+                el,
+                "click",
+                Box::new(move |event| {
+                    invoked.borrow_mut().push(name);
+                    if name == "button" {
+                        memory.prevent_default(&event);
+                    }
+                }),
+                options,
+            ).unwrap()
+        };
+        listen(&container, "container", ListenerOptions::default());
+        listen(
+            &container,
+            "capturing container",
+            ListenerOptions {
+                capture: true,
+                ..Default::default()
+            },
+        );
+        listen(
+            &button,
+            "button",
+            ListenerOptions {
+                once: true,
+                ..Default::default()
+            },
+        );
+
+        assert!(!dom.dispatch(&button, "click"));
+        assert_eq!(
+            *invoked.borrow(),
+            vec!["capturing container", "button", "container"]
+        );
+
+        invoked.borrow_mut().clear();
+        assert!(dom.dispatch(&button, "click"));
+        assert_eq!(*invoked.borrow(), vec!["capturing container", "container"]);
+    }
+
+    #[test]
+    fn should_stop_the_propagation_of_events() {
+        let dom = Rc::new(MemoryDOM::new());
+        let container = dom.container();
+        let input = dom.create_element("input").unwrap();
+        dom.insert_before(&container, &input, None).unwrap();
+        let keys = Rc::new(RefCell::new(vec![]));
+        let (of_input, memory) = (keys.clone(), dom.clone());
+        dom.add_event_listener(
+            &input,
+            "keydown",
+            Box::new(move |event| {
+                of_input.borrow_mut().push(memory.event_key(&event));
+                memory.stop_propagation(&event);
+            }),
+            ListenerOptions::default(),
+        ).unwrap();
+        let of_container = keys.clone();
+        dom.add_event_listener(
+            &container,
+            "keydown",
+            Box::new(move |_| of_container.borrow_mut().push(None)),
+            ListenerOptions::default(),
+        ).unwrap();
+
+        dom.dispatch_key(&input, "keydown", "Enter");
+        assert_eq!(*keys.borrow(), vec![Some("Enter".to_string())]);
+    }
+}
@@ -0,0 +1,268 @@
+//! The browser DOM backend.
+
+use super::{DOMBackend, Listener, ListenerOptions, Node, NodeKind, PropertyValue};
+use js_sys::Reflect;
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{
+    console, window, AddEventListenerOptions, Document, Element, Event, EventTarget,
+    KeyboardEvent, Text,
+};
+
+/// The property of an element which holds the id it is tagged with.
+const NODE_ID: &str = "__ruukh_id";
+
+/// The DOM of the browser, the backend an App is mounted on.
+pub struct WebDOM {
+    document: Document,
+}
+
+impl WebDOM {
+    /// Creates a backend on the document of the current window.
+    pub fn new() -> WebDOM {
+        WebDOM {
+            document: window().unwrap().document().unwrap(),
+        }
+    }
+}
+
+impl Default for WebDOM {
+    fn default() -> WebDOM {
+        WebDOM::new()
+    }
+}
+
+impl Node {
+    /// Wraps a node of the browser DOM.
+    pub fn web(node: impl Into<web_sys::Node>) -> Node {
+        Node::new(node.into())
+    }
+
+    /// Gets the node of the browser DOM.
+    ///
+    /// Panics if the node was not created by the `WebDOM` backend.
+    pub fn as_web(&self) -> &web_sys::Node {
+        self.downcast_ref()
+            .expect("The node does not belong to the web DOM.")
+    }
+}
+
+impl DOMBackend for WebDOM {
+    fn create_element(&self, tag: &str) -> Result<Node, JsValue> {
+        Ok(Node::web(self.document.create_element(tag)?))
+    }
+
+    fn create_element_ns(&self, namespace: &str, tag: &str) -> Result<Node, JsValue> {
+        Ok(Node::web(
+            self.document.create_element_ns(Some(namespace), tag)?,
+        ))
+    }
+
+    fn namespace_uri(&self, node: &Node) -> Option<String> {
+        node.as_web().dyn_ref::<Element>()?.namespace_uri()
+    }
+
+    fn create_text_node(&self, content: &str) -> Result<Node, JsValue> {
+        Ok(Node::web(self.document.create_text_node(content)))
+    }
+
+    fn create_comment(&self, content: &str) -> Result<Node, JsValue> {
+        Ok(Node::web(self.document.create_comment(content)))
+    }
+
+    fn insert_before(
+        &self,
+        parent: &Node,
+        node: &Node,
+        next: Option<&Node>,
+    ) -> Result<(), JsValue> {
+        parent
+            .as_web()
+            .insert_before(node.as_web(), next.map(Node::as_web))?;
+        Ok(())
+    }
+
+    fn remove_child(&self, parent: &Node, node: &Node) -> Result<(), JsValue> {
+        parent.as_web().remove_child(node.as_web())?;
+        Ok(())
+    }
+
+    fn set_text_content(&self, node: &Node, content: &str) -> Result<(), JsValue> {
+        node.as_web().set_text_content(Some(content));
+        Ok(())
+    }
+
+    fn set_attribute(&self, el: &Node, name: &str, value: &str) -> Result<(), JsValue> {
+        el.as_web()
+            .unchecked_ref::<Element>()
+            .set_attribute(name, value)
+    }
+
+    fn remove_attribute(&self, el: &Node, name: &str) -> Result<(), JsValue> {
+        el.as_web()
+            .unchecked_ref::<Element>()
+            .remove_attribute(name)
+    }
+
+    fn set_attribute_ns(
+        &self,
+        el: &Node,
+        namespace: &str,
+        name: &str,
+        value: &str,
+    ) -> Result<(), JsValue> {
+        el.as_web()
+            .unchecked_ref::<Element>()
+            .set_attribute_ns(Some(namespace), name, value)
+    }
+
+    fn remove_attribute_ns(
+        &self,
+        el: &Node,
+        namespace: &str,
+        local_name: &str,
+    ) -> Result<(), JsValue> {
+        el.as_web()
+            .unchecked_ref::<Element>()
+            .remove_attribute_ns(Some(namespace), local_name)
+    }
+
+    fn set_property(&self, el: &Node, name: &str, value: &PropertyValue) -> Result<(), JsValue> {
+        let value = match value {
+            PropertyValue::String(ref value) => JsValue::from_str(value),
+            PropertyValue::Bool(value) => JsValue::from_bool(*value),
+            PropertyValue::Number(value) => JsValue::from_f64(*value),
+        };
+        Reflect::set(el.as_web(), &JsValue::from_str(name), &value).map(|_| ())
+    }
+
+    fn get_property(&self, el: &Node, name: &str) -> Option<PropertyValue> {
+        let value = Reflect::get(el.as_web(), &JsValue::from_str(name)).ok()?;
+        if let Some(value) = value.as_string() {
+            Some(PropertyValue::String(value))
+        } else if let Some(value) = value.as_bool() {
+            Some(PropertyValue::Bool(value))
+        } else {
+            value.as_f64().map(PropertyValue::Number)
+        }
+    }
+
+    fn add_event_listener(
+        &self,
+        el: &Node,
+        type_: &str,
+        handler: Box<dyn Fn(Event)>,
+        options: ListenerOptions,
+    ) -> Result<Listener, JsValue> {
+        let js_closure: Closure<dyn Fn(Event)> = Closure::wrap(handler);
+        let js_options = AddEventListenerOptions::new();
+        js_options.set_capture(options.capture);
+        js_options.set_passive(options.passive);
+        js_options.set_once(options.once);
+        el.as_web()
+            .unchecked_ref::<EventTarget>()
+            .add_event_listener_with_callback_and_add_event_listener_options(
+                type_,
+                js_closure.as_ref().unchecked_ref(),
+                &js_options,
+            )?;
+        // The listener is only removed when its capture matches.
+        Ok(Listener::new((js_closure, options.capture)))
+    }
+
+    fn remove_event_listener(
+        &self,
+        el: &Node,
+        type_: &str,
+        listener: &Listener,
+    ) -> Result<(), JsValue> {
+        let (js_closure, capture): &(Closure<dyn Fn(Event)>, bool) = listener
+            .downcast_ref()
+            .expect("The listener does not belong to the web DOM.");
+        el.as_web()
+            .unchecked_ref::<EventTarget>()
+            .remove_event_listener_with_callback_and_bool(
+                type_,
+                js_closure.as_ref().unchecked_ref(),
+                *capture,
+            )
+    }
+
+    fn first_child(&self, node: &Node) -> Option<Node> {
+        node.as_web().first_child().map(Node::web)
+    }
+
+    fn next_sibling(&self, node: &Node) -> Option<Node> {
+        node.as_web().next_sibling().map(Node::web)
+    }
+
+    fn parent_node(&self, node: &Node) -> Option<Node> {
+        node.as_web().parent_node().map(Node::web)
+    }
+
+    fn event_target(&self, event: &Event) -> Option<Node> {
+        event
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::Node>().ok())
+            .map(Node::web)
+    }
+
+    fn prevent_default(&self, event: &Event) {
+        event.prevent_default();
+    }
+
+    fn stop_propagation(&self, event: &Event) {
+        event.stop_propagation();
+    }
+
+    fn is_propagation_stopped(&self, event: &Event) -> bool {
+        event.cancel_bubble()
+    }
+
+    fn event_key(&self, event: &Event) -> Option<String> {
+        event.dyn_ref::<KeyboardEvent>().map(KeyboardEvent::key)
+    }
+
+    fn clone_event(&self, event: &Event) -> Event {
+        event.clone()
+    }
+
+    fn set_node_id(&self, el: &Node, id: u32) -> Result<(), JsValue> {
+        Reflect::set(
+            el.as_web(),
+            &JsValue::from_str(NODE_ID),
+            &JsValue::from(id),
+        ).map(|_| ())
+    }
+
+    fn node_id(&self, node: &Node) -> Option<u32> {
+        Reflect::get(node.as_web(), &JsValue::from_str(NODE_ID))
+            .ok()
+            .and_then(|id| id.as_f64())
+            .map(|id| id as u32)
+    }
+
+    fn node_kind(&self, node: &Node) -> NodeKind {
+        let node = node.as_web();
+        match node.node_type() {
+            web_sys::Node::ELEMENT_NODE => NodeKind::Element(node.node_name().to_lowercase()),
+            web_sys::Node::TEXT_NODE => NodeKind::Text(node.text_content().unwrap_or_default()),
+            web_sys::Node::COMMENT_NODE => {
+                NodeKind::Comment(node.text_content().unwrap_or_default())
+            }
+            _ => NodeKind::Other,
+        }
+    }
+
+    fn split_text(&self, node: &Node, offset: usize) -> Result<Node, JsValue> {
+        let node = node.as_web();
+        let content = node.text_content().unwrap_or_default();
+        // The DOM counts the offset in UTF-16 code units.
+        let offset = content[..offset].encode_utf16().count() as u32;
+        let rest = node.unchecked_ref::<Text>().split_text(offset)?;
+        Ok(Node::web(rest))
+    }
+
+    fn warn(&self, message: &str) {
+        console::warn_1(&JsValue::from_str(message));
+    }
+}
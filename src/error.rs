@@ -0,0 +1,245 @@
+//! Errors which occur while rendering an App.
+
+use crate::vdom::VNode;
+use std::{
+    any::type_name,
+    fmt::{self, Debug, Display, Formatter},
+};
+#[cfg(panic = "unwind")]
+use std::panic::{self, AssertUnwindSafe};
+use wasm_bindgen::prelude::JsValue;
+
+/// An error which occurred while patching the DOM.
+///
+/// It knows the DOM operation that failed and the path of the components,
+/// from the root down to the one which rendered the failing node.
+pub struct RenderError {
+    /// The names of the components, innermost first.
+    path: Vec<&'static str>,
+    operation: Operation,
+    cause: Cause,
+}
+
+/// What caused a `RenderError`.
+enum Cause {
+    /// The DOM threw a JS error.
+    Js(JsValue),
+    /// The VDOM did not find a node that it had rendered.
+    Detached,
+    /// A component panicked, with the given message.
+    Panic(String),
+}
+
+/// The operation on the DOM during which a `RenderError` occurred.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    /// Rendering a component or invoking its lifecycle.
+    Render,
+    /// Creating an element, a text or a comment node.
+    Create,
+    /// Inserting or moving a node.
+    Insert,
+    /// Removing a node.
+    Remove,
+    /// Updating an existing node.
+    Patch,
+    /// Replacing the content of a text or a comment node.
+    SetText,
+    /// Splitting a text node while hydrating.
+    SplitText,
+    /// Setting an attribute on an element.
+    SetAttribute,
+    /// Removing an attribute from an element.
+    RemoveAttribute,
+    /// Setting a DOM property of an element.
+    SetProperty,
+    /// Adding an event listener to an element.
+    AddListener,
+    /// Removing an event listener from an element.
+    RemoveListener,
+}
+
+impl RenderError {
+    /// Creates an error for a DOM operation which threw a JS error.
+    ///
+    /// Returns a closure, so that it can be passed to `map_err` as is.
+    pub(crate) fn js(operation: Operation) -> impl FnOnce(JsValue) -> RenderError {
+        move |cause| RenderError {
+            path: vec![],
+            operation,
+            cause: Cause::Js(cause),
+        }
+    }
+
+    /// Creates an error for a DOM operation on a node which the VDOM expected
+    /// to be attached to the DOM, but was not.
+    pub(crate) fn detached(operation: Operation) -> RenderError {
+        RenderError {
+            path: vec![],
+            operation,
+            cause: Cause::Detached,
+        }
+    }
+
+    /// Creates an error for a component which panicked while rendering.
+    pub(crate) fn panicked(message: String) -> RenderError {
+        RenderError {
+            path: vec![],
+            operation: Operation::Render,
+            cause: Cause::Panic(message),
+        }
+    }
+
+    /// Adds the component `COMP` to the path, as the error bubbles up through
+    /// it.
+    pub(crate) fn within<COMP>(mut self) -> RenderError {
+        self.path.push(short_type_name::<COMP>());
+        self
+    }
+
+    /// The names of the components from the root down to the one which
+    /// rendered the failing node.
+    pub fn path(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.path.iter().rev().cloned()
+    }
+
+    /// The DOM operation which failed.
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    /// The JS error thrown by the DOM. There is none when the VDOM did not
+    /// find a node that it had rendered.
+    pub fn js_error(&self) -> Option<&JsValue> {
+        match self.cause {
+            Cause::Js(ref cause) => Some(cause),
+            _ => None,
+        }
+    }
+
+    /// The message of the panic, if a component panicked.
+    pub fn panic_message(&self) -> Option<&str> {
+        match self.cause {
+            Cause::Panic(ref message) => Some(message),
+            _ => None,
+        }
+    }
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Could not {} in `", self.operation)?;
+        for (index, component) in self.path().enumerate() {
+            if index != 0 {
+                write!(f, " > ")?;
+            }
+            write!(f, "{}", component)?;
+        }
+        write!(f, "`")?;
+        match self.cause {
+            Cause::Js(ref cause) => match cause.as_string() {
+                Some(message) => write!(f, ": {}", message),
+                None => write!(f, ": the DOM threw an error"),
+            },
+            Cause::Detached => write!(f, ": the node is not attached to the DOM"),
+            Cause::Panic(ref message) => write!(f, ": it panicked with '{}'", message),
+        }
+    }
+}
+
+impl Debug for RenderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "RenderError({})", self)
+    }
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let operation = match self {
+            Operation::Render => "render a component",
+            Operation::Create => "create a node",
+            Operation::Insert => "insert a node",
+            Operation::Remove => "remove a node",
+            Operation::Patch => "patch a node",
+            Operation::SetText => "set the text of a node",
+            Operation::SplitText => "split a text node",
+            Operation::SetAttribute => "set an attribute",
+            Operation::RemoveAttribute => "remove an attribute",
+            Operation::SetProperty => "set a property",
+            Operation::AddListener => "add an event listener",
+            Operation::RemoveListener => "remove an event listener",
+        };
+        write!(f, "{}", operation)
+    }
+}
+
+/// What the App should do when it fails to render.
+#[allow(clippy::large_enum_variant)]
+pub enum ErrorAction {
+    /// Report the error as a warning and carry on. The DOM may be left out of
+    /// sync with the VDOM.
+    Log,
+    /// Clear the DOM of the App and render the root component again from
+    /// scratch. The nested components lose their state.
+    Rerender,
+    /// Replace the App with the given markup. The App no longer re-renders.
+    Fallback(VNode<()>),
+}
+
+/// Runs the closure, catching a panic (if it unwinds) along with its message.
+#[cfg(panic = "unwind")]
+pub(crate) fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Box<Any>".to_string()
+        }
+    })
+}
+
+/// Runs the closure. A panic aborts right away, so there is nothing to catch.
+#[cfg(not(panic = "unwind"))]
+pub(crate) fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    Ok(f())
+}
+
+/// The name of a type without its module path.
+pub(crate) fn short_type_name<T>() -> &'static str {
+    let name = type_name::<T>();
+    let generics = name.find('<').unwrap_or(name.len());
+    let start = name[..generics].rfind("::").map(|i| i + 2).unwrap_or(0);
+    &name[start..]
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    struct MyApp;
+    struct Counter;
+
+    #[test]
+    fn should_display_the_component_path() {
+        let error = RenderError::detached(Operation::Remove)
+            .within::<Counter>()
+            .within::<MyApp>();
+        assert_eq!(
+            error.to_string(),
+            "Could not remove a node in `MyApp > Counter`: the node is not attached to \
+             the DOM"
+        );
+    }
+
+    #[test]
+    fn should_catch_a_panic_with_its_message() {
+        let caught = catch_panic(|| panic!("Oops"));
+        assert_eq!(caught, Err("Oops".to_string()));
+
+        let error = RenderError::panicked(caught.unwrap_err()).within::<Counter>();
+        assert_eq!(error.operation(), Operation::Render);
+        assert_eq!(error.panic_message(), Some("Oops"));
+    }
+}
@@ -0,0 +1,75 @@
+//! The DOM events passed to the `@event` listeners in `html!`.
+//!
+//! A listener of a known event is passed the event with its own type, like
+//! a `MouseEvent` for `@click` or a `KeyboardEvent` for `@keydown`, while the
+//! rest of them are passed an `Event`. So is the listener of `@input`, as
+//! scripts dispatch a plain `Event` as one quite often. An event of any other
+//! type than the one expected, like a plain `Event` dispatched as a `click`,
+//! is not passed to the listener. Instead, a warning is logged.
+//!
+//! # Example
+//! ```
+//! # #![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+//! # use ruukh::prelude::*;
+//! use ruukh::event::{Event, KeyboardEvent};
+//! use web_sys::HtmlInputElement;
+//!
+//! #[component]
+//! #[derive(Lifecycle)]
+//! struct Search {
+//!     #[state]
+//!     query: String,
+//! }
+//!
+//! impl Render for Search {
+//!     fn render(&self) -> Markup<Self> {
+//!         html! {
+//!             <input @input={Self::on_input} @keydown={Self::on_keydown}/>
+//!         }
+//!     }
+//! }
+//!
+//! impl Search {
+//!     fn on_input(&self, event: Event) {
+//!         if let Some(input) = event.current_target_as::<HtmlInputElement>() {
+//!             self.set_state(|state| state.query = input.value());
+//!         }
+//!     }
+//!
+//!     fn on_keydown(&self, event: KeyboardEvent) {
+//!         if event.key() == "Escape" {
+//!             self.set_state(|state| state.query.clear());
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::dom;
+use wasm_bindgen::JsCast;
+
+pub use web_sys::{
+    AnimationEvent, ClipboardEvent, CompositionEvent, DragEvent, Event, FocusEvent, InputEvent,
+    KeyboardEvent, MouseEvent, PointerEvent, TouchEvent, TransitionEvent, WheelEvent,
+};
+
+/// Typed access to the element which the listener of an event is on.
+pub trait CurrentTarget {
+    /// The element which the listener is on, as the type `T`. There is none
+    /// if it is not a `T` or the event is not being dispatched.
+    fn current_target_as<T: JsCast>(&self) -> Option<T>;
+}
+
+impl<E: AsRef<Event>> CurrentTarget for E {
+    fn current_target_as<T: JsCast>(&self) -> Option<T> {
+        // The current target of a delegated event is the mount element, rather
+        // than the element whose listener is invoked.
+        if let Some((_, node)) = dom::current_listener() {
+            return node
+                .downcast_ref::<web_sys::Node>()
+                .and_then(|node| node.clone().dyn_into().ok());
+        }
+        self.as_ref()
+            .current_target()
+            .and_then(|target| target.dyn_into().ok())
+    }
+}
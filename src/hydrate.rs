@@ -0,0 +1,122 @@
+//! Hydration of a server rendered markup.
+//!
+//! Instead of creating the DOM nodes on the first render, the VDOM attaches
+//! itself to the nodes which are already present in the DOM. When the DOM does
+//! not match up with the VDOM, the mismatch is reported through the backend
+//! and the offending node is rendered from scratch.
+
+use crate::{
+    component::Render,
+    dom::{DOMBackend, Node, NodeKind, Runtime},
+    error::{Operation, RenderError},
+    Shared,
+};
+
+/// Trait to attach the VDOM onto the existing DOM.
+pub(crate) trait Hydrate {
+    /// The render context of this hydration.
+    type RenderContext: Render;
+
+    /// Hydrates the VDOM by claiming the existing nodes starting from
+    /// `existing` in the `parent`.
+    ///
+    /// Returns the first node after the ones claimed by `self`, so that the
+    /// following VDOM continues from there.
+    fn hydrate(
+        &mut self,
+        parent: &Node,
+        existing: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rt: &Runtime,
+    ) -> Result<Option<Node>, RenderError>;
+}
+
+/// Reports a mismatch between the expected VDOM and the existing DOM node.
+pub(crate) fn report_mismatch(dom: &dyn DOMBackend, expected: &str, found: Option<&Node>) {
+    dom.warn(&format!(
+        "Hydration mismatch: expected {} but found {}. Rendering it on the client \
+         instead.",
+        expected,
+        describe(dom, found)
+    ));
+}
+
+/// Removes the mismatched node which was replaced by a client render and
+/// returns its next sibling.
+pub(crate) fn discard(
+    dom: &dyn DOMBackend,
+    parent: &Node,
+    mismatched: Option<Node>,
+) -> Result<Option<Node>, RenderError> {
+    match mismatched {
+        Some(node) => {
+            let next = dom.next_sibling(&node);
+            dom.remove_child(parent, &node)
+                .map_err(RenderError::js(Operation::Remove))?;
+            Ok(next)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Removes all the existing nodes from `existing` onwards as there is nothing
+/// in the VDOM to claim them.
+pub(crate) fn remove_unclaimed(
+    dom: &dyn DOMBackend,
+    parent: &Node,
+    mut existing: Option<Node>,
+) -> Result<(), RenderError> {
+    while let Some(node) = existing {
+        if !is_blank_text(dom, &node) {
+            report_mismatch(dom, "nothing", Some(&node));
+        }
+        existing = dom.next_sibling(&node);
+        dom.remove_child(parent, &node)
+            .map_err(RenderError::js(Operation::Remove))?;
+    }
+    Ok(())
+}
+
+/// Skips over (and removes) the whitespace only text nodes which are left in
+/// by formatting of the server markup.
+pub(crate) fn skip_blank_text(
+    dom: &dyn DOMBackend,
+    parent: &Node,
+    mut existing: Option<Node>,
+) -> Result<Option<Node>, RenderError> {
+    while let Some(node) = existing.take() {
+        if is_blank_text(dom, &node) {
+            existing = dom.next_sibling(&node);
+            dom.remove_child(parent, &node)
+                .map_err(RenderError::js(Operation::Remove))?;
+        } else {
+            return Ok(Some(node));
+        }
+    }
+    Ok(None)
+}
+
+/// Whether the node is an element with the given tag.
+pub(crate) fn is_element_with_tag(dom: &dyn DOMBackend, node: &Node, tag: &str) -> bool {
+    match dom.node_kind(node) {
+        NodeKind::Element(ref name) => name.eq_ignore_ascii_case(tag),
+        _ => false,
+    }
+}
+
+fn is_blank_text(dom: &dyn DOMBackend, node: &Node) -> bool {
+    match dom.node_kind(node) {
+        NodeKind::Text(ref text) => text.trim().is_empty(),
+        _ => false,
+    }
+}
+
+fn describe(dom: &dyn DOMBackend, node: Option<&Node>) -> String {
+    match node.map(|node| dom.node_kind(node)) {
+        Some(NodeKind::Element(tag)) => format!("<{}>", tag),
+        Some(NodeKind::Text(text)) => format!("text {:?}", text),
+        Some(NodeKind::Comment(text)) => format!("comment {:?}", text),
+        Some(NodeKind::Other) => "an unknown node".to_string(),
+        None => "nothing".to_string(),
+    }
+}
@@ -1,4 +1,6 @@
-#![deny(missing_docs)]
+#![cfg_attr(not(test), deny(missing_docs))]
+#![feature(decl_macro)]
+#![cfg_attr(test, feature(test))]
 #![cfg_attr(feature = "cargo-clippy", feature(tool_lints))]
 #![cfg_attr(feature = "cargo-clippy", warn(clippy::all))]
 //! # Ruukh - Introduction
@@ -41,6 +43,18 @@
 //!
 //! Here, "app" is the `id` of an element where you want to mount the App.
 //!
+//! Once mounted, the state changes are not rendered right away. They are
+//! batched and rendered together, as decided by the
+//! [scheduler](scheduler/index.html) strategy of the App.
+//!
+//! The same App may also be rendered to an HTML string on a server with
+//! [App::render_to_string](struct.App.html#method.render_to_string) and then
+//! be made interactive on the browser with
+//! [App::hydrate](struct.App.html#method.hydrate).
+//!
+//! An App which has many pages may route between them on the client with a
+//! [Router](router/index.html).
+//!
 //! Note: Docs on macros are located [here](../../ruukh_codegen/index.html).
 
 #[cfg(test)]
@@ -50,15 +64,25 @@ use wasm_bindgen_test::*;
 wasm_bindgen_test_configure!(run_in_browser);
 
 use crate::{
-    component::{Render, RootParent},
+    component::{FromEventProps, Render, RootParent},
+    dom::{delegation::EventMode, web::WebDOM, DOMPatch, Node, Runtime},
+    error::{ErrorAction, Operation, RenderError},
+    scheduler::{Scheduler, Strategy},
     vdom::vcomponent::{ComponentManager, ComponentWrapper},
 };
 use std::{cell::RefCell, rc::Rc};
-use wasm_bindgen::{prelude::*, JsCast};
-use web_sys::{window, Element, MessageChannel, MessagePort};
+use web_sys::{window, Element};
 
+pub mod bind;
 pub mod component;
-mod dom;
+pub mod context;
+pub mod dom;
+pub mod error;
+pub mod event;
+mod hydrate;
+pub mod router;
+pub mod scheduler;
+mod ssr;
 pub mod vdom;
 
 /// A VDOM Markup which is generated by using `html!` macro.
@@ -68,7 +92,8 @@ pub type Markup<RCTX> = vdom::VNode<RCTX>;
 /// prelude and start building your app.
 pub mod prelude {
     pub use crate::component::{Component, Lifecycle, Render};
-    pub use crate::{App, Markup};
+    pub use crate::event::CurrentTarget;
+    pub use crate::{App, AppHandle, Markup};
     pub use ruukh_codegen::*;
 }
 
@@ -81,9 +106,13 @@ pub mod reexports {
 /// The main entry point to use your component and run it on the browser.
 pub struct App<COMP>
 where
-    COMP: Render<Props = (), Events = ()>,
+    COMP: Render,
+    COMP::Events: FromEventProps<RootParent>,
 {
     manager: ComponentWrapper<COMP, RootParent>,
+    strategy: Strategy,
+    event_mode: EventMode,
+    on_error: Box<dyn Fn(&RenderError) -> ErrorAction>,
 }
 
 impl<COMP> App<COMP>
@@ -93,7 +122,9 @@ where
     /// Create a new App with a `Component` struct passed as its type parameter.
     ///
     /// The component that is mounted as an App should not have any props and
-    /// events declared onto it.
+    /// events declared onto it. Otherwise, use
+    /// [with_props](#method.with_props) or
+    /// [with_props_and_events](#method.with_props_and_events).
     ///
     /// # Example
     /// ```
@@ -118,6 +149,181 @@ where
     pub fn new() -> App<COMP> {
         Default::default()
     }
+}
+
+impl<COMP> App<COMP>
+where
+    COMP: Render<Events = ()>,
+{
+    /// Create a new App with the props passed into the root component.
+    ///
+    /// # Example
+    /// ```
+    /// # #![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+    /// #
+    /// # use ruukh::prelude::*;
+    /// #
+    /// #[component]
+    /// #[derive(Lifecycle)]
+    /// struct MyApp {
+    ///     name: String,
+    /// }
+    ///
+    /// impl Render for MyApp {
+    ///     fn render(&self) -> Markup<Self> {
+    ///         html! {
+    ///             "Hello "{ &self.name }"!"
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let my_app = App::<MyApp>::with_props(MyAppProps!(name: "Ruukh".to_string()));
+    /// assert_eq!(my_app.render_to_string(), "Hello Ruukh!");
+    /// ```
+    pub fn with_props(props: COMP::Props) -> App<COMP> {
+        App::with_props_and_events(props, ())
+    }
+}
+
+impl<COMP> App<COMP>
+where
+    COMP: Render,
+    COMP::Events: FromEventProps<RootParent>,
+{
+    /// Create a new App with the props and the events passed into the root
+    /// component.
+    ///
+    /// The events are how the host page subscribes to the root component.
+    /// The handlers receive a void render context as their first argument.
+    ///
+    /// # Example
+    /// ```
+    /// # #![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+    /// #
+    /// # use ruukh::prelude::*;
+    /// #
+    /// #[component]
+    /// #[derive(Lifecycle)]
+    /// #[events(
+    ///     fn saved(&self, count: i32);
+    /// )]
+    /// struct MyApp {
+    ///     count: i32,
+    /// }
+    ///
+    /// impl Render for MyApp {
+    ///     fn render(&self) -> Markup<Self> {
+    ///         html! {
+    ///             <button @click={|this: &Self, _| this.saved(this.count)}>"Save"</button>
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let my_app = App::<MyApp>::with_props_and_events(
+    ///     MyAppProps!(count: 5),
+    ///     MyAppEvent!(saved: |_, count| println!("Saved {}", count)),
+    /// );
+    /// ```
+    pub fn with_props_and_events(
+        props: COMP::Props,
+        events: <COMP::Events as FromEventProps<RootParent>>::From,
+    ) -> App<COMP> {
+        App {
+            manager: ComponentWrapper::new(props, events),
+            strategy: Strategy::default(),
+            event_mode: EventMode::default(),
+            on_error: Box::new(|_| ErrorAction::Log),
+        }
+    }
+
+    /// Sets the strategy by which the re-renders are scheduled once the App
+    /// is mounted. By default, the updates are batched into a microtask.
+    ///
+    /// # Example
+    /// ```
+    /// # #![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+    /// #
+    /// # use ruukh::{prelude::*, scheduler::Strategy};
+    /// #
+    /// # #[component]
+    /// # #[derive(Lifecycle)]
+    /// # struct MyApp;
+    /// #
+    /// # impl Render for MyApp {
+    /// #     fn render(&self) -> Markup<Self> {
+    /// #         html! {
+    /// #             "Hello World!"
+    /// #         }
+    /// #     }
+    /// # }
+    /// let my_app = App::<MyApp>::new().schedule(Strategy::AnimationFrame);
+    /// ```
+    pub fn schedule(mut self, strategy: Strategy) -> App<COMP> {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets how the event listeners are attached to the DOM once the App is
+    /// mounted. By default, every listener is a DOM listener on its own
+    /// element. See [delegation](dom/delegation/index.html).
+    ///
+    /// # Example
+    /// ```
+    /// # #![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+    /// #
+    /// # use ruukh::{prelude::*, dom::delegation::EventMode};
+    /// #
+    /// # #[component]
+    /// # #[derive(Lifecycle)]
+    /// # struct MyApp;
+    /// #
+    /// # impl Render for MyApp {
+    /// #     fn render(&self) -> Markup<Self> {
+    /// #         html! {
+    /// #             "Hello World!"
+    /// #         }
+    /// #     }
+    /// # }
+    /// let my_app = App::<MyApp>::new().event_mode(EventMode::Delegated);
+    /// ```
+    pub fn event_mode(mut self, mode: EventMode) -> App<COMP> {
+        self.event_mode = mode;
+        self
+    }
+
+    /// Sets the hook which decides what to do when the App fails to render.
+    /// By default, the errors are logged.
+    ///
+    /// # Example
+    /// ```
+    /// # #![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+    /// #
+    /// # use ruukh::{prelude::*, error::ErrorAction};
+    /// #
+    /// # #[component]
+    /// # #[derive(Lifecycle)]
+    /// # struct MyApp;
+    /// #
+    /// # impl Render for MyApp {
+    /// #     fn render(&self) -> Markup<Self> {
+    /// #         html! {
+    /// #             "Hello World!"
+    /// #         }
+    /// #     }
+    /// # }
+    /// let my_app = App::<MyApp>::new().on_error(|_| {
+    ///     ErrorAction::Fallback(html! {
+    ///         <p>"Something went wrong."</p>
+    ///     })
+    /// });
+    /// ```
+    pub fn on_error(
+        mut self,
+        hook: impl Fn(&RenderError) -> ErrorAction + 'static,
+    ) -> App<COMP> {
+        self.on_error = Box::new(hook);
+        self
+    }
 
     /// Mounts the app on the given element in the DOM.
     ///
@@ -145,24 +351,111 @@ where
     /// # }
     /// App::<MyApp>::new().mount("app");
     /// ```
-    pub fn mount(mut self, element: impl AppMount) {
-        let parent = element.app_mount();
-        let (receiver, sender) = app_message_channel();
+    pub fn mount(self, element: impl AppMount) -> AppHandle<COMP> {
+        let parent = Node::web(element.app_mount());
+        let scheduler = Scheduler::new(self.strategy);
+        let mut root = self.into_root(parent, &scheduler);
+
+        // The first render
+        root.render();
 
+        AppHandle::new(root, scheduler)
+    }
+
+    /// Hydrates the markup rendered by
+    /// [render_to_string](#method.render_to_string) which already exists in
+    /// the given element.
+    ///
+    /// Instead of creating the DOM on the first render, the existing nodes
+    /// are reused and the event listeners are attached to them. Wherever the
+    /// existing DOM does not match the rendered markup, the mismatch is
+    /// reported on the console and that part is rendered on the client.
+    ///
+    /// # Example
+    /// ```ignore
+    /// # #![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+    /// #
+    /// # use ruukh::prelude::*;
+    /// # use wasm_bindgen::prelude::*;
+    /// #
+    /// # #[component]
+    /// # #[derive(Lifecycle)]
+    /// # struct MyApp;
+    /// #
+    /// # impl Render for MyApp {
+    /// #     fn render(&self) -> Markup<Self> {
+    /// #         html! {
+    /// #             "Hello World!"
+    /// #         }
+    /// #     }
+    /// # }
+    /// App::<MyApp>::new().hydrate("app");
+    /// ```
+    pub fn hydrate(self, element: impl AppMount) -> AppHandle<COMP> {
+        let parent = Node::web(element.app_mount());
+        let scheduler = Scheduler::new(self.strategy);
+        let mut root = self.into_root(parent, &scheduler);
+
+        // The first render reuses the server rendered nodes.
+        root.hydrate();
+
+        AppHandle::new(root, scheduler)
+    }
+
+    /// Prepares the App to be mounted on the parent.
+    fn into_root(self, parent: Node, scheduler: &Rc<Scheduler>) -> Root<COMP> {
+        let mut rt = Runtime::new(
+            Rc::new(WebDOM::new()),
+            MessageSender(Some(scheduler.clone())),
+        );
+        if self.event_mode == EventMode::Delegated {
+            rt = rt.delegate_to(&parent);
+        }
+        Root {
+            manager: self.manager,
+            parent,
+            // Every component requires a render context, so provided a void
+            // context.
+            root_parent: Rc::new(RefCell::new(())),
+            rt,
+            on_error: self.on_error,
+            fallback: None,
+        }
+    }
+
+    /// Renders the app to an HTML string without requiring a DOM, so that it
+    /// can be served from a server.
+    ///
+    /// Every component in the tree is `created` and rendered but not
+    /// `mounted`. The text and attribute values are escaped, so the output is
+    /// safe to be served as is.
+    ///
+    /// # Example
+    /// ```
+    /// # #![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+    /// #
+    /// # use ruukh::prelude::*;
+    /// #
+    /// # #[component]
+    /// # #[derive(Lifecycle)]
+    /// # struct MyApp;
+    /// #
+    /// # impl Render for MyApp {
+    /// #     fn render(&self) -> Markup<Self> {
+    /// #         html! {
+    /// #             "Hello World!"
+    /// #         }
+    /// #     }
+    /// # }
+    /// let html = App::<MyApp>::new().render_to_string();
+    /// assert_eq!(html, "Hello World!");
+    /// ```
+    pub fn render_to_string(mut self) -> String {
         // Every component requires a render context, so provided a void context.
         let root_parent = Rc::new(RefCell::new(()));
 
-        // The first render
-        self.manager
-            .render_walk(parent.as_ref(), None, root_parent.clone(), sender.clone())
-            .unwrap();
-
-        // Rerender when it receives update messages.
-        receiver.react_on_message(move || {
-            self.manager
-                .render_walk(parent.as_ref(), None, root_parent.clone(), sender.clone())
-                .unwrap();
-        });
+        self.manager.ssr_walk(root_parent, MessageSender::void());
+        self.manager.to_string()
     }
 }
 
@@ -172,50 +465,276 @@ where
 {
     /// Create a new App with a component `COMP` that has void props and events.
     fn default() -> Self {
-        App {
-            manager: ComponentWrapper::new((), ()),
+        App::with_props_and_events((), ())
+    }
+}
+
+impl App<RootParent> {
+    /// Renders the pending updates of all the mounted Apps right away,
+    /// instead of waiting for them to be scheduled.
+    ///
+    /// Useful in tests and in code which requires the DOM to be up to date,
+    /// like when focusing an element which was just rendered.
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use ruukh::prelude::*;
+    /// App::flush_sync();
+    /// ```
+    pub fn flush_sync() {
+        scheduler::flush_all();
+    }
+}
+
+/// A handle to a mounted App, with which it can be updated or unmounted.
+///
+/// Dropping the handle does not unmount the App, it lives on as long as the
+/// page does.
+pub struct AppHandle<COMP>
+where
+    COMP: Render,
+    COMP::Events: FromEventProps<RootParent>,
+{
+    root: Rc<RefCell<Option<Root<COMP>>>>,
+    scheduler: Rc<Scheduler>,
+}
+
+impl<COMP> AppHandle<COMP>
+where
+    COMP: Render,
+    COMP::Events: FromEventProps<RootParent>,
+{
+    /// Creates a handle for the mounted root which is re-rendered when the
+    /// scheduler runs the pending updates.
+    fn new(root: Root<COMP>, scheduler: Rc<Scheduler>) -> AppHandle<COMP> {
+        let root = Rc::new(RefCell::new(Some(root)));
+
+        let rerender_root = root.clone();
+        scheduler.set_renderer(move || {
+            if let Some(ref mut root) = *rerender_root.borrow_mut() {
+                root.render();
+            }
+        });
+        AppHandle { root, scheduler }
+    }
+
+    /// Updates the root component with newer props and events.
+    ///
+    /// The root component goes through its `update` and, if the props changed,
+    /// its `updated` lifecycle. It is then re-rendered as scheduled.
+    pub fn update_with_events(
+        &self,
+        props: COMP::Props,
+        events: <COMP::Events as FromEventProps<RootParent>>::From,
+    ) {
+        if let Some(ref mut root) = *self
+            .root
+            .try_borrow_mut()
+            .expect("The App cannot be updated while it is being rendered.")
+        {
+            root.update(props, events);
+        }
+        self.scheduler.schedule();
+    }
+
+    /// Unmounts the App from the DOM.
+    ///
+    /// Every component in the App is `destroyed`, and all of its DOM nodes and
+    /// event listeners are removed. The pending updates are discarded.
+    ///
+    /// # Example
+    /// ```ignore
+    /// # #![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+    /// #
+    /// # use ruukh::prelude::*;
+    /// #
+    /// # #[component]
+    /// # #[derive(Lifecycle)]
+    /// # struct MyApp;
+    /// #
+    /// # impl Render for MyApp {
+    /// #     fn render(&self) -> Markup<Self> {
+    /// #         html! {
+    /// #             "Hello World!"
+    /// #         }
+    /// #     }
+    /// # }
+    /// let handle = App::<MyApp>::new().mount("app");
+    /// handle.unmount();
+    /// ```
+    pub fn unmount(self) {
+        self.scheduler.stop();
+        let root = self
+            .root
+            .try_borrow_mut()
+            .expect("The App cannot be unmounted while it is being rendered.")
+            .take();
+        if let Some(root) = root {
+            root.unmount();
         }
     }
 }
 
-/// Create a `MessageChannel` to propagate state change message to the app.
-fn app_message_channel() -> (MessageReceiver, MessageSender) {
-    let msg_channel = MessageChannel::new().unwrap();
-    (
-        MessageReceiver(msg_channel.port2()),
-        MessageSender(msg_channel.port1()),
-    )
+impl<COMP> AppHandle<COMP>
+where
+    COMP: Render<Events = ()>,
+{
+    /// Updates the root component with newer props.
+    ///
+    /// The root component goes through its `update` and, if the props changed,
+    /// its `updated` lifecycle. It is then re-rendered as scheduled.
+    ///
+    /// # Example
+    /// ```ignore
+    /// # #![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+    /// #
+    /// # use ruukh::prelude::*;
+    /// #
+    /// #[component]
+    /// #[derive(Lifecycle)]
+    /// struct MyApp {
+    ///     name: String,
+    /// }
+    ///
+    /// impl Render for MyApp {
+    ///     fn render(&self) -> Markup<Self> {
+    ///         html! {
+    ///             "Hello "{ &self.name }"!"
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let handle = App::<MyApp>::with_props(MyAppProps!(name: "Ruukh".to_string()))
+    ///     .mount("app");
+    /// handle.update(MyAppProps!(name: "World".to_string()));
+    /// ```
+    pub fn update(&self, props: COMP::Props) {
+        self.update_with_events(props, ());
+    }
 }
 
-/// The receiving end of the message port which notifies the app for any state
-/// changes.
-pub struct MessageReceiver(MessagePort);
+/// The App which is mounted on the DOM.
+struct Root<COMP>
+where
+    COMP: Render,
+    COMP::Events: FromEventProps<RootParent>,
+{
+    manager: ComponentWrapper<COMP, RootParent>,
+    parent: Node,
+    root_parent: Shared<RootParent>,
+    rt: Runtime,
+    on_error: Box<dyn Fn(&RenderError) -> ErrorAction>,
+    /// The markup rendered instead of the App, once it has failed.
+    fallback: Option<Markup<RootParent>>,
+}
+
+impl<COMP> Root<COMP>
+where
+    COMP: Render,
+    COMP::Events: FromEventProps<RootParent>,
+{
+    /// Walks the App to render the pending updates.
+    fn render(&mut self) {
+        if self.fallback.is_some() {
+            return;
+        }
+        let rendered =
+            self.manager
+                .render_walk(&self.parent, None, self.root_parent.clone(), &self.rt);
+        self.recover(rendered);
+    }
+
+    /// Hydrates the App on the existing nodes of the parent.
+    fn hydrate(&mut self) {
+        let existing = self.rt.dom.first_child(&self.parent);
+        let hydrated = self
+            .manager
+            .hydrate(&self.parent, existing, self.root_parent.clone(), &self.rt)
+            .and_then(|unclaimed| {
+                hydrate::remove_unclaimed(&*self.rt.dom, &self.parent, unclaimed)
+            });
+        self.recover(hydrated);
+    }
 
-impl MessageReceiver {
-    /// Invokes the handler, when it receives a message.
-    fn react_on_message(self, mut handler: impl FnMut() + 'static) {
-        let closure: Closure<dyn FnMut(JsValue)> = Closure::wrap(Box::new(move |_| handler()));
-        self.0.set_onmessage(Some(closure.as_ref().unchecked_ref()));
+    /// Updates the root component with newer props and events.
+    fn update(
+        &mut self,
+        props: COMP::Props,
+        events: <COMP::Events as FromEventProps<RootParent>>::From,
+    ) {
+        self.manager
+            .update(props, events, self.root_parent.clone());
+    }
 
-        // Leak the closure so that the app lives on for 'static lifetimes.
-        closure.forget();
+    /// Removes the App from the DOM.
+    fn unmount(&self) {
+        let removed = match self.fallback {
+            Some(ref fallback) => fallback.remove(&self.parent, &self.rt),
+            None => self.manager.remove(&self.parent, &self.rt),
+        };
+        if let Err(error) = removed {
+            self.rt.dom.warn(&error.to_string());
+        }
+    }
+
+    /// Handles a failed render as decided by the error hook.
+    fn recover(&mut self, result: Result<(), RenderError>) {
+        let error = match result {
+            Ok(()) => return,
+            Err(error) => error,
+        };
+        let recovered = match (self.on_error)(&error) {
+            ErrorAction::Log => Err(error),
+            ErrorAction::Rerender => self
+                .clear()
+                .and_then(|_| self.manager.rerender(&self.parent, &self.rt)),
+            ErrorAction::Fallback(mut fallback) => {
+                let patched = self.clear().and_then(|_| {
+                    fallback.patch(None, &self.parent, None, self.root_parent.clone(), &self.rt)?;
+                    fallback.render_walk(&self.parent, None, self.root_parent.clone(), &self.rt)
+                });
+                self.fallback = Some(fallback);
+                patched
+            }
+        };
+        if let Err(error) = recovered {
+            self.rt.dom.warn(&error.to_string());
+        }
+    }
+
+    /// Removes all the nodes of the App, as the VDOM can no longer be trusted
+    /// to remove them.
+    fn clear(&self) -> Result<(), RenderError> {
+        while let Some(node) = self.rt.dom.first_child(&self.parent) {
+            self.rt
+                .dom
+                .remove_child(&self.parent, &node)
+                .map_err(RenderError::js(Operation::Remove))?;
+        }
+        Ok(())
     }
 }
 
 /// MessageSender is responsible to message the App about state changes.
 #[derive(Clone)]
-struct MessageSender(MessagePort);
+struct MessageSender(Option<Rc<Scheduler>>);
 
 impl MessageSender {
+    /// A sender which is not connected to any App. Used when there is no DOM
+    /// to react upon, i.e. while rendering to a string.
+    fn void() -> MessageSender {
+        MessageSender(None)
+    }
+
     /// Sends an update message to the App.
     ///
     /// The components need to call this method when they desire the app to
-    /// be notified of state changes.
+    /// be notified of state changes. The App is not rendered right away but
+    /// when its scheduler decides to.
     fn do_react(&self) {
-        // Just send a `null` as we have only a single message to be sent.
-        self.0
-            .post_message(&JsValue::null())
-            .expect("Could not send the message");
+        if let Some(ref scheduler) = self.0 {
+            scheduler.schedule();
+        }
     }
 }
 
@@ -262,5 +781,5 @@ impl AppMount for String {
 /// For use in tests.
 #[cfg(test)]
 fn message_sender() -> MessageSender {
-    app_message_channel().1
+    MessageSender(Some(Scheduler::new(Strategy::Immediate)))
 }
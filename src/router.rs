@@ -0,0 +1,459 @@
+//! Client-side routing of an App.
+//!
+//! The [Router](struct.Router.html) is mounted as the root of an App and
+//! renders the root component of the App within it. It provides the current
+//! [Location](struct.Location.html) as a [context](../context/index.html),
+//! so that any component may render according to the path, which is matched
+//! with a [Route](struct.Route.html).
+//!
+//! The [Link](struct.Link.html) navigates to a path without loading the page
+//! again, and going back or forward in the history re-renders the App as
+//! well.
+//!
+//! # Example
+//! ```
+//! # #![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+//! # use ruukh::prelude::*;
+//! use ruukh::router::{BrowserHistory, Link, LinkEvent, LinkProps, Location, Route, Router, RouterProps};
+//!
+//! #[component]
+//! #[derive(Lifecycle)]
+//! struct MyApp;
+//!
+//! impl Render for MyApp {
+//!     fn render(&self) -> Markup<Self> {
+//!         let location = self.context::<Location>().unwrap();
+//!         let page = match location.matches(&Route::new("/users/:id")) {
+//!             Some(params) => format!("User {}", params.get::<u64>("id").unwrap_or(0)),
+//!             None => "Home".to_string(),
+//!         };
+//!         html! {
+//!             <Link to="/users/1" text="First User"></Link>
+//!             <p>{ page }</p>
+//!         }
+//!     }
+//! }
+//!
+//! # fn run() {
+//! App::<Router<MyApp>>::with_props(RouterProps::new(BrowserHistory::new())).mount("app");
+//! # }
+//! ```
+
+use crate::{
+    component::{Component, Lifecycle, Render, Status},
+    vdom::{
+        vcomponent::VComponent,
+        velement::{Attribute, EventListener, VElement},
+        vtext::VText,
+        VNode,
+    },
+    Markup, Shared,
+};
+use std::{
+    cell::RefCell,
+    marker::PhantomData,
+    mem,
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::JsCast;
+use web_sys::{Event, MouseEvent};
+
+pub use self::{
+    history::{BrowserHistory, HashHistory, History, MemoryHistory},
+    route::{Params, Route},
+};
+
+mod history;
+mod route;
+
+/// The root of an App which routes. It renders the component `COMP` and
+/// re-renders it whenever the path changes.
+pub struct Router<COMP> {
+    history: Rc<dyn History>,
+    path: String,
+    status: Shared<Status<RouterState>>,
+    app: PhantomData<COMP>,
+}
+
+/// The props of a [Router](struct.Router.html).
+pub struct RouterProps {
+    /// The history which the router navigates through.
+    pub history: Rc<dyn History>,
+}
+
+impl RouterProps {
+    /// Creates the props with the given history.
+    pub fn new(history: impl History + 'static) -> RouterProps {
+        RouterProps {
+            history: Rc::new(history),
+        }
+    }
+}
+
+/// The state of a [Router](struct.Router.html).
+#[derive(Default)]
+pub struct RouterState {
+    path: String,
+}
+
+impl<COMP> Router<COMP> {
+    /// Re-renders the router whenever the history goes back or forward.
+    fn listen(&self) {
+        let history = Rc::downgrade(&self.history);
+        let status = Rc::downgrade(&self.status);
+        self.history.listen(Box::new(move || {
+            if let Some(history) = history.upgrade() {
+                navigated(&status, history.path());
+            }
+        }));
+    }
+}
+
+/// Updates the state of the router with the path it navigated to.
+fn navigated(status: &Weak<RefCell<Status<RouterState>>>, path: String) {
+    if let Some(status) = status.upgrade() {
+        let mut status = status.borrow_mut();
+        if status.state_as_ref().path != path {
+            status.state_as_mut().path = path;
+            status.set_state_dirty(true);
+            status.do_react();
+        }
+    }
+}
+
+impl<COMP> Component for Router<COMP>
+where
+    COMP: Render<Props = (), Events = ()>,
+{
+    type Props = RouterProps;
+    type Events = ();
+    type State = RouterState;
+
+    fn init(props: Self::Props, _: Self::Events, mut status: Status<Self::State>) -> Self {
+        let path = props.history.path();
+        status.state_as_mut().path = path.clone();
+        Router {
+            history: props.history,
+            path,
+            status: Rc::new(RefCell::new(status)),
+            app: PhantomData,
+        }
+    }
+
+    fn update(&mut self, props: Self::Props, _: Self::Events) -> Option<Self::Props> {
+        if Rc::ptr_eq(&self.history, &props.history) {
+            return None;
+        }
+        self.history.unlisten();
+        let old_history = mem::replace(&mut self.history, props.history);
+        self.listen();
+        navigated(&Rc::downgrade(&self.status), self.history.path());
+        self.status.borrow_mut().set_props_dirty(true);
+        Some(RouterProps {
+            history: old_history,
+        })
+    }
+
+    fn refresh_state(&mut self) {
+        let status = self.status.borrow();
+        let state = status.state_as_ref();
+        if self.path != state.path {
+            self.path = state.path.clone();
+        }
+    }
+
+    fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+        Some(&self.status)
+    }
+
+    fn set_state(&self, mut mutator: impl FnMut(&mut Self::State)) {
+        let mut status = self.status.borrow_mut();
+        mutator(status.state_as_mut());
+        if self.path != status.state_as_ref().path {
+            status.set_state_dirty(true);
+            status.do_react();
+        }
+    }
+}
+
+impl<COMP> Lifecycle for Router<COMP>
+where
+    COMP: Render<Props = (), Events = ()>,
+{
+    fn created(&self) {
+        self.listen();
+    }
+
+    fn destroyed(&self) {
+        self.history.unlisten();
+    }
+}
+
+impl<COMP> Render for Router<COMP>
+where
+    COMP: Render<Props = (), Events = ()>,
+{
+    fn render(&self) -> Markup<Self> {
+        self.provide(Location {
+            path: self.path.clone(),
+            history: self.history.clone(),
+            status: Rc::downgrade(&self.status),
+        });
+        VNode::from(VComponent::new::<COMP>((), ()))
+    }
+}
+
+/// The current location of a [Router](struct.Router.html), provided as a
+/// context to the components rendered within it.
+///
+/// The components which read it are re-rendered when the path changes.
+#[derive(Clone)]
+pub struct Location {
+    path: String,
+    history: Rc<dyn History>,
+    status: Weak<RefCell<Status<RouterState>>>,
+}
+
+impl PartialEq for Location {
+    fn eq(&self, other: &Location) -> bool {
+        self.path == other.path
+    }
+}
+
+impl Location {
+    /// The current path along with its query.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Matches the current path against the route.
+    pub fn matches(&self, route: &Route) -> Option<Params> {
+        route.matches(&self.path)
+    }
+
+    /// Navigates to the path by adding an entry to the history.
+    pub fn push(&self, path: &str) {
+        self.history.push(path);
+        navigated(&self.status, self.history.path());
+    }
+
+    /// Navigates to the path by replacing the current entry of the history.
+    pub fn replace(&self, path: &str) {
+        self.history.replace(path);
+        navigated(&self.status, self.history.path());
+    }
+
+    /// The `href` of a link to the path.
+    pub fn href(&self, path: &str) -> String {
+        self.history.href(path)
+    }
+}
+
+/// A link which navigates to a path within the [Router](struct.Router.html)
+/// without loading the page again.
+///
+/// Use it in `html!` as `<Link to="/users/1" text="First User"></Link>`,
+/// after importing `Link`, `LinkProps` and `LinkEvent`.
+pub struct Link {
+    to: String,
+    text: String,
+    /// The location read on the last render, used to navigate on click.
+    location: RefCell<Option<Rc<Location>>>,
+    status: Shared<Status<()>>,
+}
+
+/// The props of a [Link](struct.Link.html).
+pub struct LinkProps {
+    /// The path to navigate to.
+    pub to: String,
+    /// The text of the link.
+    pub text: String,
+}
+
+/// Creates the props of a [Link](struct.Link.html), as used by `html!`.
+pub macro LinkProps {
+    (text: $text:expr, to: $to:expr) => {
+        LinkProps {
+            to: Into::into($to),
+            text: Into::into($text),
+        }
+    },
+    (to: $to:expr, text: $text:expr) => {
+        LinkProps!(text: $text, to: $to)
+    },
+}
+
+/// Creates the events of a [Link](struct.Link.html), which has none, as used
+/// by `html!`.
+pub macro LinkEvent() {
+    ()
+}
+
+impl Link {
+    fn navigate(&self, event: &Event) {
+        // A click which opens the link elsewhere, like in a new tab, is left
+        // for the browser to handle.
+        if let Some(click) = event.dyn_ref::<MouseEvent>() {
+            if click.button() != 0
+                || click.ctrl_key()
+                || click.meta_key()
+                || click.shift_key()
+                || click.alt_key()
+            {
+                return;
+            }
+        }
+        if let Some(ref location) = *self.location.borrow() {
+            event.prevent_default();
+            location.push(&self.to);
+        }
+    }
+}
+
+impl Component for Link {
+    type Props = LinkProps;
+    type Events = ();
+    type State = ();
+
+    fn init(props: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+        Link {
+            to: props.to,
+            text: props.text,
+            location: RefCell::new(None),
+            status: Rc::new(RefCell::new(status)),
+        }
+    }
+
+    fn update(&mut self, mut props: Self::Props, _: Self::Events) -> Option<Self::Props> {
+        if self.to == props.to && self.text == props.text {
+            return None;
+        }
+        mem::swap(&mut self.to, &mut props.to);
+        mem::swap(&mut self.text, &mut props.text);
+        self.status.borrow_mut().set_props_dirty(true);
+        Some(props)
+    }
+
+    fn refresh_state(&mut self) {}
+
+    fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+        Some(&self.status)
+    }
+
+    fn set_state(&self, mut mutator: impl FnMut(&mut Self::State)) {
+        mutator(&mut ());
+    }
+}
+
+impl Lifecycle for Link {}
+
+impl Render for Link {
+    fn render(&self) -> Markup<Self> {
+        let location = self.context::<Location>();
+        let href = match location {
+            Some(ref location) => location.href(&self.to),
+            None => self.to.clone(),
+        };
+        *self.location.borrow_mut() = location;
+        VNode::from(VElement::new(
+            "a",
+            vec![Attribute::new("href", href)],
+            vec![EventListener::new(
+                "click",
+                Box::new(|this: &Link, event| this.navigate(&event)),
+            )],
+            VNode::from(VText::text(self.text.clone())),
+        ))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::{
+        component::root_render_ctx,
+        dom::test::memory_runtime,
+        vdom::vcomponent::{ComponentManager, ComponentWrapper},
+    };
+
+    struct Page;
+
+    impl Component for Page {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Page
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) {}
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+
+        fn set_state(&self, _: impl FnMut(&mut Self::State)) {
+            unreachable!()
+        }
+    }
+
+    impl Lifecycle for Page {}
+
+    impl Render for Page {
+        fn render(&self) -> Markup<Self> {
+            let location = self.context::<Location>().unwrap();
+            let page = match location.matches(&Route::new("/users/:id")) {
+                Some(params) => format!("User {}", params.get::<u64>("id").unwrap()),
+                None => "Not Found".to_string(),
+            };
+            VNode::from(vec![
+                VNode::from(VComponent::new::<Link>(
+                    LinkProps!(to: "/users/1", text: "First"),
+                    LinkEvent!(),
+                )),
+                VNode::from(VText::text(page)),
+            ])
+        }
+    }
+
+    #[test]
+    fn should_render_the_route_of_the_history() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let history = Rc::new(MemoryHistory::new("/users/1"));
+        let mut router = ComponentWrapper::<Router<Page>, ()>::new(
+            RouterProps {
+                history: history.clone(),
+            },
+            (),
+        );
+        router
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To render the router");
+        assert_eq!(
+            dom.inner_html(&parent),
+            r#"<a href="/users/1">First</a>User 1"#
+        );
+
+        history.push("/users/2");
+        history.back();
+        history.forward();
+        router
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To render the router");
+        assert_eq!(
+            dom.inner_html(&parent),
+            r#"<a href="/users/1">First</a>User 2"#
+        );
+    }
+
+    #[test]
+    fn should_link_to_the_hash_of_the_path() {
+        assert_eq!(HashHistory::default().href("/users/1"), "#/users/1");
+    }
+}
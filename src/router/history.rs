@@ -0,0 +1,279 @@
+//! The histories a router navigates through.
+
+use std::cell::{Cell, RefCell};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{window, Event};
+
+/// Trait to implement a history of paths which a router navigates through.
+pub trait History {
+    /// The current path along with its query, like `/users/1?tab=posts`.
+    fn path(&self) -> String;
+
+    /// Navigates to the path by adding an entry to the history.
+    fn push(&self, path: &str);
+
+    /// Navigates to the path by replacing the current entry of the history.
+    fn replace(&self, path: &str);
+
+    /// The `href` of a link to the path.
+    fn href(&self, path: &str) -> String {
+        path.to_string()
+    }
+
+    /// Sets the callback to be invoked when the path is changed by going back
+    /// or forward in the history. It is not invoked on `push` and `replace`.
+    ///
+    /// It replaces the previously set callback.
+    fn listen(&self, on_change: Box<dyn Fn()>);
+
+    /// Removes the callback set by `listen`.
+    fn unlisten(&self);
+}
+
+/// The history of the browser, where the path is the URL path. The history
+/// is navigated with `history.pushState`.
+///
+/// The server must serve the App on all of the paths it routes to.
+#[derive(Default)]
+pub struct BrowserHistory {
+    popstate: PopState,
+}
+
+impl BrowserHistory {
+    /// Creates a history on the current window.
+    pub fn new() -> BrowserHistory {
+        BrowserHistory::default()
+    }
+}
+
+impl History for BrowserHistory {
+    fn path(&self) -> String {
+        let location = window().unwrap().location();
+        format!(
+            "{}{}",
+            location.pathname().unwrap(),
+            location.search().unwrap()
+        )
+    }
+
+    fn push(&self, path: &str) {
+        window()
+            .unwrap()
+            .history()
+            .unwrap()
+            .push_state_with_url(&JsValue::NULL, "", Some(path))
+            .expect("Could not push the path onto the history");
+    }
+
+    fn replace(&self, path: &str) {
+        window()
+            .unwrap()
+            .history()
+            .unwrap()
+            .replace_state_with_url(&JsValue::NULL, "", Some(path))
+            .expect("Could not replace the path in the history");
+    }
+
+    fn listen(&self, on_change: Box<dyn Fn()>) {
+        self.popstate.listen(on_change);
+    }
+
+    fn unlisten(&self) {
+        self.popstate.unlisten();
+    }
+}
+
+/// The history of the browser, where the path is kept in the hash of the URL,
+/// like `/#/users/1`.
+///
+/// It is a fallback for when the server cannot serve the App on all of the
+/// paths it routes to.
+#[derive(Default)]
+pub struct HashHistory {
+    popstate: PopState,
+}
+
+impl HashHistory {
+    /// Creates a history on the current window.
+    pub fn new() -> HashHistory {
+        HashHistory::default()
+    }
+}
+
+impl History for HashHistory {
+    fn path(&self) -> String {
+        let hash = window().unwrap().location().hash().unwrap();
+        match hash.get(1..) {
+            Some(path) if !path.is_empty() => path.to_string(),
+            _ => "/".to_string(),
+        }
+    }
+
+    fn push(&self, path: &str) {
+        window()
+            .unwrap()
+            .history()
+            .unwrap()
+            .push_state_with_url(&JsValue::NULL, "", Some(&self.href(path)))
+            .expect("Could not push the path onto the history");
+    }
+
+    fn replace(&self, path: &str) {
+        window()
+            .unwrap()
+            .history()
+            .unwrap()
+            .replace_state_with_url(&JsValue::NULL, "", Some(&self.href(path)))
+            .expect("Could not replace the path in the history");
+    }
+
+    fn href(&self, path: &str) -> String {
+        format!("#{}", path)
+    }
+
+    fn listen(&self, on_change: Box<dyn Fn()>) {
+        // Editing the hash of the URL by hand fires a `popstate` as well.
+        self.popstate.listen(on_change);
+    }
+
+    fn unlisten(&self) {
+        self.popstate.unlisten();
+    }
+}
+
+type Listener = Closure<dyn Fn(Event)>;
+
+/// The `popstate` listener on the window.
+#[derive(Default)]
+struct PopState {
+    listener: RefCell<Option<Listener>>,
+}
+
+impl PopState {
+    fn listen(&self, on_change: Box<dyn Fn()>) {
+        self.unlisten();
+        let listener: Listener = Closure::wrap(Box::new(move |_| on_change()));
+        window()
+            .unwrap()
+            .add_event_listener_with_callback("popstate", listener.as_ref().unchecked_ref())
+            .expect("Could not listen to `popstate`");
+        *self.listener.borrow_mut() = Some(listener);
+    }
+
+    fn unlisten(&self) {
+        if let Some(listener) = self.listener.borrow_mut().take() {
+            window()
+                .unwrap()
+                .remove_event_listener_with_callback("popstate", listener.as_ref().unchecked_ref())
+                .expect("Could not stop listening to `popstate`");
+        }
+    }
+}
+
+/// A history kept in memory, so that the routing may be tested without a
+/// browser.
+pub struct MemoryHistory {
+    entries: RefCell<Vec<String>>,
+    /// The index of the current entry.
+    current: Cell<usize>,
+    on_change: RefCell<Option<Box<dyn Fn()>>>,
+}
+
+impl MemoryHistory {
+    /// Creates a history with a single entry of the given path.
+    pub fn new(path: impl Into<String>) -> MemoryHistory {
+        MemoryHistory {
+            entries: RefCell::new(vec![path.into()]),
+            current: Cell::new(0),
+            on_change: RefCell::new(None),
+        }
+    }
+
+    /// Goes back to the previous entry, like the back button of the browser.
+    pub fn back(&self) {
+        self.go(-1);
+    }
+
+    /// Goes forward to the next entry, like the forward button of the
+    /// browser.
+    pub fn forward(&self) {
+        self.go(1);
+    }
+
+    /// Moves by `delta` entries in the history. It does nothing when there
+    /// is no such entry.
+    pub fn go(&self, delta: isize) {
+        let target = self.current.get() as isize + delta;
+        if delta == 0 || target < 0 || target as usize >= self.entries.borrow().len() {
+            return;
+        }
+        self.current.set(target as usize);
+        if let Some(ref on_change) = *self.on_change.borrow() {
+            on_change();
+        }
+    }
+
+    /// The count of entries in the history.
+    pub fn length(&self) -> usize {
+        self.entries.borrow().len()
+    }
+}
+
+impl History for MemoryHistory {
+    fn path(&self) -> String {
+        self.entries.borrow()[self.current.get()].clone()
+    }
+
+    fn push(&self, path: &str) {
+        let mut entries = self.entries.borrow_mut();
+        let next = self.current.get() + 1;
+        // The entries after the current one are lost, as in a browser.
+        entries.truncate(next);
+        entries.push(path.to_string());
+        self.current.set(next);
+    }
+
+    fn replace(&self, path: &str) {
+        self.entries.borrow_mut()[self.current.get()] = path.to_string();
+    }
+
+    fn listen(&self, on_change: Box<dyn Fn()>) {
+        *self.on_change.borrow_mut() = Some(on_change);
+    }
+
+    fn unlisten(&self) {
+        self.on_change.borrow_mut().take();
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn should_navigate_through_memory_history() {
+        let history = MemoryHistory::new("/");
+        let changes = Rc::new(Cell::new(0));
+        let counter = changes.clone();
+        history.listen(Box::new(move || counter.set(counter.get() + 1)));
+
+        history.push("/users");
+        history.push("/users/1");
+        assert_eq!(history.path(), "/users/1");
+
+        history.back();
+        history.back();
+        history.back();
+        assert_eq!(history.path(), "/");
+        assert_eq!(changes.get(), 2);
+
+        history.forward();
+        history.replace("/posts");
+        history.push("/posts/1");
+        assert_eq!(history.length(), 3);
+        history.back();
+        assert_eq!(history.path(), "/posts");
+        assert_eq!(changes.get(), 4);
+    }
+}
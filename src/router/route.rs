@@ -0,0 +1,149 @@
+//! Matching of the paths against route patterns.
+
+use std::str::FromStr;
+
+/// A pattern of paths, like `/users/:id/posts`.
+///
+/// A segment starting with `:` is a parameter which matches any segment,
+/// and a `*` at the end matches the rest of the path, if any. Every other
+/// segment must match exactly.
+///
+/// # Example
+/// ```
+/// # use ruukh::router::Route;
+/// let route = Route::new("/users/:id");
+/// let params = route.matches("/users/42?tab=posts").unwrap();
+/// assert_eq!(params.get::<u64>("id"), Some(42));
+///
+/// assert!(route.matches("/users").is_none());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route {
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    /// A segment which must match as is.
+    Exact(&'static str),
+    /// A named parameter.
+    Param(&'static str),
+    /// Matches the rest of the path.
+    Rest,
+}
+
+/// The parameters of a path matched by a [Route](struct.Route.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Params {
+    params: Vec<(&'static str, String)>,
+}
+
+impl Route {
+    /// Creates a route from its pattern.
+    ///
+    /// Panics if a `*` is not the last segment of the pattern.
+    pub fn new(pattern: &'static str) -> Route {
+        let segments: Vec<_> = segments(pattern)
+            .map(|segment| {
+                if segment == "*" {
+                    Segment::Rest
+                } else if let Some(name) = segment.strip_prefix(':') {
+                    Segment::Param(name)
+                } else {
+                    Segment::Exact(segment)
+                }
+            }).collect();
+        assert!(
+            segments
+                .iter()
+                .rev()
+                .skip(1)
+                .all(|segment| *segment != Segment::Rest),
+            "A `*` may only be at the end of the route `{}`.",
+            pattern
+        );
+        Route { segments }
+    }
+
+    /// Matches the path, ignoring its query and hash, and returns the
+    /// parameters in it.
+    pub fn matches(&self, path: &str) -> Option<Params> {
+        let path = path.split(['?', '#']).next().unwrap_or("");
+        let mut path_segments = segments(path);
+        let mut params = vec![];
+        for segment in self.segments.iter() {
+            match *segment {
+                Segment::Rest => return Some(Params { params }),
+                Segment::Exact(expected) => {
+                    if path_segments.next() != Some(expected) {
+                        return None;
+                    }
+                }
+                Segment::Param(name) => {
+                    params.push((name, path_segments.next()?.to_string()));
+                }
+            }
+        }
+        if path_segments.next().is_some() {
+            return None;
+        }
+        Some(Params { params })
+    }
+}
+
+impl Params {
+    /// Gets the parameter parsed as `T`. Returns `None` if there is no such
+    /// parameter or if it could not be parsed.
+    pub fn get<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.raw(name).and_then(|value| value.parse().ok())
+    }
+
+    /// Gets the parameter as it is in the path.
+    pub fn raw(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| *param == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// The non-empty segments of a path.
+fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn should_match_exact_routes() {
+        let route = Route::new("/users/new");
+        assert!(route.matches("/users/new").is_some());
+        assert!(route.matches("/users/new/").is_some());
+        assert!(route.matches("/users").is_none());
+        assert!(route.matches("/users/new/1").is_none());
+
+        assert!(Route::new("/").matches("/").is_some());
+        assert!(Route::new("/").matches("/users").is_none());
+    }
+
+    #[test]
+    fn should_match_typed_params() {
+        let route = Route::new("/users/:id/posts/:slug");
+        let params = route.matches("/users/42/posts/hello?draft#top").unwrap();
+
+        assert_eq!(params.get::<u64>("id"), Some(42));
+        assert_eq!(params.get::<String>("slug"), Some("hello".to_string()));
+        assert_eq!(params.get::<u64>("slug"), None);
+        assert_eq!(params.get::<u64>("missing"), None);
+    }
+
+    #[test]
+    fn should_match_the_rest_of_the_path() {
+        let route = Route::new("/files/*");
+        assert!(route.matches("/files").is_some());
+        assert!(route.matches("/files/a/b/c").is_some());
+        assert!(route.matches("/folders/a").is_none());
+    }
+}
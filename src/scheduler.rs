@@ -0,0 +1,367 @@
+//! Scheduling of the re-renders of an App.
+//!
+//! A state change does not re-render the App right away. It only marks the
+//! App as pending, and the scheduler walks the App once, at a point decided
+//! by its [Strategy](enum.Strategy.html). So, any number of state changes done
+//! in a single event handler results in a single walk.
+
+use js_sys::Promise;
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{window, MessageChannel, MessagePort};
+
+/// The strategy by which the scheduler decides when to re-render the App.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strategy {
+    /// Every update is rendered in a walk of its own, as a new task right
+    /// after the current one.
+    Immediate,
+    /// The updates are batched and rendered once at the end of the current
+    /// task, as a microtask. This is the default.
+    #[default]
+    Microtask,
+    /// The updates are batched and rendered once, right before the next
+    /// repaint of the browser with `requestAnimationFrame`.
+    AnimationFrame,
+}
+
+thread_local! {
+    /// All the schedulers of the Apps that are running.
+    static SCHEDULERS: RefCell<Vec<Weak<Scheduler>>> = const { RefCell::new(vec![]) };
+}
+
+/// Schedules the re-renders of a single App.
+pub(crate) struct Scheduler {
+    strategy: Strategy,
+    /// The count of updates which are yet to be rendered.
+    pending: Cell<usize>,
+    /// The count of triggers which are yet to invoke the callback. It is kept
+    /// apart from the pending updates, as those may be flushed synchronously
+    /// while the trigger is still on its way.
+    in_flight: Cell<usize>,
+    /// Walks the App to render the updates.
+    renderer: RefCell<Option<Box<dyn FnMut()>>>,
+    /// The JS handles that trigger a scheduled walk, created on first use.
+    trigger: RefCell<Option<Trigger>>,
+    /// A handle to itself to be invoked by the trigger.
+    this: RefCell<Weak<Scheduler>>,
+    /// Whether the App was unmounted.
+    stopped: Cell<bool>,
+}
+
+/// The JS callback which runs the scheduled walk, along with the handles
+/// which invoke it.
+struct Trigger {
+    callback: Closure<dyn FnMut(JsValue)>,
+    /// The message channel ports to post to and receive from, when the
+    /// strategy is `Immediate`.
+    ports: Option<(MessagePort, MessagePort)>,
+    /// The last requested animation frame, when the strategy is
+    /// `AnimationFrame`.
+    frame: Cell<Option<i32>>,
+}
+
+impl Scheduler {
+    /// Creates a scheduler and registers it to be flushed by `flush_all`.
+    pub(crate) fn new(strategy: Strategy) -> Rc<Scheduler> {
+        let scheduler = Rc::new(Scheduler {
+            strategy,
+            pending: Cell::new(0),
+            in_flight: Cell::new(0),
+            renderer: RefCell::new(None),
+            trigger: RefCell::new(None),
+            this: RefCell::new(Weak::new()),
+            stopped: Cell::new(false),
+        });
+        *scheduler.this.borrow_mut() = Rc::downgrade(&scheduler);
+        SCHEDULERS.with(|schedulers| {
+            let mut schedulers = schedulers.borrow_mut();
+            schedulers.retain(|scheduler| scheduler.upgrade().is_some());
+            schedulers.push(Rc::downgrade(&scheduler));
+        });
+        scheduler
+    }
+
+    /// The count of updates which are yet to be rendered.
+    #[cfg(test)]
+    pub(crate) fn pending(&self) -> usize {
+        self.pending.get()
+    }
+
+    /// Sets the walk which renders the pending updates.
+    pub(crate) fn set_renderer(&self, renderer: impl FnMut() + 'static) {
+        *self.renderer.borrow_mut() = Some(Box::new(renderer));
+    }
+
+    /// Marks an update as pending and schedules a walk for it, unless one is
+    /// already scheduled. A trigger which is yet to fire renders whatever is
+    /// pending by then, even if it was flushed since it was requested.
+    pub(crate) fn schedule(&self) {
+        if self.stopped.get() {
+            return;
+        }
+        self.pending.set(self.pending.get() + 1);
+        if self.in_flight.get() == 0 || self.strategy == Strategy::Immediate {
+            self.trigger();
+        }
+    }
+
+    /// Renders the pending updates right away.
+    pub(crate) fn flush(&self) {
+        let pending = self.pending.replace(0);
+        if pending == 0 {
+            return;
+        }
+        if !self.render() {
+            // The ongoing walk may have already passed the components which
+            // are updated, so they are rendered by a walk of their own.
+            self.pending.set(self.pending.get() + pending);
+            if self.in_flight.get() == 0 {
+                self.trigger();
+            }
+        }
+    }
+
+    /// Runs a scheduled walk. The updates may have already been flushed
+    /// synchronously, in which case there is nothing to do.
+    fn run_scheduled(&self) {
+        self.in_flight
+            .set(self.in_flight.get().saturating_sub(1));
+        match self.pending.get() {
+            0 => {}
+            pending if self.strategy == Strategy::Immediate => {
+                self.pending.set(pending - 1);
+                if !self.render() {
+                    self.pending.set(self.pending.get() + 1);
+                    self.trigger();
+                }
+            }
+            _ => self.flush(),
+        }
+    }
+
+    /// Walks the App, unless it is already being walked. Returns whether it
+    /// was walked.
+    fn render(&self) -> bool {
+        match self.renderer.try_borrow_mut() {
+            Ok(mut renderer) => {
+                if let Some(ref mut renderer) = *renderer {
+                    renderer();
+                }
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn trigger(&self) {
+        let mut trigger = self.trigger.borrow_mut();
+        if trigger.is_none() {
+            *trigger = Some(self.create_trigger());
+        }
+        let trigger = trigger.as_ref().unwrap();
+        let callback = trigger.callback.as_ref().unchecked_ref();
+        match self.strategy {
+            Strategy::Immediate => {
+                // Just send a `null` as we have only a single message to be sent.
+                let (ref sender, _) = trigger.ports.as_ref().unwrap();
+                sender
+                    .post_message(&JsValue::null())
+                    .expect("Could not send the message");
+            }
+            Strategy::Microtask => {
+                // The promise is dropped right away, as the callback is all
+                // that matters.
+                let _ = Promise::resolve(&JsValue::null()).then(&trigger.callback);
+            }
+            Strategy::AnimationFrame => {
+                let frame = window()
+                    .unwrap()
+                    .request_animation_frame(callback)
+                    .expect("Could not request an animation frame");
+                trigger.frame.set(Some(frame));
+            }
+        }
+        self.in_flight.set(self.in_flight.get() + 1);
+    }
+
+    fn create_trigger(&self) -> Trigger {
+        let scheduler = self.this.borrow().clone();
+        let callback: Closure<dyn FnMut(JsValue)> = Closure::wrap(Box::new(move |_| {
+            if let Some(scheduler) = scheduler.upgrade() {
+                scheduler.run_scheduled();
+            }
+        }));
+        let ports = if self.strategy == Strategy::Immediate {
+            let msg_channel = MessageChannel::new().unwrap();
+            let receiver = msg_channel.port2();
+            receiver.set_onmessage(Some(callback.as_ref().unchecked_ref()));
+            Some((msg_channel.port1(), receiver))
+        } else {
+            None
+        };
+        Trigger {
+            callback,
+            ports,
+            frame: Cell::new(None),
+        }
+    }
+
+    /// Stops scheduling any more walks and drops the walk along with the JS
+    /// handles which trigger it.
+    pub(crate) fn stop(&self) {
+        self.stopped.set(true);
+        self.pending.set(0);
+        let in_flight = self.in_flight.replace(0) > 0;
+        self.renderer.borrow_mut().take();
+        if let Some(trigger) = self.trigger.borrow_mut().take() {
+            if let Some((ref sender, ref receiver)) = trigger.ports {
+                receiver.set_onmessage(None);
+                sender.close();
+                receiver.close();
+            }
+            if in_flight {
+                match self.strategy {
+                    // The frame in flight is the only one requested, so it
+                    // never invokes the callback once it is cancelled.
+                    Strategy::AnimationFrame => {
+                        if let Some(frame) = trigger.frame.get() {
+                            let _ = window().unwrap().cancel_animation_frame(frame);
+                        }
+                    }
+                    // A resolved promise cannot be cancelled, so the callback
+                    // must outlive it.
+                    Strategy::Microtask => trigger.callback.forget(),
+                    Strategy::Immediate => {}
+                }
+            }
+        }
+    }
+}
+
+/// Renders the pending updates of all the running Apps right away.
+pub(crate) fn flush_all() {
+    let schedulers: Vec<_> = SCHEDULERS.with(|schedulers| {
+        schedulers
+            .borrow()
+            .iter()
+            .filter_map(|scheduler| scheduler.upgrade())
+            .collect()
+    });
+    for scheduler in schedulers {
+        scheduler.flush();
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    fn counted(strategy: Strategy) -> (Rc<Scheduler>, Rc<Cell<usize>>) {
+        let scheduler = Scheduler::new(strategy);
+        let walks = Rc::new(Cell::new(0));
+        let counter = walks.clone();
+        scheduler.set_renderer(move || counter.set(counter.get() + 1));
+        (scheduler, walks)
+    }
+
+    #[wasm_bindgen_test]
+    fn should_batch_updates_into_a_single_walk() {
+        let (scheduler, walks) = counted(Strategy::Microtask);
+        for _ in 0..10 {
+            scheduler.schedule();
+        }
+        scheduler.flush();
+        assert_eq!(walks.get(), 1);
+
+        scheduler.run_scheduled();
+        assert_eq!(walks.get(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_not_trigger_again_while_one_is_in_flight() {
+        let (scheduler, walks) = counted(Strategy::AnimationFrame);
+        scheduler.schedule();
+        scheduler.flush();
+        scheduler.schedule();
+        assert_eq!(scheduler.in_flight.get(), 1);
+
+        scheduler.run_scheduled();
+        assert_eq!(scheduler.in_flight.get(), 0);
+        assert_eq!(walks.get(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_keep_the_callback_in_flight_after_a_flush() {
+        let (scheduler, walks) = counted(Strategy::Microtask);
+        scheduler.schedule();
+        scheduler.flush();
+        assert_eq!(scheduler.in_flight.get(), 1);
+
+        // The promise still invokes the callback after the stop, which must
+        // not throw.
+        scheduler.stop();
+        assert_eq!(scheduler.in_flight.get(), 0);
+        assert_eq!(walks.get(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_keep_the_updates_flushed_while_walking() {
+        let scheduler = Scheduler::new(Strategy::Microtask);
+        let walks = Rc::new(Cell::new(0));
+        let (counter, this) = (walks.clone(), Rc::downgrade(&scheduler));
+        scheduler.set_renderer(move || {
+            counter.set(counter.get() + 1);
+            // Like a component updated in a hook, and flushed right away.
+            if counter.get() == 1 {
+                let scheduler = this.upgrade().unwrap();
+                scheduler.schedule();
+                scheduler.flush();
+            }
+        });
+        scheduler.schedule();
+        scheduler.flush();
+        assert_eq!(walks.get(), 1);
+        assert_eq!(scheduler.pending.get(), 1);
+        assert_eq!(scheduler.in_flight.get(), 1);
+
+        scheduler.run_scheduled();
+        assert_eq!(walks.get(), 2);
+        assert_eq!(scheduler.pending.get(), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_walk_for_every_update_when_immediate() {
+        let (scheduler, walks) = counted(Strategy::Immediate);
+        scheduler.schedule();
+        scheduler.schedule();
+        scheduler.run_scheduled();
+        scheduler.run_scheduled();
+        assert_eq!(walks.get(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_flush_all_the_apps() {
+        let (first, first_walks) = counted(Strategy::AnimationFrame);
+        let (second, second_walks) = counted(Strategy::Microtask);
+        first.schedule();
+        second.schedule();
+        flush_all();
+        assert_eq!(first_walks.get(), 1);
+        assert_eq!(second_walks.get(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_not_walk_once_stopped() {
+        let (scheduler, walks) = counted(Strategy::AnimationFrame);
+        scheduler.schedule();
+        scheduler.stop();
+        scheduler.flush();
+        scheduler.run_scheduled();
+        assert_eq!(walks.get(), 0);
+    }
+}
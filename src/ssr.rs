@@ -0,0 +1,129 @@
+//! Rendering of the VDOM into an HTML string, so that an App may be served
+//! already rendered from a server.
+//!
+//! The VDOM types already know how to display themselves as markup. What they
+//! lack before being mounted is the rendered markup of their components, which
+//! is what [SSRWalk](trait.SSRWalk.html) builds without touching the DOM.
+
+use crate::{component::Render, MessageSender, Shared};
+use std::borrow::Cow;
+
+/// Trait to build up the complete VDOM tree without a DOM.
+pub(crate) trait SSRWalk {
+    /// The render context of this walk.
+    type RenderContext: Render;
+
+    /// Walks through the VDOM and initializes every component found along the
+    /// way. The components are `created` and rendered but never `mounted` as
+    /// there is no DOM to mount them onto.
+    fn ssr_walk(&mut self, render_ctx: Shared<Self::RenderContext>, rx_sender: MessageSender);
+}
+
+/// Escapes a text so that it is safe to be placed as the content of an
+/// element.
+pub(crate) fn escape_text(text: &str) -> Cow<'_, str> {
+    escape(text, |ch| match ch {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        _ => None,
+    })
+}
+
+/// Escapes a text so that it is safe to be placed within a quoted attribute
+/// value.
+pub(crate) fn escape_attribute(value: &str) -> Cow<'_, str> {
+    escape(value, |ch| match ch {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        '"' => Some("&quot;"),
+        '\'' => Some("&#39;"),
+        _ => None,
+    })
+}
+
+/// Escapes a text so that it is safe to be placed within a comment.
+///
+/// Entities are not decoded inside comments, so instead of escaping, the
+/// sequences which may terminate the comment early are broken up with a
+/// space. i.e. A comment cannot start with `>` or `->` and cannot contain
+/// `--` at all.
+pub(crate) fn escape_comment(comment: &str) -> Cow<'_, str> {
+    let unsafe_start = comment.starts_with('>') || comment.starts_with("->");
+    if !unsafe_start && !comment.contains("--") && !comment.ends_with('-') {
+        return Cow::Borrowed(comment);
+    }
+
+    let mut escaped = String::with_capacity(comment.len() + 2);
+    if unsafe_start {
+        escaped.push(' ');
+    }
+    let mut last = None;
+    for ch in comment.chars() {
+        if ch == '-' && last == Some('-') {
+            escaped.push(' ');
+        }
+        escaped.push(ch);
+        last = Some(ch);
+    }
+    // A trailing `-` would form `--->` with the terminator.
+    if last == Some('-') {
+        escaped.push(' ');
+    }
+    Cow::Owned(escaped)
+}
+
+fn escape(text: &str, replacement: impl Fn(char) -> Option<&'static str>) -> Cow<'_, str> {
+    if !text.chars().any(|ch| replacement(ch).is_some()) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut escaped = String::with_capacity(text.len() + 8);
+    for ch in text.chars() {
+        match replacement(ch) {
+            Some(entity) => escaped.push_str(entity),
+            None => escaped.push(ch),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_escape_text() {
+        assert_eq!(
+            escape_text("<script>alert('1 & 2')</script>"),
+            "&lt;script&gt;alert('1 &amp; 2')&lt;/script&gt;"
+        );
+    }
+
+    #[test]
+    fn should_not_allocate_for_safe_text() {
+        match escape_text("Nothing to escape here.") {
+            Cow::Borrowed(_) => {}
+            Cow::Owned(_) => panic!("Safe text should be borrowed as is"),
+        }
+    }
+
+    #[test]
+    fn should_escape_attribute() {
+        assert_eq!(
+            escape_attribute(r#"" onclick="alert('x')"#),
+            "&quot; onclick=&quot;alert(&#39;x&#39;)"
+        );
+    }
+
+    #[test]
+    fn should_escape_comment_terminators() {
+        assert_eq!(escape_comment("a --> b"), "a - -> b");
+        assert_eq!(escape_comment("a --!> b"), "a - -!> b");
+        assert_eq!(escape_comment(">a"), " >a");
+        assert_eq!(escape_comment("->a"), " ->a");
+        assert_eq!(escape_comment("a-"), "a- ");
+        assert_eq!(escape_comment("a - b"), "a - b");
+    }
+}
@@ -1,12 +1,26 @@
 //! Conversion from the non-component types to VNode for use in html!
 //! expression blocks. Allows the user to use basic types such as string and
 //! number types ergonomically within html! expression blocks.
+//!
+//! A `Vec` or an iterator of vnodes, or of vnodes along with their keys, is
+//! converted to a list. The keys of the items rendered with a single keyed
+//! element are picked up, so that they keep their identity on reorder. Only
+//! the iterators of the common adapters, like `Map` or `Filter`, are converted
+//! directly. Any other iterator is to be collected into a `VNode`.
 
 use crate::{
     component::Render,
-    vdom::{vlist::VList, vtext::VText, VNode},
+    vdom::{
+        vlist::{ListItem, VList},
+        vtext::VText,
+        VNode,
+    },
+};
+use std::{
+    borrow::Cow,
+    iter::{Chain, Filter, FilterMap, FromIterator, Map, Rev, Skip, Take},
+    option, vec,
 };
-use std::borrow::Cow;
 
 impl<RCTX: Render> From<String> for VNode<RCTX> {
     fn from(value: String) -> VNode<RCTX> {
@@ -54,8 +68,49 @@ impl_with_to_string!(
     i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool
 );
 
-impl<RCTX: Render> From<Vec<VNode<RCTX>>> for VNode<RCTX> {
-    fn from(value: Vec<VNode<RCTX>>) -> VNode<RCTX> {
+impl<RCTX: Render, T: ListItem<RCTX>> From<Vec<T>> for VNode<RCTX> {
+    fn from(value: Vec<T>) -> VNode<RCTX> {
         VNode::from(VList::from(value))
     }
 }
+
+impl<RCTX: Render, T: ListItem<RCTX>> FromIterator<T> for VNode<RCTX> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> VNode<RCTX> {
+        VNode::from(VList::from_iter(iter))
+    }
+}
+
+/// A blanket conversion from every iterator would overlap with the other
+/// conversions, so only the commonly used iterators are converted. Any other
+/// iterator can be collected into a `VNode`.
+macro_rules! impl_from_iterator {
+    ($($iter:ident<$($param:ident),*>),*) => {
+        $(
+            impl<RCTX: Render, T: ListItem<RCTX>, $($param),*> From<$iter<$($param),*>>
+                for VNode<RCTX>
+            where
+                $iter<$($param),*>: Iterator<Item = T>,
+            {
+                fn from(iter: $iter<$($param),*>) -> VNode<RCTX> {
+                    iter.collect()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_iterator!(
+    Map<I, F>, FilterMap<I, F>, Filter<I, P>, Chain<A, B>, Rev<I>, Skip<I>, Take<I>
+);
+
+impl<RCTX: Render, T: ListItem<RCTX>> From<vec::IntoIter<T>> for VNode<RCTX> {
+    fn from(iter: vec::IntoIter<T>) -> VNode<RCTX> {
+        iter.collect()
+    }
+}
+
+impl<RCTX: Render, T: ListItem<RCTX>> From<option::IntoIter<T>> for VNode<RCTX> {
+    fn from(iter: option::IntoIter<T>) -> VNode<RCTX> {
+        iter.collect()
+    }
+}
@@ -2,26 +2,31 @@
 
 use crate::{
     component::Render,
-    dom::DOMPatch,
+    dom::{DOMPatch, Node, Runtime},
+    error::RenderError,
+    hydrate::Hydrate,
+    ssr::SSRWalk,
     vdom::{
         vcomponent::VComponent,
         velement::VElement,
         vlist::VList,
+        vslot::VSlot,
         vtext::VText
     },
     MessageSender,
     Shared
 };
+use fnv::FnvHasher;
 use std::{
     borrow::Cow, 
-    fmt::{self, Display, Formatter}
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher}
 };
-use wasm_bindgen::prelude::JsValue;
-use web_sys::Node;
 
 pub mod vcomponent;
 pub mod velement;
 pub mod vlist;
+pub mod vslot;
 pub mod vtext;
 mod conversions;
 
@@ -35,6 +40,8 @@ pub enum VNode<RCTX: Render> {
     List(VList<RCTX>),
     /// A component vnode
     Component(VComponent<RCTX>),
+    /// A vnode of the markup passed into a component
+    Slot(VSlot),
     /// The empty variant
     None
 }
@@ -56,6 +63,7 @@ impl<RCTX: Render> Display for VNode<RCTX> {
             VNode::Element(inner) => write!(f, "{}", inner),
             VNode::List(inner) => write!(f, "{}", inner),
             VNode::Component(inner) => write!(f, "{}", inner),
+            VNode::Slot(inner) => write!(f, "{}", inner),
             VNode::None => Ok(())
         }
     }
@@ -68,19 +76,19 @@ macro_rules! patch {
         $parent:ident, 
         $next:ident, 
         $render_ctx:ident, 
-        $rx_sender:ident
+        $rt:ident
     ) => {
         match $old {
             Some(VNode::$variant(old)) => {
                 // If the variant is same patch it.
-                $this.patch(Some(old), $parent, $next, $render_ctx, $rx_sender)
+                $this.patch(Some(old), $parent, $next, $render_ctx, $rt)
             }
             Some(old) => {
                 // If it is a different variant, remove the old one.
-                old.remove($parent)?;
-                $this.patch(None, $parent, $next, $render_ctx, $rx_sender)
+                old.remove($parent, $rt)?;
+                $this.patch(None, $parent, $next, $render_ctx, $rt)
             }
-            None => $this.patch(None, $parent, $next, $render_ctx, $rx_sender),
+            None => $this.patch(None, $parent, $next, $render_ctx, $rt),
         }
     };
 }
@@ -94,12 +102,14 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
         parent: &Self::Node,
         next: Option<&Self::Node>,
         render_ctx: Shared<Self::RenderContext>,
-        rx_sender: MessageSender,
-    ) -> Result<(), JsValue> {
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
         match self {
-            VNode::Element(ref mut el) => el.render_walk(parent, next, render_ctx, rx_sender),
-            VNode::List(ref mut list) => list.render_walk(parent, next, render_ctx, rx_sender),
-            VNode::Component(ref mut comp) => comp.render_walk(parent, next, render_ctx, rx_sender),
+            VNode::Element(ref mut el) => el.render_walk(parent, next, render_ctx, rt),
+            VNode::List(ref mut list) => list.render_walk(parent, next, render_ctx, rt),
+            VNode::Component(ref mut comp) => comp.render_walk(parent, next, render_ctx, rt),
+            // The markup is walked in the context of the component which wrote it.
+            VNode::Slot(ref mut slot) => slot.render_walk(parent, next, rt),
             // There is nothing to walk on.
             VNode::Text(_) => Ok(()),
             VNode::None => Ok(())
@@ -112,46 +122,56 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
         parent: &Self::Node,
         next: Option<&Self::Node>,
         render_ctx: Shared<Self::RenderContext>,
-        rx_sender: MessageSender,
-    ) -> Result<(), JsValue> {
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
         match self {
             VNode::Element(ref mut new_el) => {
-                patch!(Element => new_el, old, parent, next, render_ctx, rx_sender)
+                patch!(Element => new_el, old, parent, next, render_ctx, rt)
             }
             VNode::Text(ref mut new_txt) => {
-                patch!(Text => new_txt, old, parent, next, render_ctx, rx_sender)
+                patch!(Text => new_txt, old, parent, next, render_ctx, rt)
             }
             VNode::List(ref mut new_li) => {
-                patch!(List => new_li, old, parent, next, render_ctx, rx_sender)
+                patch!(List => new_li, old, parent, next, render_ctx, rt)
             }
             VNode::Component(ref mut new_comp) => {
-                patch!(Component => new_comp, old, parent, next, render_ctx, rx_sender)
+                patch!(Component => new_comp, old, parent, next, render_ctx, rt)
             }
+            VNode::Slot(ref mut new_slot) => match old {
+                Some(VNode::Slot(old)) => new_slot.patch(Some(old), parent, next, rt),
+                Some(old) => {
+                    old.remove(parent, rt)?;
+                    new_slot.patch(None, parent, next, rt)
+                }
+                None => new_slot.patch(None, parent, next, rt),
+            },
             VNode::None => {
                 if let Some(old) = old {
-                    old.remove(parent)?;
+                    old.remove(parent, rt)?;
                 }
                 Ok(())
             }
         }
     }
 
-    fn reorder(&self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
+    fn reorder(&self, parent: &Node, next: Option<&Node>, rt: &Runtime) -> Result<(), RenderError> {
         match self {
-            VNode::Text(txt) => txt.reorder(parent, next),
-            VNode::Element(el) => el.reorder(parent, next),
-            VNode::List(li) => li.reorder(parent, next),
-            VNode::Component(comp) => comp.reorder(parent, next),
+            VNode::Text(txt) => txt.reorder(parent, next, rt),
+            VNode::Element(el) => el.reorder(parent, next, rt),
+            VNode::List(li) => li.reorder(parent, next, rt),
+            VNode::Component(comp) => comp.reorder(parent, next, rt),
+            VNode::Slot(slot) => slot.reorder(parent, next, rt),
             VNode::None => Ok(())
         }
     }
 
-    fn remove(&self, parent: &Self::Node) -> Result<(), JsValue> {
+    fn remove(&self, parent: &Self::Node, rt: &Runtime) -> Result<(), RenderError> {
         match self {
-            VNode::Text(txt) => txt.remove(parent),
-            VNode::Element(el) => el.remove(parent),
-            VNode::List(li) => li.remove(parent),
-            VNode::Component(comp) => comp.remove(parent),
+            VNode::Text(txt) => txt.remove(parent, rt),
+            VNode::Element(el) => el.remove(parent, rt),
+            VNode::List(li) => li.remove(parent, rt),
+            VNode::Component(comp) => comp.remove(parent, rt),
+            VNode::Slot(slot) => slot.remove(parent, rt),
             VNode::None => Ok(())
         }
     }
@@ -162,30 +182,88 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
             VNode::Element(el) => el.node(),
             VNode::List(li) => li.node(),
             VNode::Component(comp) => comp.node(),
+            VNode::Slot(slot) => slot.node(),
             VNode::None => None
         }
     }
 }
 
+impl<RCTX: Render> Hydrate for VNode<RCTX> {
+    type RenderContext = RCTX;
+
+    fn hydrate(
+        &mut self,
+        parent: &Node,
+        existing: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rt: &Runtime,
+    ) -> Result<Option<Node>, RenderError> {
+        match self {
+            VNode::Text(ref mut txt) => txt.hydrate(parent, existing, render_ctx, rt),
+            VNode::Element(ref mut el) => el.hydrate(parent, existing, render_ctx, rt),
+            VNode::List(ref mut list) => list.hydrate(parent, existing, render_ctx, rt),
+            VNode::Component(ref mut comp) => comp.hydrate(parent, existing, render_ctx, rt),
+            VNode::Slot(ref mut slot) => slot.hydrate(parent, existing, rt),
+            // Nothing is rendered, so nothing to claim.
+            VNode::None => Ok(existing)
+        }
+    }
+}
+
+impl<RCTX: Render> SSRWalk for VNode<RCTX> {
+    type RenderContext = RCTX;
+
+    fn ssr_walk(&mut self, render_ctx: Shared<Self::RenderContext>, rx_sender: MessageSender) {
+        match self {
+            VNode::Element(ref mut el) => el.ssr_walk(render_ctx, rx_sender),
+            VNode::List(ref mut list) => list.ssr_walk(render_ctx, rx_sender),
+            VNode::Component(ref mut comp) => comp.ssr_walk(render_ctx, rx_sender),
+            VNode::Slot(ref mut slot) => slot.ssr_walk(rx_sender),
+            // There is nothing to walk on.
+            VNode::Text(_) => (),
+            VNode::None => ()
+        }
+    }
+}
+
 /// Keys to identify a VNode in VDOM.
 /// 
 /// Users don't need to explicitly use the `Key` type in html! macro. Any 
 /// supported type is automatically converted to it.
 /// 
-/// Note:
-/// WASM only supported 32-bit and 64-bit of the integers.
-#[derive(Clone, Eq, PartialEq, Hash)]
+/// The keys converted from the same value are always equal, so that a vnode
+/// is identified across renders. A string is copied into the key, unless it is
+/// a `'static` one passed to [Key::from_static](enum.Key.html#method.from_static).
+#[derive(Clone, Debug, Eq)]
 pub enum Key {
     /// An `i32` key
     I32(i32),
     /// An `i64` key
     I64(i64),
+    /// An `i128` key
+    I128(i128),
     /// An `u32` key
     U32(u32),
     /// An `u64` key
     U64(u64),
+    /// An `u128` key
+    U128(u128),
+    /// A `char` key
+    Char(char),
     /// A `String` key
     String(String),
+    /// A `'static` string key, which is equal to the `String` key of the same
+    /// string.
+    Static(&'static str),
+    /// A key composed of other keys, converted from a tuple.
+    Tuple(Vec<Key>),
+    /// The hash of a value, as created by
+    /// [Key::hashed](enum.Key.html#method.hashed).
+    Hashed(u64),
+    /// A key which is a duplicate of another in the same list, along with
+    /// the count of its earlier duplicates. Duplicate keys are only kept in
+    /// release builds.
+    Duplicate(Box<Key>, u32),
 }
 
 impl Key {
@@ -193,43 +271,109 @@ impl Key {
     pub fn new<T: Into<Key>>(val: T) -> Key {
         val.into()
     }
+
+    /// Construct a Key from the hash of any value, like an UUID.
+    ///
+    /// The hash is stable across renders, though different values may
+    /// rarely end up with the same hash.
+    ///
+    /// # Example
+    /// ```
+    /// use ruukh::vdom::Key;
+    ///
+    /// assert_eq!(Key::hashed(&("users", 1)), Key::hashed(&("users", 1)));
+    /// assert_ne!(Key::hashed(&("users", 1)), Key::hashed(&("users", 2)));
+    /// ```
+    pub fn hashed<T: Hash + ?Sized>(val: &T) -> Key {
+        let mut hasher = FnvHasher::default();
+        val.hash(&mut hasher);
+        Key::Hashed(hasher.finish())
+    }
+
+    /// Construct a Key from a `'static` string without copying it.
+    ///
+    /// # Example
+    /// ```
+    /// use ruukh::vdom::Key;
+    ///
+    /// assert_eq!(Key::from_static("header"), Key::new("header".to_string()));
+    /// ```
+    pub fn from_static(string: &'static str) -> Key {
+        Key::Static(string)
+    }
+
+    /// The string of a string key, whether it is static or not.
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Key::String(ref string) => Some(string.as_str()),
+            Key::Static(string) => Some(*string),
+            _ => None,
+        }
+    }
 }
 
-macro_rules! convert {
-    ([$($f:ty),*] to I32) => {
-        $(
-            impl From<$f> for Key {
-                fn from(num: $f) -> Key {
-                    Key::I32(i32::from(num))
-                }
+impl PartialEq for Key {
+    fn eq(&self, other: &Key) -> bool {
+        match (self, other) {
+            (Key::I32(l), Key::I32(r)) => l == r,
+            (Key::I64(l), Key::I64(r)) => l == r,
+            (Key::I128(l), Key::I128(r)) => l == r,
+            (Key::U32(l), Key::U32(r)) => l == r,
+            (Key::U64(l), Key::U64(r)) => l == r,
+            (Key::U128(l), Key::U128(r)) => l == r,
+            (Key::Char(l), Key::Char(r)) => l == r,
+            (Key::Tuple(l), Key::Tuple(r)) => l == r,
+            (Key::Hashed(l), Key::Hashed(r)) => l == r,
+            (Key::Duplicate(l, l_count), Key::Duplicate(r, r_count)) => {
+                l == r && l_count == r_count
             }
-        )*
-    };
-    ([$($f:ty),*] to U32) => {
+            (l, r) => match (l.as_str(), r.as_str()) {
+                (Some(l), Some(r)) => l == r,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Hashed along with the variant, except for the strings which hash the same
+/// whether they are static or not.
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Key::I32(val) => (0u8, val).hash(state),
+            Key::I64(val) => (1u8, val).hash(state),
+            Key::I128(val) => (2u8, val).hash(state),
+            Key::U32(val) => (3u8, val).hash(state),
+            Key::U64(val) => (4u8, val).hash(state),
+            Key::U128(val) => (5u8, val).hash(state),
+            Key::Char(val) => (6u8, val).hash(state),
+            Key::String(_) | Key::Static(_) => (7u8, self.as_str()).hash(state),
+            Key::Tuple(keys) => (8u8, keys).hash(state),
+            Key::Hashed(val) => (9u8, val).hash(state),
+            Key::Duplicate(key, count) => (10u8, key, count).hash(state),
+        }
+    }
+}
+
+macro_rules! convert {
+    ([$($f:ty),*] to $variant:ident($t:ty)) => {
         $(
             impl From<$f> for Key {
                 fn from(num: $f) -> Key {
-                    Key::U32(u32::from(num))
+                    Key::$variant(<$t>::from(num))
                 }
             }
         )*
     };
 }
 
-convert!([i8, i16, i32] to I32);
-convert!([u8, u16, u32] to U32);
-
-impl From<i64> for Key {
-    fn from(num: i64) -> Key {
-        Key::I64(num)
-    }
-}
-
-impl From<u64> for Key {
-    fn from(num: u64) -> Key {
-        Key::U64(num)
-    }
-}
+convert!([i8, i16, i32] to I32(i32));
+convert!([i64] to I64(i64));
+convert!([i128] to I128(i128));
+convert!([u8, u16, u32] to U32(u32));
+convert!([u64] to U64(u64));
+convert!([u128] to U128(u128));
+convert!([char] to Char(char));
 
 impl<'a> From<&'a str> for Key {
     fn from(string: &'a str) -> Key {
@@ -237,9 +381,15 @@ impl<'a> From<&'a str> for Key {
     }
 }
 
+impl<'a> From<&'a String> for Key {
+    fn from(string: &'a String) -> Key {
+        Key::String(string.clone())
+    }
+}
+
 impl<'a> From<Cow<'a, str>> for Key {
     fn from(string: Cow<'a, str>) -> Key {
-        Key::String(string.to_string())
+        Key::String(string.into_owned())
     }
 }
 
@@ -249,9 +399,25 @@ impl From<String> for Key {
     }
 }
 
+macro_rules! convert_tuple {
+    ($(($($t:ident),*)),*) => {
+        $(
+            impl<$($t: Into<Key>),*> From<($($t,)*)> for Key {
+                #[allow(non_snake_case)]
+                fn from(($($t,)*): ($($t,)*)) -> Key {
+                    Key::Tuple(vec![$($t.into()),*])
+                }
+            }
+        )*
+    };
+}
+
+convert_tuple!((A, B), (A, B, C), (A, B, C, D));
+
 #[cfg(test)]
 mod test {
-    use crate::vdom::{vtext::VText, VNode};
+    use crate::vdom::{vtext::VText, Key, VNode};
+    use std::borrow::Cow;
     use web_sys::{window, Element};
 
     pub fn container() -> Element {
@@ -263,4 +429,31 @@ mod test {
         let node = VNode::<()>::from(VText::text("Hello World!"));
         assert_eq!(format!("{}", node), "Hello World!");
     }
+
+    #[test]
+    fn should_compare_keys_from_the_same_value() {
+        let name = "row".to_string();
+        assert_eq!(Key::new("row"), Key::new(&name));
+        assert_eq!(Key::new(name.clone()), Key::new(Cow::Borrowed(name.as_str())));
+        assert_eq!(Key::new(('a', 1u8)), Key::new(('a', 1u32)));
+        assert_ne!(Key::new(("users", 1)), Key::new(("posts", 1)));
+        assert_eq!(Key::new(u128::max_value()), Key::U128(u128::max_value()));
+        assert_eq!(Key::hashed("row"), Key::hashed(&name[..]));
+        assert_eq!(Key::new(name.as_str()), Key::new("row"));
+    }
+
+    #[test]
+    fn should_compare_and_hash_static_keys_as_owned_ones() {
+        use fnv::FnvHashSet;
+
+        assert_eq!(Key::from_static("row"), Key::new("row".to_string()));
+        assert_eq!(Key::new("row"), Key::from_static("row"));
+        assert_ne!(Key::from_static("row"), Key::from_static("col"));
+        assert_ne!(Key::from_static("1"), Key::new(1));
+
+        let mut keys = FnvHashSet::default();
+        keys.insert(Key::new("row".to_string()));
+        assert!(keys.contains(&Key::from_static("row")));
+        assert!(!keys.contains(&Key::new(("row", 1))));
+    }
 }
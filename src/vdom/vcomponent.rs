@@ -2,18 +2,21 @@
 
 use crate::{
     component::{FromEventProps, Render, Status},
-    dom::DOMPatch,
-    vdom::{Shared, VNode},
+    dom::{DOMPatch, Node, Runtime},
+    context::Scope,
+    error::{catch_panic, RenderError},
+    hydrate::Hydrate,
+    ssr::SSRWalk,
+    vdom::{vslot, Shared, VNode},
     MessageSender,
 };
 use std::{
     any::Any,
     cell::RefCell,
     fmt::{self, Display, Formatter},
+    mem,
     rc::Rc,
 };
-use wasm_bindgen::prelude::JsValue;
-use web_sys::Node;
 
 /// The representation of a component in a Virtual DOM.
 pub struct VComponent<RCTX: Render>(Box<dyn ComponentManager<RenderContext = RCTX>>);
@@ -39,6 +42,11 @@ where
     props: Option<COMP::Props>,
     events: Option<<COMP::Events as FromEventProps<RCTX>>::From>,
     cached_render: Option<VNode<COMP>>,
+    /// The context provided to and read by the component.
+    scope: Option<Rc<Scope>>,
+    /// Whether the props were updated, for a component which does not keep
+    /// a status to mark them dirty.
+    props_updated: bool,
 }
 
 impl<COMP: Render, RCTX: Render> ComponentWrapper<COMP, RCTX>
@@ -54,8 +62,210 @@ where
             props: Some(props),
             events: Some(events),
             cached_render: None,
+            scope: None,
+            props_updated: false,
         }
     }
+
+    /// The context scope of the component. It is created within the scope of
+    /// the component currently being rendered, i.e. its parent.
+    fn scope(&mut self, rx_sender: &MessageSender) -> Rc<Scope> {
+        self.scope
+            .get_or_insert_with(|| Scope::new(rx_sender.clone()))
+            .clone()
+    }
+
+    /// Initializes the component with the props and events passed to it and
+    /// invokes its `created` lifecycle.
+    fn create_component(&mut self, render_ctx: Shared<RCTX>, rx_sender: &MessageSender) -> COMP {
+        let props = self.props.take().unwrap();
+        let events = self.events.take().unwrap();
+        let instance = COMP::init(
+            props,
+            FromEventProps::from(events, render_ctx),
+            Status::new(COMP::State::default(), rx_sender.clone()),
+        );
+        instance.created();
+        instance
+    }
+
+    /// Updates the created component with newer props and events and invokes
+    /// its `updated` lifecycle if the props changed.
+    pub(crate) fn update(
+        &mut self,
+        props: COMP::Props,
+        events: <COMP::Events as FromEventProps<RCTX>>::From,
+        render_ctx: Shared<RCTX>,
+    ) {
+        let comp = self
+            .component
+            .as_ref()
+            .expect("The component must be created before updating it.");
+        let scope = self.scope.as_ref().unwrap();
+        let updated = Scope::enter(scope, || {
+            let old_props = comp
+                .borrow_mut()
+                .update(props, FromEventProps::from(events, render_ctx));
+            match old_props {
+                Some(old_props) => {
+                    comp.borrow().updated(old_props);
+                    true
+                }
+                None => false,
+            }
+        });
+        self.props_updated = self.props_updated || updated;
+    }
+
+    /// Creates the component or re-renders it if it changed, and then walks
+    /// its render.
+    fn walk(
+        &mut self,
+        parent: &Node,
+        next: Option<&Node>,
+        render_ctx: Shared<RCTX>,
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        if self.component.is_none() {
+            let instance = self.create_component(render_ctx, &rt.rx_sender);
+            let shared_instance = Rc::new(RefCell::new(instance));
+            let mut initial_render = render(&shared_instance);
+            let patched = initial_render.patch(None, parent, next, shared_instance.clone(), rt);
+            // Keep the render even if it fails, as the nodes patched so far are
+            // stored in it.
+            self.component = Some(shared_instance.clone());
+            self.cached_render = Some(initial_render);
+            patched.map_err(RenderError::within::<COMP>)?;
+            shared_instance.borrow().mounted();
+        } else {
+            let comp = self.component.as_ref().unwrap();
+
+            let state_changed = comp
+                .borrow()
+                .status()
+                .map(|s| s.borrow().is_state_dirty())
+                .unwrap_or(false);
+            if state_changed {
+                comp.borrow_mut().refresh_state();
+                comp.borrow()
+                    .status()
+                    .unwrap()
+                    .borrow_mut()
+                    .set_state_dirty(false);
+            }
+
+            let props_changed = comp
+                .borrow()
+                .status()
+                .map(|s| s.borrow().is_props_dirty())
+                .unwrap_or(false);
+            if props_changed {
+                comp.borrow()
+                    .status()
+                    .unwrap()
+                    .borrow_mut()
+                    .set_props_dirty(false);
+            }
+            let props_changed = mem::replace(&mut self.props_updated, false) || props_changed;
+
+            let context_changed = self
+                .scope
+                .as_ref()
+                .is_some_and(|scope| scope.take_dirty());
+
+            if state_changed || props_changed || context_changed {
+                let mut rerender = render(comp);
+                let mut cached_render = self.cached_render.take();
+                let patched =
+                    rerender.patch(cached_render.as_mut(), parent, next, comp.clone(), rt);
+                self.cached_render = Some(rerender);
+                patched.map_err(RenderError::within::<COMP>)?;
+            }
+        }
+        if let Some(ref mut cached) = self.cached_render {
+            cached
+                .render_walk(
+                    parent,
+                    next,
+                    self.component.as_ref().unwrap().clone(),
+                    rt,
+                ).map_err(RenderError::within::<COMP>)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the failed render of an error boundary with the one rendered
+    /// after it captures the error.
+    fn capture(
+        &mut self,
+        error: RenderError,
+        parent: &Node,
+        next: Option<&Node>,
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        // The boundary itself could not be created, so it is for the next
+        // boundary to capture.
+        let comp = match self.component {
+            Some(ref comp) => comp.clone(),
+            None => return Err(error),
+        };
+        if let Some(failed) = self.cached_render.take() {
+            // The nodes of a partially patched render which could not be
+            // removed are left as is.
+            let _ = failed.remove(parent, rt);
+        }
+
+        comp.borrow().error_captured(&error);
+        let state_changed = comp
+            .borrow()
+            .status()
+            .map(|s| s.borrow().is_state_dirty())
+            .unwrap_or(false);
+        if state_changed {
+            comp.borrow_mut().refresh_state();
+            comp.borrow()
+                .status()
+                .unwrap()
+                .borrow_mut()
+                .set_state_dirty(false);
+        }
+
+        let mut fallback = render(&comp);
+        let patched = fallback.patch(None, parent, next, comp.clone(), rt);
+        self.cached_render = Some(fallback);
+        patched.map_err(RenderError::within::<COMP>)?;
+        self.cached_render
+            .as_mut()
+            .unwrap()
+            .render_walk(parent, next, comp, rt)
+            .map_err(RenderError::within::<COMP>)
+    }
+
+    /// Renders the created component from scratch in the parent, discarding
+    /// the nodes of its cached render.
+    pub(crate) fn rerender(&mut self, parent: &Node, rt: &Runtime) -> Result<(), RenderError> {
+        let comp = self
+            .component
+            .clone()
+            .expect("The component must be created before re-rendering it.");
+        let scope = self.scope(&rt.rx_sender);
+        Scope::walk(&scope, || {
+            let mut rerender = render(&comp);
+            let patched = rerender.patch(None, parent, None, comp.clone(), rt);
+            self.cached_render = Some(rerender);
+            patched.map_err(RenderError::within::<COMP>)?;
+            self.cached_render
+                .as_mut()
+                .unwrap()
+                .render_walk(parent, None, comp, rt)
+                .map_err(RenderError::within::<COMP>)
+        })
+    }
+}
+
+/// Renders the component, binding the children it passes on to it.
+fn render<COMP: Render>(comp: &Shared<COMP>) -> VNode<COMP> {
+    vslot::rendering(comp, || comp.borrow().render())
 }
 
 impl<RCTX: Render> DOMPatch for VComponent<RCTX> {
@@ -67,9 +277,9 @@ impl<RCTX: Render> DOMPatch for VComponent<RCTX> {
         parent: &Self::Node,
         next: Option<&Self::Node>,
         render_ctx: Shared<Self::RenderContext>,
-        rx_sender: MessageSender,
-    ) -> Result<(), JsValue> {
-        self.0.render_walk(parent, next, render_ctx, rx_sender)
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        self.0.render_walk(parent, next, render_ctx, rt)
     }
 
     fn patch(
@@ -78,18 +288,18 @@ impl<RCTX: Render> DOMPatch for VComponent<RCTX> {
         parent: &Self::Node,
         next: Option<&Self::Node>,
         render_ctx: Shared<Self::RenderContext>,
-        _: MessageSender,
-    ) -> Result<(), JsValue> {
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
         self.0
-            .patch(old.map(|old| &mut *old.0), parent, next, render_ctx)
+            .patch(old.map(|old| &mut *old.0), parent, next, render_ctx, rt)
     }
 
-    fn reorder(&self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
-        self.0.reorder(parent, next)
+    fn reorder(&self, parent: &Node, next: Option<&Node>, rt: &Runtime) -> Result<(), RenderError> {
+        self.0.reorder(parent, next, rt)
     }
 
-    fn remove(&self, parent: &Self::Node) -> Result<(), JsValue> {
-        self.0.remove(parent)
+    fn remove(&self, parent: &Self::Node, rt: &Runtime) -> Result<(), RenderError> {
+        self.0.remove(parent, rt)
     }
 
     fn node(&self) -> Option<&Node> {
@@ -97,6 +307,28 @@ impl<RCTX: Render> DOMPatch for VComponent<RCTX> {
     }
 }
 
+impl<RCTX: Render> Hydrate for VComponent<RCTX> {
+    type RenderContext = RCTX;
+
+    fn hydrate(
+        &mut self,
+        parent: &Node,
+        existing: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rt: &Runtime,
+    ) -> Result<Option<Node>, RenderError> {
+        self.0.hydrate(parent, existing, render_ctx, rt)
+    }
+}
+
+impl<RCTX: Render> SSRWalk for VComponent<RCTX> {
+    type RenderContext = RCTX;
+
+    fn ssr_walk(&mut self, render_ctx: Shared<Self::RenderContext>, rx_sender: MessageSender) {
+        self.0.ssr_walk(render_ctx, rx_sender)
+    }
+}
+
 pub(crate) trait ComponentManager: Display + 'static {
     type RenderContext;
 
@@ -105,8 +337,8 @@ pub(crate) trait ComponentManager: Display + 'static {
         parent: &Node,
         next: Option<&Node>,
         render_ctx: Shared<Self::RenderContext>,
-        rx_sender: MessageSender,
-    ) -> Result<(), JsValue>;
+        rt: &Runtime,
+    ) -> Result<(), RenderError>;
 
     fn patch(
         &mut self,
@@ -114,14 +346,25 @@ pub(crate) trait ComponentManager: Display + 'static {
         parent: &Node,
         next: Option<&Node>,
         render_ctx: Shared<Self::RenderContext>,
-    ) -> Result<(), JsValue>;
+        rt: &Runtime,
+    ) -> Result<(), RenderError>;
 
-    fn reorder(&self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue>;
+    fn reorder(&self, parent: &Node, next: Option<&Node>, rt: &Runtime) -> Result<(), RenderError>;
 
-    fn remove(&self, parent: &Node) -> Result<(), JsValue>;
+    fn remove(&self, parent: &Node, rt: &Runtime) -> Result<(), RenderError>;
 
     fn node(&self) -> Option<&Node>;
 
+    fn hydrate(
+        &mut self,
+        parent: &Node,
+        existing: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rt: &Runtime,
+    ) -> Result<Option<Node>, RenderError>;
+
+    fn ssr_walk(&mut self, render_ctx: Shared<Self::RenderContext>, rx_sender: MessageSender);
+
     fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
@@ -136,81 +379,20 @@ where
         parent: &Node,
         next: Option<&Node>,
         render_ctx: Shared<Self::RenderContext>,
-        rx_sender: MessageSender,
-    ) -> Result<(), JsValue> {
-        if self.component.is_none() {
-            let props = self.props.take().unwrap();
-            let events = self.events.take().unwrap();
-            let instance = COMP::init(
-                props,
-                FromEventProps::from(events, render_ctx),
-                Status::new(COMP::State::default(), rx_sender.clone()),
-            );
-            instance.created();
-            let mut initial_render = instance.render();
-            let shared_instance = Rc::new(RefCell::new(instance));
-            initial_render.patch(
-                None,
-                parent,
-                next,
-                shared_instance.clone(),
-                rx_sender.clone(),
-            )?;
-            shared_instance.borrow().mounted();
-            self.component = Some(shared_instance);
-            self.cached_render = Some(initial_render);
-        } else {
-            let comp = self.component.as_ref().unwrap();
-
-            let state_changed = comp
-                .borrow()
-                .status()
-                .map(|s| s.borrow().is_state_dirty())
-                .unwrap_or(false);
-            if state_changed {
-                comp.borrow_mut().refresh_state();
-                comp.borrow()
-                    .status()
-                    .unwrap()
-                    .borrow_mut()
-                    .set_state_dirty(false);
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        let scope = self.scope(&rt.rx_sender);
+        Scope::walk(&scope, || {
+            if !COMP::BOUNDARY {
+                return self.walk(parent, next, render_ctx, rt);
             }
-
-            let props_changed = comp
-                .borrow()
-                .status()
-                .map(|s| s.borrow().is_props_dirty())
-                .unwrap_or(false);
-            if props_changed {
-                comp.borrow()
-                    .status()
-                    .unwrap()
-                    .borrow_mut()
-                    .set_props_dirty(false);
+            let walked = catch_panic(|| self.walk(parent, next, render_ctx, rt))
+                .unwrap_or_else(|message| Err(RenderError::panicked(message).within::<COMP>()));
+            match walked {
+                Ok(()) => Ok(()),
+                Err(error) => self.capture(error, parent, next, rt),
             }
-
-            if state_changed || props_changed {
-                let mut rerender = comp.borrow().render();
-                let mut cached_render = self.cached_render.take();
-                rerender.patch(
-                    cached_render.as_mut(),
-                    parent,
-                    next,
-                    comp.clone(),
-                    rx_sender.clone(),
-                )?;
-                self.cached_render = Some(rerender);
-            }
-        }
-        if let Some(ref mut cached) = self.cached_render {
-            cached.render_walk(
-                parent,
-                next,
-                self.component.as_ref().unwrap().clone(),
-                rx_sender,
-            )?;
-        }
-        Ok(())
+        })
     }
 
     fn patch(
@@ -219,28 +401,23 @@ where
         parent: &Node,
         _: Option<&Node>,
         render_ctx: Shared<Self::RenderContext>,
-    ) -> Result<(), JsValue> {
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
         if let Some(old) = old {
             let is_same = match old
                 .as_any_mut()
                 .downcast_mut::<ComponentWrapper<COMP, RCTX>>()
             {
                 Some(old) => {
-                    let comp = old.component.take().unwrap();
+                    // Reuse the older component along with its cached render
+                    // to do patches on.
+                    self.component = old.component.take();
+                    self.cached_render = old.cached_render.take();
+                    self.scope = old.scope.take();
+
                     let props = self.props.take().unwrap();
                     let events = self.events.take().unwrap();
-
-                    // Reuse the older component by passing in the newer props.
-                    let old_props = comp
-                        .borrow_mut()
-                        .update(props, FromEventProps::from(events, render_ctx));
-                    if let Some(old_props) = old_props {
-                        comp.borrow().updated(old_props);
-                    }
-                    self.component = Some(comp);
-
-                    // Reuse the cached render too to do patches on.
-                    self.cached_render = old.cached_render.take();
+                    self.update(props, events, render_ctx);
 
                     true
                 }
@@ -248,24 +425,28 @@ where
             };
             if !is_same {
                 // The component is not the same, remove it from the DOM tree.
-                old.remove(parent)?;
+                old.remove(parent, rt)?;
             }
         }
         Ok(())
     }
 
-    fn reorder(&self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
+    fn reorder(&self, parent: &Node, next: Option<&Node>, rt: &Runtime) -> Result<(), RenderError> {
         if let Some(ref cached_render) = self.cached_render {
-            cached_render.reorder(parent, next)?;
+            cached_render
+                .reorder(parent, next, rt)
+                .map_err(RenderError::within::<COMP>)?;
         }
         Ok(())
     }
 
-    fn remove(&self, parent: &Node) -> Result<(), JsValue> {
+    fn remove(&self, parent: &Node, rt: &Runtime) -> Result<(), RenderError> {
         if let Some(ref cached_render) = self.cached_render {
-            cached_render.remove(parent)?;
+            cached_render
+                .remove(parent, rt)
+                .map_err(RenderError::within::<COMP>)?;
             let comp = self.component.as_ref().unwrap();
-            comp.borrow().destroyed();
+            Scope::enter(self.scope.as_ref().unwrap(), || comp.borrow().destroyed());
         }
         Ok(())
     }
@@ -274,6 +455,42 @@ where
         self.cached_render.as_ref().and_then(|inner| inner.node())
     }
 
+    fn hydrate(
+        &mut self,
+        parent: &Node,
+        existing: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rt: &Runtime,
+    ) -> Result<Option<Node>, RenderError> {
+        let scope = self.scope(&rt.rx_sender);
+        Scope::enter(&scope, || {
+            let instance = self.create_component(render_ctx, &rt.rx_sender);
+            let shared_instance = Rc::new(RefCell::new(instance));
+            let mut initial_render = render(&shared_instance);
+            let hydrated = initial_render.hydrate(parent, existing, shared_instance.clone(), rt);
+            self.component = Some(shared_instance.clone());
+            self.cached_render = Some(initial_render);
+            let unclaimed = hydrated.map_err(RenderError::within::<COMP>)?;
+            shared_instance.borrow().mounted();
+            Ok(unclaimed)
+        })
+    }
+
+    fn ssr_walk(&mut self, render_ctx: Shared<Self::RenderContext>, rx_sender: MessageSender) {
+        let scope = self.scope(&rx_sender);
+        Scope::enter(&scope, || {
+            if self.component.is_none() {
+                let instance = self.create_component(render_ctx, &rx_sender);
+                let instance = Rc::new(RefCell::new(instance));
+                self.cached_render = Some(render(&instance));
+                self.component = Some(instance);
+            }
+            if let Some(ref mut cached) = self.cached_render {
+                cached.ssr_walk(self.component.as_ref().unwrap().clone(), rx_sender);
+            }
+        })
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
@@ -312,9 +529,11 @@ pub mod test {
     use crate::{
         component::*,
         prelude::*,
+        dom::test::{memory_runtime, web_runtime},
         vdom::{test::container, velement::*, vtext::*, VNode},
         Shared,
     };
+    use std::cell::Cell;
     use wasm_bindgen_test::*;
 
     struct Button {
@@ -382,10 +601,10 @@ pub mod test {
         let div = container();
         vcomp
             .render_walk(
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(
@@ -400,10 +619,10 @@ pub mod test {
         let div = container();
         vcomp
             .render_walk(
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(
@@ -415,17 +634,17 @@ pub mod test {
         patched
             .patch(
                 Some(&mut vcomp),
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).unwrap();
         patched
             .render_walk(
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(
@@ -433,4 +652,409 @@ pub mod test {
             r#"<button disabled="true">Click</button>"#
         );
     }
+
+    #[test]
+    fn should_patch_component_update_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut vcomp = VComponent::new::<Button>(ButtonProps { disabled: false }, ());
+        vcomp
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(
+            dom.inner_html(&parent),
+            r#"<button disabled="false">Click</button>"#
+        );
+
+        dom.reset_mutations();
+        let mut patched = VComponent::new::<Button>(ButtonProps { disabled: true }, ());
+        patched
+            .patch(Some(&mut vcomp), &parent, None, root_render_ctx(), &rt)
+            .unwrap();
+        patched
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        assert_eq!(
+            dom.inner_html(&parent),
+            r#"<button disabled="true">Click</button>"#
+        );
+        assert_eq!(dom.mutations().created, 0);
+        assert_eq!(dom.mutations().attributes_set, 1);
+    }
+
+    struct Listening {
+        destroyed: Rc<Cell<usize>>,
+    }
+
+    impl Lifecycle for Listening {
+        fn destroyed(&self) {
+            self.destroyed.set(self.destroyed.get() + 1);
+        }
+    }
+
+    impl Component for Listening {
+        type Props = Rc<Cell<usize>>;
+        type Events = ();
+        type State = ();
+
+        fn init(destroyed: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Listening { destroyed }
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) {
+            unreachable!()
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+
+        fn set_state(&self, _: impl FnMut(&mut Self::State)) {
+            unreachable!()
+        }
+    }
+
+    impl Render for Listening {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VElement::new(
+                "div",
+                vec![],
+                vec![EventListener::new("click", Box::new(|_: &Self, _| {}))],
+                VNode::from(VComponent::new::<Button>(ButtonProps { disabled: false }, ())),
+            ))
+        }
+    }
+
+    #[test]
+    fn should_remove_nodes_and_listeners_of_component_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let destroyed = Rc::new(Cell::new(0));
+        let mut vcomp = VComponent::new::<Listening>(destroyed.clone(), ());
+        vcomp
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(
+            dom.inner_html(&parent),
+            r#"<div><button disabled="false">Click</button></div>"#
+        );
+
+        dom.reset_mutations();
+        vcomp.remove(&parent, &rt).expect("To remove the component");
+
+        assert_eq!(dom.inner_html(&parent), "");
+        assert_eq!(dom.mutations().listeners_removed, 1);
+        assert_eq!(destroyed.get(), 1);
+    }
+
+    #[test]
+    fn should_update_created_component_with_newer_props() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut wrapper = ComponentWrapper::<Button, ()>::new(ButtonProps { disabled: false }, ());
+        wrapper
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        wrapper.update(ButtonProps { disabled: true }, (), root_render_ctx());
+        wrapper
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        assert_eq!(
+            dom.inner_html(&parent),
+            r#"<button disabled="true">Click</button>"#
+        );
+    }
+
+    struct Failing;
+
+    impl Lifecycle for Failing {}
+
+    impl Component for Failing {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Failing
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) {
+            unreachable!()
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+
+        fn set_state(&self, _: impl FnMut(&mut Self::State)) {
+            unreachable!()
+        }
+    }
+
+    impl Render for Failing {
+        fn render(&self) -> Markup<Self> {
+            panic!("Failed to render")
+        }
+    }
+
+    struct Boundary {
+        captured: Rc<RefCell<Option<String>>>,
+    }
+
+    impl Lifecycle for Boundary {
+        fn error_captured(&self, error: &RenderError) {
+            *self.captured.borrow_mut() = Some(error.to_string());
+        }
+    }
+
+    impl Component for Boundary {
+        type Props = Rc<RefCell<Option<String>>>;
+        type Events = ();
+        type State = ();
+
+        const BOUNDARY: bool = true;
+
+        fn init(captured: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Boundary { captured }
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) {
+            unreachable!()
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+
+        fn set_state(&self, _: impl FnMut(&mut Self::State)) {
+            unreachable!()
+        }
+    }
+
+    impl Render for Boundary {
+        fn render(&self) -> Markup<Self> {
+            if self.captured.borrow().is_some() {
+                VNode::from(VText::text("Something went wrong."))
+            } else {
+                VNode::from(VElement::new(
+                    "div",
+                    vec![],
+                    vec![],
+                    VNode::from(VComponent::new::<Failing>((), ())),
+                ))
+            }
+        }
+    }
+
+    #[cfg(panic = "unwind")]
+    #[test]
+    fn should_render_the_fallback_of_boundary_when_a_child_panics() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let captured = Rc::new(RefCell::new(None));
+        let mut vcomp = VComponent::new::<Boundary>(captured.clone(), ());
+        vcomp
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To capture the error");
+
+        assert_eq!(dom.inner_html(&parent), "Something went wrong.");
+        assert_eq!(
+            captured.borrow().as_ref().map(|error| error.as_str()),
+            Some("Could not render a component in `Boundary`: it panicked with 'Failed to render'")
+        );
+    }
+
+    #[cfg(panic = "unwind")]
+    #[test]
+    fn should_pass_on_the_panic_without_a_boundary() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut vcomp = VComponent::new::<Failing>((), ());
+        let caught = catch_panic(|| vcomp.render_walk(&parent, None, root_render_ctx(), &rt));
+
+        assert_eq!(caught.err(), Some("Failed to render".to_string()));
+    }
+
+    struct Themed {
+        theme: &'static str,
+        __status: Shared<Status<()>>,
+    }
+
+    impl Lifecycle for Themed {}
+
+    impl Component for Themed {
+        type Props = &'static str;
+        type Events = ();
+        type State = ();
+
+        fn init(theme: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+            Themed {
+                theme,
+                __status: Rc::new(RefCell::new(status)),
+            }
+        }
+
+        fn update(&mut self, theme: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            self.__status.borrow_mut().set_props_dirty(true);
+            Some(std::mem::replace(&mut self.theme, theme))
+        }
+
+        fn refresh_state(&mut self) {
+            unreachable!()
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            Some(&self.__status)
+        }
+
+        fn set_state(&self, _: impl FnMut(&mut Self::State)) {
+            unreachable!()
+        }
+    }
+
+    impl Render for Themed {
+        fn render(&self) -> Markup<Self> {
+            self.provide(self.theme);
+            VNode::from(VElement::new(
+                "div",
+                vec![],
+                vec![],
+                VNode::from(VComponent::new::<Toolbar>((), ())),
+            ))
+        }
+    }
+
+    struct Toolbar;
+
+    impl Lifecycle for Toolbar {}
+
+    impl Component for Toolbar {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Toolbar
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) {
+            unreachable!()
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+
+        fn set_state(&self, _: impl FnMut(&mut Self::State)) {
+            unreachable!()
+        }
+    }
+
+    impl Render for Toolbar {
+        fn render(&self) -> Markup<Self> {
+            let theme = self.context::<&'static str>().map_or("none", |theme| *theme);
+            VNode::from(VText::text(theme))
+        }
+    }
+
+    #[test]
+    fn should_rerender_consumers_when_the_context_changes() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut wrapper = ComponentWrapper::<Themed, ()>::new("light", ());
+        wrapper
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.inner_html(&parent), "<div>light</div>");
+
+        wrapper.update("dark", (), root_render_ctx());
+        wrapper
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.inner_html(&parent), "<div>dark</div>");
+    }
+
+    struct Badge {
+        text: &'static str,
+    }
+
+    impl Lifecycle for Badge {}
+
+    impl Component for Badge {
+        type Props = &'static str;
+        type Events = ();
+        type State = ();
+
+        fn init(text: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Badge { text }
+        }
+
+        fn update(&mut self, text: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            if self.text == text {
+                None
+            } else {
+                Some(std::mem::replace(&mut self.text, text))
+            }
+        }
+
+        fn refresh_state(&mut self) {
+            unreachable!()
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+
+        fn set_state(&self, _: impl FnMut(&mut Self::State)) {
+            unreachable!()
+        }
+    }
+
+    impl Render for Badge {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VText::text(self.text))
+        }
+    }
+
+    #[test]
+    fn should_rerender_component_without_status_when_props_change() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut wrapper = ComponentWrapper::<Badge, ()>::new("new", ());
+        wrapper
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        wrapper.update("new", (), root_render_ctx());
+        dom.reset_mutations();
+        wrapper
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.mutations().texts_set, 0);
+
+        wrapper.update("old", (), root_render_ctx());
+        wrapper
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.inner_html(&parent), "old");
+    }
 }
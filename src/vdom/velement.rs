@@ -1,6 +1,18 @@
 //! Element representation in a VDOM.
 
-use crate::{component::Render, dom::DOMPatch, vdom::VNode, MessageSender, Shared};
+use crate::{
+    component::Render,
+    dom::{
+        self, delegation::HandlerSlot, DOMPatch, Listener, ListenerOptions, Node, PropertyValue,
+        Runtime, MATHML_NAMESPACE, SVG_NAMESPACE, XLINK_NAMESPACE, XMLNS_NAMESPACE,
+        XML_NAMESPACE,
+    },
+    error::{Operation, RenderError},
+    hydrate::{self, Hydrate},
+    ssr::{escape_attribute, SSRWalk},
+    vdom::VNode,
+    MessageSender, Shared,
+};
 use indexmap::IndexMap;
 use std::{
     borrow::Cow,
@@ -8,8 +20,8 @@ use std::{
     fmt::{self, Display, Formatter},
     rc::Rc,
 };
-use wasm_bindgen::{prelude::*, JsCast};
-use web_sys::{window, Element, Event, EventTarget, Node};
+use wasm_bindgen::JsCast;
+use web_sys::Event;
 
 /// The representation of an element in virtual DOM.
 pub struct VElement<RCTX: Render> {
@@ -17,12 +29,14 @@ pub struct VElement<RCTX: Render> {
     tag: &'static str,
     /// The attributes of the given element
     attributes: Attributes,
+    /// The DOM properties of the given element
+    properties: Properties,
     /// Event listeners to the DOM events
     event_listeners: EventListeners<RCTX>,
     /// The child node of the given element
     child: Box<VNode<RCTX>>,
     /// Element reference to the DOM
-    node: Option<Element>,
+    node: Option<Node>,
 }
 
 /// A list of attributes.
@@ -36,6 +50,22 @@ pub struct Attribute {
     value: AttributeValue,
 }
 
+/// A list of DOM properties.
+struct Properties(IndexMap<&'static str, PropertyValue>);
+
+/// The key, value pair of a DOM property of an element.
+///
+/// Unlike an attribute, which is only the initial value of an input, the
+/// property is its live value. So it is compared against the live value in the
+/// DOM and set again once the user has changed it, which keeps the inputs in
+/// sync with the state.
+pub struct Property {
+    /// The name of the property. Eg: value, checked, selectedIndex, ...
+    key: &'static str,
+    /// The value of the property
+    value: PropertyValue,
+}
+
 /// Either a string or a bool
 pub enum AttributeValue {
     /// A string attribute value
@@ -44,16 +74,43 @@ pub enum AttributeValue {
     Bool(bool),
 }
 
-struct EventListeners<RCTX: Render>(Vec<Box<dyn EventManager<RenderContext = RCTX>>>);
+struct EventListeners<RCTX: Render>(Vec<EventListener<RCTX>>);
+
+/// How an event listener is attached to the DOM.
+enum Listening {
+    /// With a DOM listener on the element.
+    Direct(Listener),
+    /// With the delegator of the App.
+    Delegated,
+}
 
 /// Event listener to be invoked on a DOM event.
 pub struct EventListener<RCTX: Render> {
     type_: &'static str,
     listener: Option<Box<dyn Fn(&RCTX, Event)>>,
-    dom_listener: Option<Closure<dyn Fn(Event)>>,
+    options: ListenerOptions,
+    prevent_default: bool,
+    stop_propagation: bool,
+    /// Only the keyboard events for this key invoke the listener.
+    key: Option<&'static str>,
+    /// The handler invoked on the events. It is swapped on every patch, so
+    /// that the element keeps listening as is while the event stays the same.
+    handler: Option<HandlerSlot>,
+    listening: Option<Listening>,
 }
 
 impl<RCTX: Render> VElement<RCTX> {
+    /// Sets the DOM properties of the element.
+    pub fn with_properties(mut self, properties: Vec<Property>) -> VElement<RCTX> {
+        self.properties = Properties(
+            properties
+                .into_iter()
+                .map(|property| (property.key, property.value))
+                .collect(),
+        );
+        self
+    }
+
     /// Create a VElement.
     pub fn new(
         tag: &'static str,
@@ -64,16 +121,8 @@ impl<RCTX: Render> VElement<RCTX> {
         VElement {
             tag,
             attributes: Attributes::from(attributes),
-            event_listeners: EventListeners(
-                event_listeners
-                    .into_iter()
-                    .map(|listener| {
-                        let listener: Box<
-                            dyn EventManager<RenderContext = RCTX>,
-                        > = Box::new(listener);
-                        listener
-                    }).collect(),
-            ),
+            properties: Properties(IndexMap::new()),
+            event_listeners: EventListeners(event_listeners),
             child: Box::new(child),
             node: None,
         }
@@ -88,16 +137,8 @@ impl<RCTX: Render> VElement<RCTX> {
         VElement {
             tag,
             attributes: Attributes::from(attributes),
-            event_listeners: EventListeners(
-                event_listeners
-                    .into_iter()
-                    .map(|listener| {
-                        let listener: Box<
-                            dyn EventManager<RenderContext = RCTX>,
-                        > = Box::new(listener);
-                        listener
-                    }).collect(),
-            ),
+            properties: Properties(IndexMap::new()),
+            event_listeners: EventListeners(event_listeners),
             child: Box::new(VNode::None),
             node: None,
         }
@@ -114,15 +155,93 @@ impl Attribute {
     }
 }
 
+impl Property {
+    /// Create a Property.
+    pub fn new(key: &'static str, value: impl Into<PropertyValue>) -> Property {
+        Property {
+            key,
+            value: value.into(),
+        }
+    }
+}
+
 impl<RCTX: Render> EventListener<RCTX> {
     /// Create a EventListener.
     pub fn new(type_: &'static str, listener: Box<dyn Fn(&RCTX, Event)>) -> EventListener<RCTX> {
         EventListener {
             type_,
             listener: Some(listener),
-            dom_listener: None,
+            options: ListenerOptions::default(),
+            prevent_default: false,
+            stop_propagation: false,
+            key: None,
+            handler: None,
+            listening: None,
         }
     }
+
+    /// Create a EventListener which is passed the event as the type `E`.
+    ///
+    /// An event which is not an `E`, like a plain `Event` dispatched by a
+    /// script, is not passed to the listener. A warning is logged instead.
+    pub fn typed<E: JsCast + 'static>(
+        type_: &'static str,
+        listener: Box<dyn Fn(&RCTX, E)>,
+    ) -> EventListener<RCTX> {
+        EventListener::new(
+            type_,
+            Box::new(move |render_ctx, event| match event.dyn_into() {
+                Ok(event) => listener(render_ctx, event),
+                Err(_) => {
+                    if let Some((dom, _)) = dom::current_listener() {
+                        dom.warn(&format!(
+                            "The `{}` event is not of the type its listener expects, so it \
+                             is not passed to the listener.",
+                            type_
+                        ));
+                    }
+                }
+            }),
+        )
+    }
+
+    /// Prevents the default action of the event before invoking the listener.
+    pub fn prevent(mut self) -> Self {
+        self.prevent_default = true;
+        self
+    }
+
+    /// Stops the propagation of the event before invoking the listener.
+    pub fn stop(mut self) -> Self {
+        self.stop_propagation = true;
+        self
+    }
+
+    /// Listens to the event while it captures down to the target.
+    pub fn capture(mut self) -> Self {
+        self.options.capture = true;
+        self
+    }
+
+    /// Promises the browser that the listener never prevents the default
+    /// action.
+    pub fn passive(mut self) -> Self {
+        self.options.passive = true;
+        self
+    }
+
+    /// Stops listening after the first event.
+    pub fn once(mut self) -> Self {
+        self.options.once = true;
+        self
+    }
+
+    /// Invokes the listener only for the keyboard events of the given key, as
+    /// named by `KeyboardEvent.key`. Eg: "Enter", "Escape", "ArrowUp", ...
+    pub fn key(mut self, key: &'static str) -> Self {
+        self.key = Some(key);
+        self
+    }
 }
 
 impl<RCTX: Render> From<VElement<RCTX>> for VNode<RCTX> {
@@ -131,7 +250,7 @@ impl<RCTX: Render> From<VElement<RCTX>> for VNode<RCTX> {
     }
 }
 
-const VOID_TAGS: [&str; 14] = [
+pub(crate) const VOID_TAGS: [&str; 14] = [
     "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
     "track", "wbr",
 ];
@@ -165,7 +284,7 @@ impl Display for Attributes {
         for (k, v) in self.0.iter() {
             match v {
                 AttributeValue::String(ref v) => {
-                    write!(f, " {}=\"{}\"", k, v);
+                    write!(f, " {}=\"{}\"", k, escape_attribute(v))?;
                 }
                 AttributeValue::Bool(truthy) => if *truthy {
                     write!(f, " {}=\"\"", k)?;
@@ -182,28 +301,49 @@ impl<RCTX: Render> VElement<RCTX> {
         parent: &Node,
         next: Option<&Node>,
         render_ctx: Shared<RCTX>,
-        rx_sender: MessageSender,
-    ) -> Result<(), JsValue> {
-        let el = window()
-            .unwrap()
-            .document()
-            .unwrap()
-            .create_element(&self.tag)?;
-        self.attributes.patch(
-            None,
-            &el,
-            None,
-            Rc::new(RefCell::new(())),
-            rx_sender.clone(),
-        )?;
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        let el = match self.namespace(parent, rt) {
+            Some(namespace) => rt.dom.create_element_ns(namespace, self.tag),
+            None => rt.dom.create_element(self.tag),
+        }.map_err(RenderError::js(Operation::Create))?;
+        self.attributes
+            .patch(None, &el, None, Rc::new(RefCell::new(())), rt)?;
         self.event_listeners
-            .patch(None, &el, None, render_ctx.clone(), rx_sender.clone())?;
-        self.child
-            .patch(None, el.as_ref(), None, render_ctx, rx_sender)?;
-        parent.insert_before(el.as_ref(), next)?;
+            .patch(None, &el, None, render_ctx.clone(), rt)?;
+        self.child.patch(None, &el, None, render_ctx, rt)?;
+        // The properties are set after the children, as the value of a select
+        // requires its options.
+        self.properties
+            .patch(None, &el, None, Rc::new(RefCell::new(())), rt)?;
+        rt.dom
+            .insert_before(parent, &el, next)
+            .map_err(RenderError::js(Operation::Insert))?;
         self.node = Some(el);
         Ok(())
     }
+
+    /// The namespace the element is created in, if it is not an HTML one.
+    /// `svg` and `math` start their own namespace which the elements within
+    /// them inherit, except the ones within a `foreignObject` which are HTML
+    /// again.
+    fn namespace(&self, parent: &Node, rt: &Runtime) -> Option<&'static str> {
+        match self.tag {
+            "svg" => return Some(SVG_NAMESPACE),
+            "math" => return Some(MATHML_NAMESPACE),
+            _ => {}
+        }
+        match rt.dom.namespace_uri(parent) {
+            Some(ref namespace)
+                if namespace == SVG_NAMESPACE
+                    && !hydrate::is_element_with_tag(&*rt.dom, parent, "foreignObject") =>
+            {
+                Some(SVG_NAMESPACE)
+            }
+            Some(ref namespace) if namespace == MATHML_NAMESPACE => Some(MATHML_NAMESPACE),
+            _ => None,
+        }
+    }
 }
 
 impl<RCTX: Render> DOMPatch for VElement<RCTX> {
@@ -215,14 +355,13 @@ impl<RCTX: Render> DOMPatch for VElement<RCTX> {
         _: &Node,
         _: Option<&Node>,
         render_ctx: Shared<Self::RenderContext>,
-        rx_sender: MessageSender,
-    ) -> Result<(), JsValue> {
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
         let node = self
             .node
             .as_ref()
-            .expect("The element itself must be patched before rendering the child");
-        self.child
-            .render_walk(node.as_ref(), None, render_ctx, rx_sender)
+            .ok_or_else(|| RenderError::detached(Operation::Patch))?;
+        self.child.render_walk(node, None, render_ctx, rt)
     }
 
     fn patch(
@@ -231,91 +370,152 @@ impl<RCTX: Render> DOMPatch for VElement<RCTX> {
         parent: &Node,
         next: Option<&Node>,
         render_ctx: Shared<Self::RenderContext>,
-        rx_sender: MessageSender,
-    ) -> Result<(), JsValue> {
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
         if let Some(old) = old {
             if self.tag == old.tag {
                 let old_el = old
                     .node
                     .as_ref()
-                    .expect("The old node is expected to be attached to the DOM");
+                    .ok_or_else(|| RenderError::detached(Operation::Patch))?;
                 self.attributes.patch(
                     Some(&mut old.attributes),
-                    &old_el,
+                    old_el,
                     None,
                     Rc::new(RefCell::new(())),
-                    rx_sender.clone(),
+                    rt,
                 )?;
                 self.event_listeners.patch(
                     Some(&mut old.event_listeners),
-                    &old_el,
+                    old_el,
                     None,
                     render_ctx.clone(),
-                    rx_sender.clone(),
+                    rt,
                 )?;
                 self.child.patch(
                     Some(&mut *old.child),
-                    old_el.as_ref(),
+                    old_el,
                     None,
                     render_ctx.clone(),
-                    rx_sender,
+                    rt,
+                )?;
+                self.properties.patch(
+                    Some(&mut old.properties),
+                    old_el,
+                    None,
+                    Rc::new(RefCell::new(())),
+                    rt,
                 )?;
 
                 self.node = Some(old_el.clone());
                 Ok(())
             } else {
-                old.remove(parent)?;
-                self.patch_new(parent, next, render_ctx, rx_sender)
+                old.remove(parent, rt)?;
+                self.patch_new(parent, next, render_ctx, rt)
             }
         } else {
-            self.patch_new(parent, next, render_ctx, rx_sender)
+            self.patch_new(parent, next, render_ctx, rt)
         }
     }
 
-    fn reorder(&self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
-        let el = self.node.as_ref().unwrap();
-        parent.insert_before(el.as_ref(), next)?;
-        Ok(())
+    fn reorder(&self, parent: &Node, next: Option<&Node>, rt: &Runtime) -> Result<(), RenderError> {
+        let el = self
+            .node
+            .as_ref()
+            .ok_or_else(|| RenderError::detached(Operation::Insert))?;
+        rt.dom
+            .insert_before(parent, el, next)
+            .map_err(RenderError::js(Operation::Insert))
     }
 
-    fn remove(&self, parent: &Node) -> Result<(), JsValue> {
+    fn remove(&self, parent: &Node, rt: &Runtime) -> Result<(), RenderError> {
         let el = self
             .node
             .as_ref()
-            .expect("The old node is expected to be attached to the DOM");
-        self.child.remove(el.as_ref())?;
-        self.attributes.remove(&el)?;
-        parent.remove_child(el.as_ref())?;
-        Ok(())
+            .ok_or_else(|| RenderError::detached(Operation::Remove))?;
+        self.child.remove(el, rt)?;
+        self.attributes.remove(el, rt)?;
+        self.event_listeners.remove(el, rt)?;
+        rt.dom
+            .remove_child(parent, el)
+            .map_err(RenderError::js(Operation::Remove))
     }
 
     fn node(&self) -> Option<&Node> {
-        self.node.as_ref().map(|el| el.as_ref())
+        self.node.as_ref()
+    }
+}
+
+impl<RCTX: Render> Hydrate for VElement<RCTX> {
+    type RenderContext = RCTX;
+
+    fn hydrate(
+        &mut self,
+        parent: &Node,
+        existing: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rt: &Runtime,
+    ) -> Result<Option<Node>, RenderError> {
+        let existing = hydrate::skip_blank_text(&*rt.dom, parent, existing)?;
+        match existing {
+            Some(el) if hydrate::is_element_with_tag(&*rt.dom, &el, self.tag) => {
+                self.attributes
+                    .patch(None, &el, None, Rc::new(RefCell::new(())), rt)?;
+                self.event_listeners
+                    .patch(None, &el, None, render_ctx.clone(), rt)?;
+                let unclaimed =
+                    self.child
+                        .hydrate(&el, rt.dom.first_child(&el), render_ctx, rt)?;
+                hydrate::remove_unclaimed(&*rt.dom, &el, unclaimed)?;
+                self.properties
+                    .patch(None, &el, None, Rc::new(RefCell::new(())), rt)?;
+                let next = rt.dom.next_sibling(&el);
+                self.node = Some(el);
+                Ok(next)
+            }
+            existing => {
+                hydrate::report_mismatch(
+                    &*rt.dom,
+                    &format!("<{}>", self.tag),
+                    existing.as_ref(),
+                );
+                self.patch_new(parent, existing.as_ref(), render_ctx, rt)?;
+                hydrate::discard(&*rt.dom, parent, existing)
+            }
+        }
+    }
+}
+
+impl<RCTX: Render> SSRWalk for VElement<RCTX> {
+    type RenderContext = RCTX;
+
+    fn ssr_walk(&mut self, render_ctx: Shared<Self::RenderContext>, rx_sender: MessageSender) {
+        self.child.ssr_walk(render_ctx, rx_sender);
     }
 }
 
 impl DOMPatch for Attributes {
     type RenderContext = ();
-    type Node = Element;
+    type Node = Node;
 
     fn render_walk(
         &mut self,
-        _: &Element,
-        _: Option<&Element>,
+        _: &Node,
+        _: Option<&Node>,
         _: Shared<Self::RenderContext>,
-        _: MessageSender,
-    ) -> Result<(), JsValue> {
+        _: &Runtime,
+    ) -> Result<(), RenderError> {
         unreachable!("Attributes do not have nested Components");
     }
 
     fn patch(
         &mut self,
         mut old: Option<&mut Self>,
-        parent: &Element,
-        next: Option<&Element>,
+        parent: &Node,
+        next: Option<&Node>,
         _: Shared<Self::RenderContext>,
-        _: MessageSender,
-    ) -> Result<(), JsValue> {
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
         debug_assert!(next.is_none());
         for (k, v) in self.0.iter() {
             // Remove the key from old as it exists in the newer.
@@ -326,31 +526,31 @@ impl DOMPatch for Attributes {
             };
             match v {
                 AttributeValue::String(val) => {
-                    parent.set_attribute(&k, &val)?;
+                    set_attribute(parent, k, val, rt)?;
                 }
                 AttributeValue::Bool(truthy) => {
                     if *truthy {
-                        parent.set_attribute(&k, "")?;
+                        set_attribute(parent, k, "", rt)?;
                     } else if existed {
-                        parent.remove_attribute(&k)?;
+                        remove_attribute(parent, k, rt)?;
                     }
                 }
             }
         }
         // Remove the remaining keys.
         if let Some(old) = old {
-            old.remove(parent)?;
+            old.remove(parent, rt)?;
         }
         Ok(())
     }
 
-    fn reorder(&self, _: &Self::Node, _: Option<&Self::Node>) -> Result<(), JsValue> {
+    fn reorder(&self, _: &Node, _: Option<&Node>, _: &Runtime) -> Result<(), RenderError> {
         unreachable!("Cannot reorder Attributes");
     }
 
-    fn remove(&self, parent: &Element) -> Result<(), JsValue> {
+    fn remove(&self, parent: &Node, rt: &Runtime) -> Result<(), RenderError> {
         for (k, _) in self.0.iter() {
-            parent.remove_attribute(&k)?;
+            remove_attribute(parent, k, rt)?;
         }
         Ok(())
     }
@@ -360,44 +560,169 @@ impl DOMPatch for Attributes {
     }
 }
 
+/// The namespace of a prefixed attribute, like `xlink:href`, along with its
+/// name without the prefix.
+fn attribute_namespace(name: &str) -> Option<(&'static str, &str)> {
+    let colon = name.find(':')?;
+    let namespace = match &name[..colon] {
+        "xlink" => XLINK_NAMESPACE,
+        "xml" => XML_NAMESPACE,
+        "xmlns" => XMLNS_NAMESPACE,
+        _ => return None,
+    };
+    Some((namespace, &name[colon + 1..]))
+}
+
+fn set_attribute(el: &Node, name: &str, value: &str, rt: &Runtime) -> Result<(), RenderError> {
+    match attribute_namespace(name) {
+        Some((namespace, _)) => rt.dom.set_attribute_ns(el, namespace, name, value),
+        None => rt.dom.set_attribute(el, name, value),
+    }.map_err(RenderError::js(Operation::SetAttribute))
+}
+
+fn remove_attribute(el: &Node, name: &str, rt: &Runtime) -> Result<(), RenderError> {
+    match attribute_namespace(name) {
+        Some((namespace, local_name)) => rt.dom.remove_attribute_ns(el, namespace, local_name),
+        None => rt.dom.remove_attribute(el, name),
+    }.map_err(RenderError::js(Operation::RemoveAttribute))
+}
+
+impl DOMPatch for Properties {
+    type RenderContext = ();
+    type Node = Node;
+
+    fn render_walk(
+        &mut self,
+        _: &Node,
+        _: Option<&Node>,
+        _: Shared<Self::RenderContext>,
+        _: &Runtime,
+    ) -> Result<(), RenderError> {
+        unreachable!("Properties do not have nested Components");
+    }
+
+    fn patch(
+        &mut self,
+        mut old: Option<&mut Self>,
+        parent: &Node,
+        next: Option<&Node>,
+        _: Shared<Self::RenderContext>,
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        debug_assert!(next.is_none());
+        for (k, v) in self.0.iter() {
+            if let Some(ref mut old) = old {
+                old.0.swap_remove(k);
+            }
+            // Compared against the live value rather than the older VDOM, as
+            // the user may have changed it since.
+            if !is_live(rt.dom.get_property(parent, k), v) {
+                rt.dom
+                    .set_property(parent, k, v)
+                    .map_err(RenderError::js(Operation::SetProperty))?;
+            }
+        }
+        // Clear the remaining keys.
+        if let Some(old) = old {
+            old.remove(parent, rt)?;
+        }
+        Ok(())
+    }
+
+    fn reorder(&self, _: &Node, _: Option<&Node>, _: &Runtime) -> Result<(), RenderError> {
+        unreachable!("Cannot reorder Properties");
+    }
+
+    /// A property cannot be removed, so it is cleared instead.
+    fn remove(&self, parent: &Node, rt: &Runtime) -> Result<(), RenderError> {
+        for (k, v) in self.0.iter() {
+            let cleared = match v {
+                PropertyValue::String(_) => PropertyValue::String(String::new()),
+                PropertyValue::Bool(_) => PropertyValue::Bool(false),
+                PropertyValue::Number(_) => PropertyValue::Number(0.0),
+            };
+            rt.dom
+                .set_property(parent, k, &cleared)
+                .map_err(RenderError::js(Operation::SetProperty))?;
+        }
+        Ok(())
+    }
+
+    fn node(&self) -> Option<&Node> {
+        unreachable!("Properties have no nodes");
+    }
+}
+
+/// Whether the live value of a property is the value.
+fn is_live(live: Option<PropertyValue>, value: &PropertyValue) -> bool {
+    match (live, value) {
+        // The value of an input is always a string, so it is compared with a
+        // number as the number it parses to. Thus, `1.` is kept as typed.
+        (Some(PropertyValue::String(ref live)), PropertyValue::Number(value)) => {
+            live.trim().parse::<f64>().ok() == Some(*value)
+        }
+        (live, value) => live.as_ref() == Some(value),
+    }
+}
+
 impl<RCTX: Render> DOMPatch for EventListeners<RCTX> {
     type RenderContext = RCTX;
-    type Node = Element;
+    type Node = Node;
 
     fn render_walk(
         &mut self,
-        _: &Element,
-        _: Option<&Element>,
+        _: &Node,
+        _: Option<&Node>,
         _: Shared<Self::RenderContext>,
-        _: MessageSender,
-    ) -> Result<(), JsValue> {
+        _: &Runtime,
+    ) -> Result<(), RenderError> {
         unreachable!("EventListeners does not have nested Components");
     }
 
     fn patch(
         &mut self,
         old: Option<&mut Self>,
-        parent: &Element,
-        _: Option<&Element>,
+        parent: &Node,
+        _: Option<&Node>,
         render_ctx: Shared<Self::RenderContext>,
-        _: MessageSender,
-    ) -> Result<(), JsValue> {
-        if let Some(old) = old {
-            old.remove(parent)?;
-        }
-        for listener in self.0.iter_mut() {
-            listener.start_listening(parent.as_ref(), render_ctx.clone())?;
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        match old {
+            Some(old) => {
+                for listener in self.0.iter_mut() {
+                    // Take over the DOM listener of the same event, so that only
+                    // the handler behind it is swapped.
+                    let reusable = old.0.iter_mut().find(|old_listener| {
+                        old_listener.listening.is_some()
+                            && old_listener.type_ == listener.type_
+                            && old_listener.options == listener.options
+                    });
+                    match reusable {
+                        Some(old_listener) => {
+                            listener.swap_handler(old_listener, parent, render_ctx.clone(), rt)
+                        }
+                        None => listener.start_listening(parent, render_ctx.clone(), rt)?,
+                    }
+                }
+                // The ones which were not taken over are not listened to anymore.
+                old.remove(parent, rt)
+            }
+            None => {
+                for listener in self.0.iter_mut() {
+                    listener.start_listening(parent, render_ctx.clone(), rt)?;
+                }
+                Ok(())
+            }
         }
-        Ok(())
     }
 
-    fn reorder(&self, _: &Self::Node, _: Option<&Self::Node>) -> Result<(), JsValue> {
+    fn reorder(&self, _: &Node, _: Option<&Node>, _: &Runtime) -> Result<(), RenderError> {
         unreachable!("Cannot reorder EventListeners");
     }
 
-    fn remove(&self, parent: &Element) -> Result<(), JsValue> {
+    fn remove(&self, parent: &Node, rt: &Runtime) -> Result<(), RenderError> {
         for listener in self.0.iter() {
-            listener.stop_listening(parent.as_ref())?;
+            listener.stop_listening(parent, rt)?;
         }
         Ok(())
     }
@@ -407,44 +732,105 @@ impl<RCTX: Render> DOMPatch for EventListeners<RCTX> {
     }
 }
 
-trait EventManager {
-    type RenderContext;
+impl<RCTX: Render> EventListener<RCTX> {
+    /// Wraps the listener on the element into a handler which applies the
+    /// modifiers and invokes it with the render context.
+    fn handler(
+        &mut self,
+        el: &Node,
+        render_ctx: Shared<RCTX>,
+        rt: &Runtime,
+    ) -> Rc<dyn Fn(Event)> {
+        let listener = self.listener.take().unwrap();
+        let (prevent_default, stop_propagation, key) =
+            (self.prevent_default, self.stop_propagation, self.key);
+        // The DOM keeps the handler, so it is not kept alive by the handler.
+        let (weak_dom, el) = (Rc::downgrade(&rt.dom), el.clone());
+        Rc::new(move |event: Event| {
+            let backend = match weak_dom.upgrade() {
+                Some(backend) => backend,
+                None => return,
+            };
+            if let Some(key) = key {
+                if backend.event_key(&event).as_deref() != Some(key) {
+                    return;
+                }
+            }
+            if prevent_default {
+                backend.prevent_default(&event);
+            }
+            if stop_propagation {
+                backend.stop_propagation(&event);
+            }
+            dom::listen_on(&weak_dom, &el, || listener(&*render_ctx.borrow(), event))
+        })
+    }
 
     fn start_listening(
         &mut self,
-        parent: &EventTarget,
-        render_ctx: Shared<Self::RenderContext>,
-    ) -> Result<(), JsValue>;
-
-    fn stop_listening(&self, parent: &EventTarget) -> Result<(), JsValue>;
-}
+        parent: &Node,
+        render_ctx: Shared<RCTX>,
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        let handler: HandlerSlot = Rc::new(RefCell::new(self.handler(parent, render_ctx, rt)));
+        if let Some(ref delegator) = rt.delegator {
+            if delegator.delegates(self.type_, self.options) {
+                delegator
+                    .register(parent, self.type_, handler.clone())
+                    .map_err(RenderError::js(Operation::AddListener))?;
+                self.handler = Some(handler);
+                self.listening = Some(Listening::Delegated);
+                return Ok(());
+            }
+        }
 
-impl<RCTX: Render> EventManager for EventListener<RCTX> {
-    type RenderContext = RCTX;
+        let slot = handler.clone();
+        let dom_listener = rt
+            .dom
+            .add_event_listener(
+                parent,
+                self.type_,
+                Box::new(move |event| {
+                    // Cloned out of the slot, so that the handler may be swapped
+                    // while it is being invoked.
+                    let handler = slot.borrow().clone();
+                    handler(event)
+                }),
+                self.options,
+            ).map_err(RenderError::js(Operation::AddListener))?;
+        self.handler = Some(handler);
+        self.listening = Some(Listening::Direct(dom_listener));
+        Ok(())
+    }
 
-    fn start_listening(
+    /// Takes over the listening of the old one, swapping in the new handler.
+    fn swap_handler(
         &mut self,
-        parent: &EventTarget,
-        render_ctx: Shared<Self::RenderContext>,
-    ) -> Result<(), JsValue> {
-        let listener = self.listener.take().unwrap();
-        let js_closure: Closure<dyn Fn(Event)> = Closure::wrap(Box::new(move |event| {
-            listener(&*render_ctx.borrow(), event)
-        }));
-        parent
-            .add_event_listener_with_callback(&self.type_, js_closure.as_ref().unchecked_ref())?;
-        self.dom_listener = Some(js_closure);
-        Ok(())
+        old: &mut EventListener<RCTX>,
+        parent: &Node,
+        render_ctx: Shared<RCTX>,
+        rt: &Runtime,
+    ) {
+        let handler = old.handler.take().unwrap();
+        *handler.borrow_mut() = self.handler(parent, render_ctx, rt);
+        self.handler = Some(handler);
+        self.listening = old.listening.take();
     }
 
-    fn stop_listening(&self, parent: &EventTarget) -> Result<(), JsValue> {
-        if let Some(ref dom_listener) = self.dom_listener {
-            parent.remove_event_listener_with_callback(
-                &self.type_,
-                dom_listener.as_ref().unchecked_ref(),
-            )?;
+    fn stop_listening(&self, parent: &Node, rt: &Runtime) -> Result<(), RenderError> {
+        match self.listening {
+            Some(Listening::Direct(ref dom_listener)) => rt
+                .dom
+                .remove_event_listener(parent, self.type_, dom_listener)
+                .map_err(RenderError::js(Operation::RemoveListener)),
+            Some(Listening::Delegated) => match (&rt.delegator, &self.handler) {
+                (Some(ref delegator), Some(ref handler)) => delegator
+                    .unregister(parent, self.type_, handler)
+                    .map_err(RenderError::js(Operation::RemoveListener)),
+                _ => Ok(()),
+            },
+            None => Ok(()),
         }
-        Ok(())
     }
 }
 
@@ -472,6 +858,50 @@ impl<'a> From<Cow<'a, str>> for AttributeValue {
     }
 }
 
+impl<'a> From<&'a str> for PropertyValue {
+    fn from(val: &'a str) -> PropertyValue {
+        PropertyValue::String(val.to_string())
+    }
+}
+
+impl<'a> From<&'a String> for PropertyValue {
+    fn from(val: &'a String) -> PropertyValue {
+        PropertyValue::String(val.clone())
+    }
+}
+
+impl From<String> for PropertyValue {
+    fn from(val: String) -> PropertyValue {
+        PropertyValue::String(val)
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for PropertyValue {
+    fn from(val: Cow<'a, str>) -> PropertyValue {
+        PropertyValue::String(val.into())
+    }
+}
+
+impl From<bool> for PropertyValue {
+    fn from(val: bool) -> PropertyValue {
+        PropertyValue::Bool(val)
+    }
+}
+
+macro_rules! impl_number_property {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for PropertyValue {
+                fn from(val: $t) -> PropertyValue {
+                    PropertyValue::Number(val.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_number_property!(i8, i16, i32, u8, u16, u32, f32, f64);
+
 impl From<Vec<Attribute>> for Attributes {
     fn from(val: Vec<Attribute>) -> Attributes {
         let attrs = val.into_iter().map(|attr| (attr.key, attr.value)).collect();
@@ -484,10 +914,25 @@ pub mod test {
     use super::*;
     use crate::{
         component::root_render_ctx,
+        dom::{
+            test::{memory_runtime, web_runtime},
+            DOMBackend, HTML_NAMESPACE,
+        },
         vdom::{test::container, vtext::VText},
     };
     use wasm_bindgen_test::*;
 
+    type Invoked = Rc<RefCell<Vec<&'static str>>>;
+
+    /// A listener of the `type_` events which records its name when invoked.
+    fn recording(invoked: &Invoked, type_: &'static str, name: &'static str) -> EventListener<()> {
+        let invoked = invoked.clone();
+        EventListener::new(
+            type_,
+            Box::new(move |_: &(), _| invoked.borrow_mut().push(name)),
+        )
+    }
+
     #[test]
     fn should_display_a_div() {
         let div = VElement::<()>::childless("div", vec![], vec![]);
@@ -517,6 +962,61 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn should_display_escaped_attribute_values() {
+        let a = VElement::<()>::childless(
+            "a",
+            vec![Attribute::new("title", r#""><script>alert('&')</script>"#)],
+            vec![],
+        );
+        assert_eq!(
+            format!("{}", a),
+            "<a title=\"&quot;&gt;&lt;script&gt;alert(&#39;&amp;&#39;)&lt;/script&gt;\"></a>"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn should_hydrate_existing_element() {
+        let mut div_el = VElement::new(
+            "div",
+            vec![Attribute::new("class", "bg-white")],
+            vec![],
+            VNode::from(VText::text("Hello")),
+        );
+        let div = container();
+        div.set_inner_html(r#"<div class="bg-white">Hello</div>"#);
+        let existing = div.first_child();
+
+        div_el
+            .hydrate(
+                &Node::web(div.clone()),
+                existing.clone().map(Node::web),
+                root_render_ctx(),
+                &web_runtime(),
+            ).expect("To hydrate div");
+
+        assert!(div_el.node().unwrap().as_web().is_same_node(existing.as_ref()));
+        assert_eq!(div.inner_html(), r#"<div class="bg-white">Hello</div>"#);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_render_on_client_when_hydration_mismatches() {
+        let mut button_el =
+            VElement::new("button", vec![], vec![], VNode::from(VText::text("Click")));
+        let div = container();
+        div.set_inner_html("<span>Click</span>");
+
+        button_el
+            .hydrate(
+                &Node::web(div.clone()),
+                div.first_child().map(Node::web),
+                root_render_ctx(),
+                &web_runtime(),
+            ).expect("To hydrate div");
+
+        assert_eq!(div.inner_html(), "<button>Click</button>");
+    }
+
     #[wasm_bindgen_test]
     fn should_patch_container_with_button_element() {
         let mut button_el = VElement::childless("button", vec![], vec![]);
@@ -524,10 +1024,10 @@ pub mod test {
         button_el
             .patch(
                 None,
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(div.inner_html(), "<button></button>");
@@ -547,10 +1047,10 @@ pub mod test {
         button_el
             .patch(
                 None,
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(
@@ -575,10 +1075,10 @@ pub mod test {
         div_el
             .patch(
                 None,
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(
@@ -594,10 +1094,10 @@ pub mod test {
         div_el
             .patch(
                 None,
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(div.inner_html(), "<div></div>");
@@ -606,10 +1106,10 @@ pub mod test {
         button_el
             .patch(
                 Some(&mut div_el),
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(div.inner_html(), "<button></button>");
@@ -623,10 +1123,10 @@ pub mod test {
         div_el
             .patch(
                 None,
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(div.inner_html(), r#"<div class="bg-white"></div>"#);
@@ -642,10 +1142,10 @@ pub mod test {
         div_diff
             .patch(
                 Some(&mut div_el),
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(
@@ -653,4 +1153,589 @@ pub mod test {
             r#"<div class="bg-white txt-black" id="main"></div>"#
         )
     }
+
+    #[test]
+    fn should_patch_nested_elements_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut div_el = VElement::new(
+            "div",
+            vec![Attribute::new("class", "bg-white")],
+            vec![],
+            VNode::from(VElement::childless("br", vec![], vec![])),
+        );
+        div_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        assert_eq!(dom.inner_html(&parent), r#"<div class="bg-white"><br></div>"#);
+        assert_eq!(dom.mutations().created, 2);
+        assert_eq!(dom.mutations().inserted, 2);
+        assert_eq!(dom.mutations().attributes_set, 1);
+    }
+
+    #[test]
+    fn should_diff_attributes_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut div_el = VElement::childless(
+            "div",
+            vec![
+                Attribute::new("class", "bg-white"),
+                Attribute::new("hidden", true),
+            ],
+            vec![],
+        );
+        div_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        dom.reset_mutations();
+        let mut div_diff = VElement::childless(
+            "div",
+            vec![
+                Attribute::new("id", "main"),
+                Attribute::new("hidden", false),
+            ],
+            vec![],
+        );
+        div_diff
+            .patch(Some(&mut div_el), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        assert_eq!(dom.inner_html(&parent), r#"<div id="main"></div>"#);
+        assert_eq!(dom.mutations().created, 0);
+        assert_eq!(dom.mutations().attributes_set, 1);
+        assert_eq!(dom.mutations().attributes_removed, 2);
+    }
+
+    #[test]
+    fn should_create_elements_in_their_namespaces_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut svg_el = VElement::new(
+            "svg",
+            vec![Attribute::new("viewBox", "0 0 10 10")],
+            vec![],
+            VNode::from(VElement::new(
+                "g",
+                vec![],
+                vec![],
+                VNode::from(VElement::new(
+                    "foreignObject",
+                    vec![],
+                    vec![],
+                    VNode::from(VElement::childless("p", vec![], vec![])),
+                )),
+            )),
+        );
+        svg_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        let svg = dom.first_child(&parent).unwrap();
+        let g = dom.first_child(&svg).unwrap();
+        let foreign_object = dom.first_child(&g).unwrap();
+        let p = dom.first_child(&foreign_object).unwrap();
+        assert_eq!(dom.namespace_uri(&svg), Some(SVG_NAMESPACE.to_string()));
+        assert_eq!(dom.namespace_uri(&g), Some(SVG_NAMESPACE.to_string()));
+        assert_eq!(
+            dom.namespace_uri(&foreign_object),
+            Some(SVG_NAMESPACE.to_string())
+        );
+        assert_eq!(dom.namespace_uri(&p), Some(HTML_NAMESPACE.to_string()));
+
+        let mut math_el = VElement::new(
+            "math",
+            vec![],
+            vec![],
+            VNode::from(VElement::childless("mi", vec![], vec![])),
+        );
+        math_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        let mi = dom.first_child(&math_el.node.clone().unwrap()).unwrap();
+        assert_eq!(dom.namespace_uri(&mi), Some(MATHML_NAMESPACE.to_string()));
+    }
+
+    #[test]
+    fn should_diff_namespaced_attributes_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut use_el = VElement::childless(
+            "use",
+            vec![Attribute::new("xlink:href", "#dot")],
+            vec![],
+        );
+        use_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        let el = use_el.node.clone().unwrap();
+        assert_eq!(
+            dom.attribute_namespace(&el, "xlink:href"),
+            Some(XLINK_NAMESPACE.to_string())
+        );
+
+        let mut use_diff = VElement::childless("use", vec![], vec![]);
+        use_diff
+            .patch(Some(&mut use_el), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.inner_html(&parent), "<use></use>");
+    }
+
+    #[test]
+    fn should_keep_the_listener_of_the_same_event_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let invoked = Invoked::default();
+        let mut button_el =
+            VElement::childless("button", vec![], vec![recording(&invoked, "click", "old")]);
+        button_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        let button = button_el.node().unwrap().clone();
+        assert_eq!(dom.listener_count(&button, "click"), 1);
+
+        let mut updated =
+            VElement::childless("button", vec![], vec![recording(&invoked, "click", "new")]);
+        updated
+            .patch(Some(&mut button_el), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        assert_eq!(dom.listener_count(&button, "click"), 1);
+        assert_eq!(dom.mutations().listeners_added, 1);
+        assert_eq!(dom.mutations().listeners_removed, 0);
+
+        // The kept DOM listener invokes the new handler.
+        dom.dispatch(&button, "click");
+        assert_eq!(*invoked.borrow(), vec!["new"]);
+    }
+
+    #[test]
+    fn should_diff_listeners_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let invoked = Invoked::default();
+        let mut input_el = VElement::childless(
+            "input",
+            vec![],
+            vec![
+                recording(&invoked, "input", "old input"),
+                recording(&invoked, "focus", "focus"),
+                EventListener::new("scroll", Box::new(|_: &(), _| {})),
+            ],
+        );
+        input_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        let input = input_el.node().unwrap().clone();
+        dom.reset_mutations();
+
+        let mut updated = VElement::childless(
+            "input",
+            vec![],
+            vec![
+                recording(&invoked, "blur", "blur"),
+                recording(&invoked, "input", "new input"),
+                EventListener::new("scroll", Box::new(|_: &(), _| {})).passive(),
+            ],
+        );
+        updated
+            .patch(Some(&mut input_el), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        // The input listener is kept, the focus listener is removed, the blur
+        // listener is added and the scroll listener is added anew with its
+        // changed options.
+        assert_eq!(dom.mutations().listeners_added, 2);
+        assert_eq!(dom.mutations().listeners_removed, 2);
+        assert_eq!(dom.listener_count(&input, "input"), 1);
+        assert_eq!(dom.listener_count(&input, "focus"), 0);
+        assert_eq!(dom.listener_count(&input, "blur"), 1);
+        assert_eq!(dom.listener_count(&input, "scroll"), 1);
+
+        for type_ in &["input", "focus", "blur"] {
+            dom.dispatch(&input, type_);
+        }
+        assert_eq!(*invoked.borrow(), vec!["new input", "blur"]);
+    }
+
+    #[test]
+    fn should_add_listeners_with_options_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut div_el = VElement::childless(
+            "div",
+            vec![],
+            vec![
+                EventListener::new("scroll", Box::new(|_: &(), _| {})).passive(),
+                EventListener::new("click", Box::new(|_: &(), _| {}))
+                    .prevent()
+                    .capture()
+                    .once(),
+            ],
+        );
+        div_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        let div = div_el.node().unwrap().clone();
+
+        assert_eq!(
+            dom.listener_options(&div, "scroll"),
+            vec![ListenerOptions {
+                passive: true,
+                ..Default::default()
+            }]
+        );
+        assert_eq!(
+            dom.listener_options(&div, "click"),
+            vec![ListenerOptions {
+                capture: true,
+                passive: false,
+                once: true,
+            }]
+        );
+
+        // The default action is prevented only once.
+        assert!(!dom.dispatch(&div, "click"));
+        assert!(dom.dispatch(&div, "click"));
+        assert_eq!(dom.listener_count(&div, "click"), 0);
+    }
+
+    #[test]
+    fn should_apply_the_modifiers_of_listeners_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let invoked = Invoked::default();
+        let mut form_el = VElement::new(
+            "form",
+            vec![],
+            vec![
+                recording(&invoked, "click", "form"),
+                recording(&invoked, "keydown", "form"),
+            ],
+            VNode::from(vec![
+                VNode::from(VElement::childless(
+                    "button",
+                    vec![],
+                    vec![recording(&invoked, "click", "button").stop()],
+                )),
+                VNode::from(VElement::childless(
+                    "input",
+                    vec![],
+                    vec![recording(&invoked, "keydown", "enter").key("Enter")],
+                )),
+            ]),
+        );
+        form_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        let button = dom.first_child(form_el.node().unwrap()).unwrap();
+        let input = dom.next_sibling(&button).unwrap();
+
+        dom.dispatch(&button, "click");
+        assert_eq!(*invoked.borrow(), vec!["button"]);
+
+        invoked.borrow_mut().clear();
+        dom.dispatch_key(&input, "keydown", "Escape");
+        dom.dispatch_key(&input, "keydown", "Enter");
+        assert_eq!(*invoked.borrow(), vec!["form", "enter", "form"]);
+    }
+
+    #[test]
+    fn should_render_on_client_when_hydration_mismatches_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let span = rt.dom.create_element("span").unwrap();
+        rt.dom.insert_before(&parent, &span, None).unwrap();
+        let mut button_el =
+            VElement::new("button", vec![], vec![], VNode::from(VText::text("Click")));
+
+        button_el
+            .hydrate(&parent, Some(span), root_render_ctx(), &rt)
+            .expect("To hydrate the container");
+
+        assert_eq!(dom.inner_html(&parent), "<button>Click</button>");
+        assert_eq!(dom.warnings().len(), 1);
+    }
+
+    #[test]
+    fn should_delegate_listeners_to_the_root_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let rt = rt.delegate_to(&parent);
+        let invoked = Invoked::default();
+        let rows = || {
+            VElement::new(
+                "ul",
+                vec![],
+                vec![recording(&invoked, "click", "ul")],
+                VNode::from(
+                    ["first", "second", "third"]
+                        .iter()
+                        .map(|name| {
+                            VNode::from(VElement::childless(
+                                "li",
+                                vec![],
+                                vec![recording(&invoked, "click", name).stop()],
+                            ))
+                        }).collect::<Vec<_>>(),
+                ),
+            )
+        };
+        let mut list_el =
+            VElement::childless("div", vec![], vec![recording(&invoked, "focus", "div")]);
+        let mut ul_el = rows();
+        list_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        ul_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        // The focus event does not bubble, so it is not delegated.
+        assert_eq!(dom.mutations().listeners_added, 2);
+        assert_eq!(dom.listener_count(&parent, "click"), 1);
+        assert_eq!(dom.listener_count(list_el.node().unwrap(), "focus"), 1);
+
+        let mut updated = rows();
+        updated
+            .patch(Some(&mut ul_el), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.mutations().listeners_added, 2);
+
+        let ul = updated.node().unwrap().clone();
+        let second = dom.next_sibling(&dom.first_child(&ul).unwrap()).unwrap();
+        dom.dispatch(&second, "click");
+        dom.dispatch(&ul, "click");
+        dom.dispatch(list_el.node().unwrap(), "focus");
+        assert_eq!(*invoked.borrow(), vec!["second", "ul", "div"]);
+
+        updated.remove(&parent, &rt).expect("To remove the list");
+        assert_eq!(dom.listener_count(&parent, "click"), 0);
+    }
+
+    #[test]
+    fn should_delegate_every_listener_of_the_same_event_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let rt = rt.delegate_to(&parent);
+        let invoked = Invoked::default();
+        let mut button_el = VElement::childless(
+            "button",
+            vec![],
+            vec![
+                recording(&invoked, "click", "first"),
+                recording(&invoked, "click", "second"),
+            ],
+        );
+        button_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        let delegator = rt.delegator.clone().unwrap();
+        let button = button_el.node().unwrap().clone();
+        assert_eq!(delegator.handler_count(&button, "click"), 2);
+        dom.dispatch(&button, "click");
+        assert_eq!(*invoked.borrow(), vec!["first", "second"]);
+
+        let mut single_el =
+            VElement::childless("button", vec![], vec![recording(&invoked, "click", "single")]);
+        single_el
+            .patch(Some(&mut button_el), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(delegator.handler_count(&button, "click"), 1);
+        assert_eq!(dom.listener_count(&parent, "click"), 1);
+
+        invoked.borrow_mut().clear();
+        dom.dispatch(&button, "click");
+        assert_eq!(*invoked.borrow(), vec!["single"]);
+    }
+
+    #[test]
+    fn should_delegate_a_bound_value_along_with_an_input_listener_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let rt = rt.delegate_to(&parent);
+        let invoked = Invoked::default();
+        let invalid = Rc::new(RefCell::new(vec![]));
+        let input = || {
+            let invalid = invalid.clone();
+            VElement::childless(
+                "input",
+                vec![],
+                vec![
+                    crate::bind::value::<(), u8>(
+                        |_, _| {},
+                        move |_, error| invalid.borrow_mut().push(error.value),
+                    ),
+                    recording(&invoked, "input", "input"),
+                ],
+            )
+        };
+        let mut input_el = input();
+        input_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        let delegator = rt.delegator.clone().unwrap();
+        let el = input_el.node().unwrap().clone();
+        assert_eq!(delegator.handler_count(&el, "input"), 2);
+
+        let mut updated = input();
+        updated
+            .patch(Some(&mut input_el), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(delegator.handler_count(&el, "input"), 2);
+
+        // The bound value is read from the input, along with the listener
+        // being invoked.
+        dom.set_property(&el, "value", &PropertyValue::from("many"))
+            .unwrap();
+        dom.dispatch(&el, "input");
+        assert_eq!(*invalid.borrow(), vec!["many".to_string()]);
+        assert_eq!(*invoked.borrow(), vec!["input"]);
+
+        updated.remove(&parent, &rt).expect("To remove the input");
+        assert_eq!(delegator.handler_count(&el, "input"), 0);
+        assert_eq!(dom.listener_count(&parent, "input"), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_dispatch_delegated_events() {
+        use web_sys::EventInit;
+
+        let clicked = Rc::new(RefCell::new(vec![]));
+        let on_click = |name: &'static str| {
+            let clicked = clicked.clone();
+            EventListener::new(
+                "click",
+                Box::new(move |_: &(), _| clicked.borrow_mut().push(name)),
+            )
+        };
+        let mut div_el = VElement::new(
+            "div",
+            vec![],
+            vec![on_click("div")],
+            VNode::from(vec![
+                VNode::from(VElement::childless("span", vec![], vec![on_click("span")])),
+                VNode::from(VElement::childless(
+                    "button",
+                    vec![],
+                    vec![on_click("button").stop()],
+                )),
+            ]),
+        );
+        let div = container();
+        let parent = Node::web(div.clone());
+        let rt = web_runtime().delegate_to(&parent);
+        div_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch div");
+
+        let click = || {
+            let init = EventInit::new();
+            init.set_bubbles(true);
+            Event::new_with_event_init_dict("click", &init).unwrap()
+        };
+        let span = div.query_selector("span").unwrap().unwrap();
+        let button = div.query_selector("button").unwrap().unwrap();
+        span.dispatch_event(&click()).unwrap();
+        button.dispatch_event(&click()).unwrap();
+
+        assert_eq!(*clicked.borrow(), vec!["span", "div", "button"]);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_not_pass_a_plain_event_to_a_typed_listener() {
+        use web_sys::InputEvent;
+
+        let invoked = Invoked::default();
+        let typed = invoked.clone();
+        let mut input_el = VElement::childless(
+            "input",
+            vec![],
+            vec![
+                EventListener::typed(
+                    "input",
+                    Box::new(move |_: &(), _: InputEvent| typed.borrow_mut().push("typed")),
+                ),
+                recording(&invoked, "input", "plain"),
+            ],
+        );
+        let div = container();
+        let parent = Node::web(div.clone());
+        let rt = web_runtime();
+        input_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch div");
+
+        // Like the ones dispatched by the form libraries.
+        let input = div.query_selector("input").unwrap().unwrap();
+        input.dispatch_event(&Event::new("input").unwrap()).unwrap();
+        assert_eq!(*invoked.borrow(), vec!["plain"]);
+    }
+
+    #[test]
+    fn should_sync_properties_with_the_live_dom_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let input = |value: &str| {
+            VElement::<()>::childless("input", vec![], vec![])
+                .with_properties(vec![Property::new("value", value)])
+        };
+        let mut input_el = input("Ruukh");
+        input_el
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        let el = input_el.node().unwrap().clone();
+        assert_eq!(
+            dom.get_property(&el, "value"),
+            Some(PropertyValue::String("Ruukh".to_string()))
+        );
+
+        // The same value is not set again.
+        dom.reset_mutations();
+        let mut same = input("Ruukh");
+        same
+            .patch(Some(&mut input_el), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.mutations().properties_set, 0);
+
+        // The user typed, so the value is set back although the VDOM is the
+        // same.
+        dom.set_property(&el, "value", &PropertyValue::from("Ruukh!"))
+            .unwrap();
+        dom.reset_mutations();
+        let mut controlled = input("Ruukh");
+        controlled
+            .patch(Some(&mut same), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.mutations().properties_set, 1);
+        assert_eq!(
+            dom.get_property(&el, "value"),
+            Some(PropertyValue::String("Ruukh".to_string()))
+        );
+
+        // A property which is not bound anymore is cleared.
+        let mut unbound = VElement::<()>::childless("input", vec![], vec![]);
+        unbound
+            .patch(Some(&mut controlled), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(
+            dom.get_property(&el, "value"),
+            Some(PropertyValue::String(String::new()))
+        );
+    }
+
+    #[test]
+    fn should_compare_numbers_with_the_live_strings() {
+        let number = PropertyValue::Number(1.0);
+        assert!(is_live(Some(PropertyValue::from("1.")), &number));
+        assert!(is_live(Some(PropertyValue::from(" 1")), &number));
+        assert!(!is_live(Some(PropertyValue::from("")), &number));
+        assert!(!is_live(None, &number));
+    }
 }
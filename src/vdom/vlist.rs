@@ -2,8 +2,11 @@
 
 use crate::{
     component::Render,
-    dom::DOMPatch,
-    vdom::{Key, VNode},
+    dom::{DOMPatch, Node, Runtime},
+    error::RenderError,
+    hydrate::Hydrate,
+    ssr::SSRWalk,
+    vdom::{vslot, Key, VNode},
     MessageSender, Shared,
 };
 use fnv::FnvBuildHasher;
@@ -11,12 +14,16 @@ use indexmap::IndexMap;
 use std::{
     collections::HashSet,
     fmt::{self, Display, Formatter},
+    iter::FromIterator,
 };
-use wasm_bindgen::prelude::JsValue;
-use web_sys::Node;
 
 /// The representation of a list of vnodes in the vtree.
-pub struct VList<RCTX: Render>(IndexMap<Key, VNode<RCTX>, FnvBuildHasher>);
+pub struct VList<RCTX: Render> {
+    nodes: IndexMap<Key, VNode<RCTX>, FnvBuildHasher>,
+    /// Whether all the vnodes are keyed by the user, rather than by their
+    /// position in the list.
+    keyed: bool,
+}
 
 impl<RCTX: Render> From<VList<RCTX>> for VNode<RCTX> {
     fn from(list: VList<RCTX>) -> VNode<RCTX> {
@@ -24,27 +31,141 @@ impl<RCTX: Render> From<VList<RCTX>> for VNode<RCTX> {
     }
 }
 
-impl<RCTX: Render> From<Vec<VNode<RCTX>>> for VList<RCTX> {
-    fn from(children: Vec<VNode<RCTX>>) -> Self {
-        VList(
-            children
-                .into_iter()
-                .enumerate()
-                .map(|(k, v)| (Key::new(k as u32), v))
-                .collect(),
-        )
+impl<RCTX: Render> VList<RCTX> {
+    /// Creates an empty list of keyed vnodes.
+    pub fn keyed(capacity: usize) -> VList<RCTX> {
+        VList {
+            nodes: IndexMap::with_capacity_and_hasher(capacity, FnvBuildHasher::default()),
+            keyed: true,
+        }
+    }
+
+    /// Appends the vnode along with its key to the list.
+    ///
+    /// A key must be unique within a list. A duplicate key panics in debug
+    /// builds, along with the path of the components being rendered. In
+    /// release builds, the vnode is kept under a key derived from the
+    /// duplicate one.
+    pub fn insert(&mut self, key: Key, vnode: VNode<RCTX>) {
+        let key = if self.nodes.contains_key(&key) {
+            if cfg!(debug_assertions) {
+                panic!(
+                    "Duplicate key `{:?}` in a list rendered within `{}`.",
+                    key,
+                    vslot::rendering_path().join(" > ")
+                );
+            }
+            let mut count = 1;
+            loop {
+                let fallback = Key::Duplicate(Box::new(key.clone()), count);
+                if !self.nodes.contains_key(&fallback) {
+                    break fallback;
+                }
+                count += 1;
+            }
+        } else {
+            key
+        };
+        self.nodes.insert(key, vnode);
+    }
+
+    /// Whether the vnodes are keyed by the user, rather than by their
+    /// position in the list.
+    pub fn is_keyed(&self) -> bool {
+        self.keyed
+    }
+
+    /// Gets the keys of the vnodes in order.
+    pub fn keys(&self) -> impl Iterator<Item = &Key> {
+        self.nodes.keys()
+    }
+
+    /// Unwraps a keyed list of a single vnode into its key and vnode.
+    fn into_single_keyed(mut self) -> Result<(Key, VNode<RCTX>), VList<RCTX>> {
+        if self.keyed && self.nodes.len() == 1 {
+            Ok(self.nodes.pop().unwrap())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// An item of a list of vnodes, which is either a vnode or a vnode along
+/// with its key.
+///
+/// The list is keyed when every item has a key, otherwise the vnodes are
+/// keyed by their position in the list. A vnode has the key of the element
+/// when it is a keyed list of a single vnode, i.e. the markup of a single
+/// keyed element.
+pub trait ListItem<RCTX: Render> {
+    /// Splits the item into its key, if any, and its vnode.
+    fn into_entry(self) -> (Option<Key>, VNode<RCTX>);
+}
+
+impl<RCTX: Render> ListItem<RCTX> for VNode<RCTX> {
+    fn into_entry(self) -> (Option<Key>, VNode<RCTX>) {
+        match self {
+            VNode::List(list) => match list.into_single_keyed() {
+                Ok((key, vnode)) => (Some(key), vnode),
+                Err(list) => (None, VNode::List(list)),
+            },
+            vnode => (None, vnode),
+        }
+    }
+}
+
+impl<K: Into<Key>, RCTX: Render> ListItem<RCTX> for (K, VNode<RCTX>) {
+    fn into_entry(self) -> (Option<Key>, VNode<RCTX>) {
+        (Some(self.0.into()), self.1)
+    }
+}
+
+impl<RCTX: Render, T: ListItem<RCTX>> FromIterator<T> for VList<RCTX> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let entries: Vec<_> = iter.into_iter().map(ListItem::into_entry).collect();
+        let mut list = VList::keyed(entries.len());
+        if entries.iter().all(|(key, _)| key.is_some()) {
+            for (key, vnode) in entries {
+                list.insert(key.unwrap(), vnode);
+            }
+        } else {
+            // The keyed vnodes are kept within their own list, so that they
+            // are still identified by their key at their position.
+            list.keyed = false;
+            for (index, (key, vnode)) in entries.into_iter().enumerate() {
+                let vnode = match key {
+                    Some(key) => {
+                        let mut keyed = VList::keyed(1);
+                        keyed.insert(key, vnode);
+                        VNode::List(keyed)
+                    }
+                    None => vnode,
+                };
+                list.nodes.insert(Key::new(index as u32), vnode);
+            }
+        }
+        list
+    }
+}
+
+impl<RCTX: Render, T: ListItem<RCTX>> From<Vec<T>> for VList<RCTX> {
+    fn from(children: Vec<T>) -> Self {
+        children.into_iter().collect()
     }
 }
 
 impl<RCTX: Render> From<IndexMap<Key, VNode<RCTX>, FnvBuildHasher>> for VList<RCTX> {
     fn from(map: IndexMap<Key, VNode<RCTX>, FnvBuildHasher>) -> Self {
-        VList(map)
+        VList {
+            nodes: map,
+            keyed: true,
+        }
     }
 }
 
 impl<RCTX: Render> Display for VList<RCTX> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        for (_, vnode) in self.0.iter() {
+        for (_, vnode) in self.nodes.iter() {
             write!(f, "{}", vnode)?;
         }
         Ok(())
@@ -60,11 +181,11 @@ impl<RCTX: Render> DOMPatch for VList<RCTX> {
         parent: &Self::Node,
         next: Option<&Self::Node>,
         render_ctx: Shared<Self::RenderContext>,
-        rx_sender: MessageSender,
-    ) -> Result<(), JsValue> {
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
         let mut next = next;
-        for (_, vnode) in self.0.iter_mut().rev() {
-            vnode.render_walk(parent, next, render_ctx.clone(), rx_sender.clone())?;
+        for (_, vnode) in self.nodes.iter_mut().rev() {
+            vnode.render_walk(parent, next, render_ctx.clone(), rt)?;
             next = vnode.node();
         }
         Ok(())
@@ -76,68 +197,137 @@ impl<RCTX: Render> DOMPatch for VList<RCTX> {
         parent: &Self::Node,
         next: Option<&Self::Node>,
         render_ctx: Shared<Self::RenderContext>,
-        rx_sender: MessageSender,
-    ) -> Result<(), JsValue> {
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
         let mut next = next;
         if let Some(old) = old {
             // Collect the keys of alive nodes from old vlist.
             let mut alive_keys = HashSet::with_hasher(FnvBuildHasher::default());
 
-            for (index, (key, vnode)) in self.0.iter_mut().enumerate().rev() {
+            let old_indices: Vec<_> = self
+                .nodes
+                .keys()
+                .map(|key| old.nodes.get_full(key).map(|(old_index, _, _)| old_index))
+                .collect();
+            let stable = stable_nodes(&old_indices);
+
+            for (index, (key, vnode)) in self.nodes.iter_mut().enumerate().rev() {
                 // Patch the old vnode if found.
-                if let Some((old_index, _, old)) = old.0.get_full_mut(key) {
-                    vnode.patch(
-                        Some(old),
-                        parent,
-                        next,
-                        render_ctx.clone(),
-                        rx_sender.clone(),
-                    )?;
-
-                    // If the order changed, update it in the DOM.
-                    if index != old_index {
-                        vnode.reorder(parent, next)?;
+                if let Some(old) = old.nodes.get_mut(key) {
+                    vnode.patch(Some(old), parent, next, render_ctx.clone(), rt)?;
+
+                    // If the order changed, update it in the DOM. The nodes
+                    // after it are already in place.
+                    if !stable[index] {
+                        vnode.reorder(parent, next, rt)?;
                     }
 
                     alive_keys.insert(key);
                 } else {
-                    vnode.patch(None, parent, next, render_ctx.clone(), rx_sender.clone())?;
+                    vnode.patch(None, parent, next, render_ctx.clone(), rt)?;
                 }
 
                 next = vnode.node().or(next);
             }
 
             // Remove all the remaining ones.
-            for (key, vnode) in old.0.iter() {
+            for (key, vnode) in old.nodes.iter() {
                 if !alive_keys.contains(key) {
-                    vnode.remove(parent)?;
+                    vnode.remove(parent, rt)?;
                 }
             }
         } else {
-            for (_, vnode) in self.0.iter_mut().rev() {
-                vnode.patch(None, parent, next, render_ctx.clone(), rx_sender.clone())?;
+            for (_, vnode) in self.nodes.iter_mut().rev() {
+                vnode.patch(None, parent, next, render_ctx.clone(), rt)?;
                 next = vnode.node().or(next);
             }
         }
         Ok(())
     }
 
-    fn reorder(&self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
-        for (_, node) in self.0.iter() {
-            node.reorder(parent, next)?;
+    fn reorder(&self, parent: &Node, next: Option<&Node>, rt: &Runtime) -> Result<(), RenderError> {
+        for (_, node) in self.nodes.iter() {
+            node.reorder(parent, next, rt)?;
         }
         Ok(())
     }
 
-    fn remove(&self, parent: &Self::Node) -> Result<(), JsValue> {
-        for (_, vnode) in self.0.iter() {
-            vnode.remove(parent)?;
+    fn remove(&self, parent: &Self::Node, rt: &Runtime) -> Result<(), RenderError> {
+        for (_, vnode) in self.nodes.iter() {
+            vnode.remove(parent, rt)?;
         }
         Ok(())
     }
 
     fn node(&self) -> Option<&Node> {
-        self.0.get_index(0).and_then(|(_, first)| first.node())
+        self.nodes.get_index(0).and_then(|(_, first)| first.node())
+    }
+}
+
+/// Marks the nodes which are kept in place while the rest of them are moved
+/// around them, given the old index of every node if it was there before.
+///
+/// The nodes in the longest increasing subsequence of the old indices are
+/// already in order relative to each other, so the fewest moves are needed.
+fn stable_nodes(old_indices: &[Option<usize>]) -> Vec<bool> {
+    // The index of the smallest tail of the increasing subsequences of every
+    // length found so far.
+    let mut tails: Vec<usize> = vec![];
+    let mut predecessors = vec![None; old_indices.len()];
+    for (index, old_index) in old_indices.iter().enumerate() {
+        let old_index = match old_index {
+            Some(old_index) => old_index,
+            None => continue,
+        };
+        let len = match tails.binary_search_by_key(&old_index, |&tail| {
+            old_indices[tail].as_ref().unwrap()
+        }) {
+            Ok(len) | Err(len) => len,
+        };
+        if len > 0 {
+            predecessors[index] = Some(tails[len - 1]);
+        }
+        if len == tails.len() {
+            tails.push(index);
+        } else {
+            tails[len] = index;
+        }
+    }
+
+    let mut stable = vec![false; old_indices.len()];
+    let mut last = tails.last().cloned();
+    while let Some(index) = last {
+        stable[index] = true;
+        last = predecessors[index];
+    }
+    stable
+}
+
+impl<RCTX: Render> Hydrate for VList<RCTX> {
+    type RenderContext = RCTX;
+
+    fn hydrate(
+        &mut self,
+        parent: &Node,
+        existing: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rt: &Runtime,
+    ) -> Result<Option<Node>, RenderError> {
+        let mut existing = existing;
+        for (_, vnode) in self.nodes.iter_mut() {
+            existing = vnode.hydrate(parent, existing, render_ctx.clone(), rt)?;
+        }
+        Ok(existing)
+    }
+}
+
+impl<RCTX: Render> SSRWalk for VList<RCTX> {
+    type RenderContext = RCTX;
+
+    fn ssr_walk(&mut self, render_ctx: Shared<Self::RenderContext>, rx_sender: MessageSender) {
+        for (_, vnode) in self.nodes.iter_mut() {
+            vnode.ssr_walk(render_ctx.clone(), rx_sender.clone());
+        }
     }
 }
 
@@ -146,6 +336,10 @@ pub mod test {
     use super::*;
     use crate::{
         component::root_render_ctx,
+        dom::{
+            memory::Mutations,
+            test::{memory_runtime, web_runtime},
+        },
         vdom::{test::container, velement::VElement, vtext::VText, VNode},
     };
     use wasm_bindgen_test::*;
@@ -168,10 +362,10 @@ pub mod test {
         let div = container();
         list.patch(
             None,
-            div.as_ref(),
+            &Node::web(div.clone()),
             None,
             root_render_ctx(),
-            crate::message_sender(),
+            &web_runtime(),
         ).expect("To patch div");
 
         assert_eq!(div.inner_html(), "Hello World!<div></div>");
@@ -186,10 +380,10 @@ pub mod test {
         let div = container();
         list.patch(
             None,
-            div.as_ref(),
+            &Node::web(div.clone()),
             None,
             root_render_ctx(),
-            crate::message_sender(),
+            &web_runtime(),
         ).expect("To patch div");
 
         assert_eq!(div.inner_html(), "Hello World!<div></div>");
@@ -202,12 +396,268 @@ pub mod test {
         new_list
             .patch(
                 Some(&mut list),
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(div.inner_html(), "<div></div>Hello World!How are you?");
     }
+
+    #[test]
+    fn should_patch_updated_list_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut list = VList::from(vec![
+            VNode::from(VText::text("Hello World!")),
+            VNode::from(VElement::childless("div", vec![], vec![])),
+        ]);
+        list.patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.inner_html(&parent), "Hello World!<div></div>");
+
+        dom.reset_mutations();
+        let mut new_list = VList::from(vec![
+            VNode::from(VText::text("Hello World!")),
+            VNode::from(VElement::childless("div", vec![], vec![])),
+            VNode::from(VText::text("How are you?")),
+        ]);
+        new_list
+            .patch(Some(&mut list), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        assert_eq!(
+            dom.inner_html(&parent),
+            "Hello World!<div></div>How are you?"
+        );
+        assert_eq!(dom.mutations().created, 1);
+        assert_eq!(dom.mutations().inserted, 1);
+        assert_eq!(dom.mutations().removed, 0);
+    }
+
+    #[test]
+    fn should_remove_dropped_keys_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut list = VList::from(vec![
+            VNode::from(VText::text("First")),
+            VNode::from(VText::text("Second")),
+            VNode::from(VText::text("Third")),
+        ]);
+        list.patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        dom.reset_mutations();
+        let mut new_list = VList::from(vec![VNode::from(VText::text("First"))]);
+        new_list
+            .patch(Some(&mut list), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        assert_eq!(dom.inner_html(&parent), "First");
+        assert_eq!(dom.mutations().removed, 2);
+        assert_eq!(dom.mutations().total(), 2);
+    }
+
+    fn keyed_row(id: u32) -> VNode<()> {
+        let mut map = IndexMap::with_hasher(FnvBuildHasher::default());
+        map.insert(
+            Key::new(id),
+            VNode::from(VElement::childless("tr", vec![], vec![])),
+        );
+        VNode::from(VList::from(map))
+    }
+
+    #[test]
+    fn should_pick_up_the_keys_of_the_items() {
+        let list: VList<()> = vec![keyed_row(3), keyed_row(1)].into_iter().collect();
+        assert!(list.keyed);
+        assert!(list.nodes.contains_key(&Key::new(3u32)));
+
+        let list: VList<()> = vec![keyed_row(3), VNode::from(VText::text("Text"))].into();
+        assert!(!list.keyed);
+        assert!(list.nodes.contains_key(&Key::new(0u32)));
+        assert!(list.nodes.contains_key(&Key::new(1u32)));
+        match list.nodes.get_index(0) {
+            Some((_, VNode::List(ref keyed))) => {
+                assert!(keyed.nodes.contains_key(&Key::new(3u32)))
+            }
+            _ => panic!("Expected the keyed row to be kept within its own list"),
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "Duplicate key `U32(3)`")]
+    fn should_panic_on_duplicate_keys() {
+        let _: VList<()> = vec![keyed_row(3), keyed_row(1), keyed_row(3)].into();
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[test]
+    fn should_keep_the_nodes_with_duplicate_keys() {
+        let list: VList<()> = vec![keyed_row(3), keyed_row(3), keyed_row(3)].into();
+        assert_eq!(list.nodes.len(), 3);
+        assert!(
+            list.nodes
+                .contains_key(&Key::Duplicate(Box::new(Key::new(3u32)), 2))
+        );
+    }
+
+    #[test]
+    fn should_not_recreate_reordered_items_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut list: VList<()> = (1..4u32)
+            .map(|id| (id, VNode::from(VText::text(id.to_string()))))
+            .collect();
+        list.patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        dom.reset_mutations();
+        let mut new_list: VList<()> = (1..4u32)
+            .rev()
+            .map(|id| (id, VNode::from(VText::text(id.to_string()))))
+            .collect();
+        new_list
+            .patch(Some(&mut list), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        assert_eq!(dom.inner_html(&parent), "321");
+        assert_eq!(dom.mutations().created, 0);
+        assert_eq!(dom.mutations().removed, 0);
+    }
+
+    fn keyed_texts(keys: &[u32]) -> VList<()> {
+        keys.iter()
+            .map(|&key| (key, VNode::from(VText::text(key.to_string()))))
+            .collect()
+    }
+
+    /// Patches the list keyed in the old order with the one keyed in the new
+    /// order and returns the mutations done by the latter.
+    fn patch_keyed(old: &[u32], new: &[u32]) -> Mutations {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut list = keyed_texts(old);
+        list.patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        dom.reset_mutations();
+        let mut new_list = keyed_texts(new);
+        new_list
+            .patch(Some(&mut list), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+
+        let expected: String = new.iter().map(|key| key.to_string()).collect();
+        assert_eq!(dom.inner_html(&parent), expected);
+        dom.mutations()
+    }
+
+    #[test]
+    fn should_only_insert_the_prepended_node() {
+        let mutations = patch_keyed(&[1, 2, 3, 4], &[0, 1, 2, 3, 4]);
+        assert_eq!(mutations.created, 1);
+        assert_eq!(mutations.inserted, 1);
+        assert_eq!(mutations.total(), 2);
+    }
+
+    #[test]
+    fn should_only_move_the_node_moved_to_the_front() {
+        let mutations = patch_keyed(&[1, 2, 3, 4, 5], &[5, 1, 2, 3, 4]);
+        assert_eq!(mutations.inserted, 1);
+        assert_eq!(mutations.total(), 1);
+    }
+
+    #[test]
+    fn should_move_all_but_one_of_the_reversed_nodes() {
+        let mutations = patch_keyed(&[1, 2, 3, 4, 5], &[5, 4, 3, 2, 1]);
+        assert_eq!(mutations.inserted, 4);
+        assert_eq!(mutations.total(), 4);
+    }
+
+    #[test]
+    fn should_only_move_the_swapped_nodes() {
+        let mutations = patch_keyed(&[1, 2, 3, 4, 5], &[5, 2, 3, 4, 1]);
+        assert_eq!(mutations.inserted, 2);
+        assert_eq!(mutations.total(), 2);
+    }
+
+    #[test]
+    fn should_only_remove_the_middle_node() {
+        let mutations = patch_keyed(&[1, 2, 3, 4, 5], &[1, 2, 4, 5]);
+        assert_eq!(mutations.removed, 1);
+        assert_eq!(mutations.total(), 1);
+    }
+
+    #[test]
+    fn should_find_the_stable_nodes() {
+        assert_eq!(
+            stable_nodes(&[Some(4), Some(0), None, Some(1), Some(3), Some(2)]),
+            vec![false, true, false, true, false, true]
+        );
+        assert!(stable_nodes(&[None, None]).iter().all(|stable| !stable));
+    }
+}
+
+#[cfg(test)]
+mod bench {
+    extern crate test;
+
+    use super::*;
+    use crate::{component::root_render_ctx, dom::test::memory_runtime, vdom::vtext::VText};
+    use self::test::Bencher;
+
+    const ROWS: u32 = 1000;
+
+    fn rows(keys: impl Iterator<Item = u32>) -> VList<()> {
+        keys.map(|key| (key, VNode::from(VText::text(key.to_string()))))
+            .collect()
+    }
+
+    fn bench_reorder(b: &mut Bencher, reordered: impl Fn() -> VList<()>) {
+        b.iter(|| {
+            let (dom, rt) = memory_runtime();
+            let parent = dom.container();
+            let mut list = rows(0..ROWS);
+            list.patch(None, &parent, None, root_render_ctx(), &rt)
+                .expect("To patch the container");
+            let mut new_list = reordered();
+            new_list
+                .patch(Some(&mut list), &parent, None, root_render_ctx(), &rt)
+                .expect("To patch the container");
+        });
+    }
+
+    #[bench]
+    fn bench_prepend(b: &mut Bencher) {
+        bench_reorder(b, || rows(Some(ROWS).into_iter().chain(0..ROWS)));
+    }
+
+    #[bench]
+    fn bench_move_last_to_front(b: &mut Bencher) {
+        bench_reorder(b, || rows(Some(ROWS - 1).into_iter().chain(0..ROWS - 1)));
+    }
+
+    #[bench]
+    fn bench_reverse(b: &mut Bencher) {
+        bench_reorder(b, || rows((0..ROWS).rev()));
+    }
+
+    #[bench]
+    fn bench_swap(b: &mut Bencher) {
+        bench_reorder(b, || {
+            rows(
+                Some(ROWS - 1)
+                    .into_iter()
+                    .chain(1..ROWS - 1)
+                    .chain(Some(0)),
+            )
+        });
+    }
+
+    #[bench]
+    fn bench_remove_middle(b: &mut Bencher) {
+        bench_reorder(b, || rows((0..ROWS).filter(|&key| key != ROWS / 2)));
+    }
 }
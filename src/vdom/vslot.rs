@@ -0,0 +1,467 @@
+//! Representation of the markup passed into a component in VDOM.
+//!
+//! The markup between the tags of a component, `<Card>...</Card>`, is passed
+//! to it as its `children` prop, while an element marked as a slot,
+//! `<header slot>...</header>`, is passed as the prop named after its tag.
+//! The component places them anywhere in its own render.
+//!
+//! The markup is still rendered in the context of the component which wrote
+//! it, so that its event listeners are invoked on that component.
+
+use crate::{
+    component::Render,
+    dom::{DOMPatch, Node, Runtime},
+    error::{short_type_name, RenderError},
+    hydrate::Hydrate,
+    ssr::SSRWalk,
+    vdom::VNode,
+    MessageSender, Shared,
+};
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt::{self, Display, Formatter},
+    rc::Rc,
+};
+
+thread_local! {
+    /// The components being rendered along with their names, innermost last.
+    static RENDERING: RefCell<Vec<(&'static str, Rc<dyn Any>)>> = RefCell::new(vec![]);
+}
+
+/// Runs the closure with the component as the one being rendered, so that
+/// the children created within it are bound to it.
+pub(crate) fn rendering<COMP: Render, R>(comp: &Shared<COMP>, f: impl FnOnce() -> R) -> R {
+    /// Stops rendering the component even if the closure panics.
+    struct Rendered;
+
+    impl Drop for Rendered {
+        fn drop(&mut self) {
+            RENDERING.with(|rendering| rendering.borrow_mut().pop());
+        }
+    }
+
+    RENDERING.with(|rendering| {
+        rendering
+            .borrow_mut()
+            .push((short_type_name::<COMP>(), comp.clone()))
+    });
+    let _rendered = Rendered;
+    f()
+}
+
+/// The names of the components being rendered, from the root down to the
+/// innermost one.
+pub(crate) fn rendering_path() -> Vec<&'static str> {
+    RENDERING.with(|rendering| rendering.borrow().iter().map(|(name, _)| *name).collect())
+}
+
+/// The markup passed into a component, either as its children or as one of
+/// its named slots.
+///
+/// Declare it as a prop, `children: Children`, and place it in the render
+/// with `{ &self.children }`. Place it only once in a render, as its nodes
+/// may only be at one place in the DOM.
+#[derive(Clone, Default)]
+pub struct Children(Option<Rc<RefCell<dyn SlotContent>>>);
+
+impl Children {
+    /// Binds the markup to the component currently being rendered, which
+    /// is its render context.
+    ///
+    /// Panics if `RCTX` is not the component being rendered, unless it is
+    /// the root parent `()`.
+    pub fn new<RCTX: Render>(markup: VNode<RCTX>) -> Children {
+        if markup.is_none() {
+            return Children(None);
+        }
+        let root_parent: Rc<dyn Any> = Rc::new(RefCell::new(()));
+        let render_ctx = RENDERING
+            .with(|rendering| rendering.borrow().last().map(|(_, comp)| comp.clone()))
+            .and_then(|comp| comp.downcast::<RefCell<RCTX>>().ok())
+            .or_else(|| root_parent.downcast::<RefCell<RCTX>>().ok())
+            .expect(
+                "The children can only be created in the render of the component which \
+                 writes them.",
+            );
+        Children(Some(Rc::new(RefCell::new(BoundMarkup { markup, render_ctx }))))
+    }
+
+    /// Whether there is no markup passed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl PartialEq for Children {
+    fn eq(&self, other: &Children) -> bool {
+        match (&self.0, &other.0) {
+            (Some(this), Some(other)) => Rc::ptr_eq(this, other),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<'a, RCTX: Render> From<&'a Children> for VNode<RCTX> {
+    fn from(children: &'a Children) -> VNode<RCTX> {
+        match children.0 {
+            Some(ref content) => VNode::Slot(VSlot {
+                content: content.clone(),
+                node: None,
+            }),
+            None => VNode::None,
+        }
+    }
+}
+
+impl<RCTX: Render> From<Children> for VNode<RCTX> {
+    fn from(children: Children) -> VNode<RCTX> {
+        VNode::from(&children)
+    }
+}
+
+/// The representation of the passed markup in the vtree of the component
+/// which places it.
+pub struct VSlot {
+    content: Rc<RefCell<dyn SlotContent>>,
+    /// The first node of the markup as of its last patch.
+    node: Option<Node>,
+}
+
+impl VSlot {
+    pub(crate) fn render_walk(
+        &mut self,
+        parent: &Node,
+        next: Option<&Node>,
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        self.content.borrow_mut().render_walk(parent, next, rt)?;
+        self.node = self.content.borrow().node();
+        Ok(())
+    }
+
+    pub(crate) fn patch(
+        &mut self,
+        old: Option<&mut VSlot>,
+        parent: &Node,
+        next: Option<&Node>,
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        match old {
+            // The same markup is already patched in place.
+            Some(ref old) if Rc::ptr_eq(&self.content, &old.content) => (),
+            Some(old) => self.content.borrow_mut().patch(
+                Some(&mut *old.content.borrow_mut()),
+                parent,
+                next,
+                rt,
+            )?,
+            None => self.content.borrow_mut().patch(None, parent, next, rt)?,
+        }
+        self.node = self.content.borrow().node();
+        Ok(())
+    }
+
+    pub(crate) fn reorder(
+        &self,
+        parent: &Node,
+        next: Option<&Node>,
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        self.content.borrow().reorder(parent, next, rt)
+    }
+
+    pub(crate) fn remove(&self, parent: &Node, rt: &Runtime) -> Result<(), RenderError> {
+        self.content.borrow().remove(parent, rt)
+    }
+
+    pub(crate) fn node(&self) -> Option<&Node> {
+        self.node.as_ref()
+    }
+
+    pub(crate) fn hydrate(
+        &mut self,
+        parent: &Node,
+        existing: Option<Node>,
+        rt: &Runtime,
+    ) -> Result<Option<Node>, RenderError> {
+        let unclaimed = self.content.borrow_mut().hydrate(parent, existing, rt)?;
+        self.node = self.content.borrow().node();
+        Ok(unclaimed)
+    }
+
+    pub(crate) fn ssr_walk(&mut self, rx_sender: MessageSender) {
+        self.content.borrow_mut().ssr_walk(rx_sender)
+    }
+}
+
+impl Display for VSlot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.content.borrow())
+    }
+}
+
+/// The markup with its render context erased, so that it may be placed by a
+/// component of another type.
+trait SlotContent: Display {
+    fn render_walk(
+        &mut self,
+        parent: &Node,
+        next: Option<&Node>,
+        rt: &Runtime,
+    ) -> Result<(), RenderError>;
+
+    fn patch(
+        &mut self,
+        old: Option<&mut dyn SlotContent>,
+        parent: &Node,
+        next: Option<&Node>,
+        rt: &Runtime,
+    ) -> Result<(), RenderError>;
+
+    fn reorder(&self, parent: &Node, next: Option<&Node>, rt: &Runtime) -> Result<(), RenderError>;
+
+    fn remove(&self, parent: &Node, rt: &Runtime) -> Result<(), RenderError>;
+
+    fn node(&self) -> Option<Node>;
+
+    fn hydrate(
+        &mut self,
+        parent: &Node,
+        existing: Option<Node>,
+        rt: &Runtime,
+    ) -> Result<Option<Node>, RenderError>;
+
+    fn ssr_walk(&mut self, rx_sender: MessageSender);
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// The markup along with the component it is rendered in the context of.
+struct BoundMarkup<RCTX: Render> {
+    markup: VNode<RCTX>,
+    render_ctx: Shared<RCTX>,
+}
+
+impl<RCTX: Render> SlotContent for BoundMarkup<RCTX> {
+    fn render_walk(
+        &mut self,
+        parent: &Node,
+        next: Option<&Node>,
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        self.markup
+            .render_walk(parent, next, self.render_ctx.clone(), rt)
+    }
+
+    fn patch(
+        &mut self,
+        old: Option<&mut dyn SlotContent>,
+        parent: &Node,
+        next: Option<&Node>,
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        if let Some(old) = old {
+            let is_same = match old.as_any_mut().downcast_mut::<BoundMarkup<RCTX>>() {
+                Some(old) => {
+                    self.markup.patch(
+                        Some(&mut old.markup),
+                        parent,
+                        next,
+                        self.render_ctx.clone(),
+                        rt,
+                    )?;
+                    true
+                }
+                None => false,
+            };
+            if is_same {
+                return Ok(());
+            }
+            // The markup was written by a component of another type.
+            old.remove(parent, rt)?;
+        }
+        self.markup
+            .patch(None, parent, next, self.render_ctx.clone(), rt)
+    }
+
+    fn reorder(&self, parent: &Node, next: Option<&Node>, rt: &Runtime) -> Result<(), RenderError> {
+        self.markup.reorder(parent, next, rt)
+    }
+
+    fn remove(&self, parent: &Node, rt: &Runtime) -> Result<(), RenderError> {
+        self.markup.remove(parent, rt)
+    }
+
+    fn node(&self) -> Option<Node> {
+        self.markup.node().cloned()
+    }
+
+    fn hydrate(
+        &mut self,
+        parent: &Node,
+        existing: Option<Node>,
+        rt: &Runtime,
+    ) -> Result<Option<Node>, RenderError> {
+        self.markup
+            .hydrate(parent, existing, self.render_ctx.clone(), rt)
+    }
+
+    fn ssr_walk(&mut self, rx_sender: MessageSender) {
+        self.markup.ssr_walk(self.render_ctx.clone(), rx_sender)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl<RCTX: Render> Display for BoundMarkup<RCTX> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.markup)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::{
+        component::{root_render_ctx, Component, Lifecycle, Status},
+        dom::test::memory_runtime,
+        vdom::{
+            vcomponent::{ComponentManager, ComponentWrapper, VComponent},
+            velement::{EventListener, VElement},
+            vtext::VText,
+        },
+        Markup,
+    };
+    use std::mem;
+
+    struct Card {
+        children: Children,
+        __status: Shared<Status<()>>,
+    }
+
+    impl Lifecycle for Card {}
+
+    impl Component for Card {
+        type Props = Children;
+        type Events = ();
+        type State = ();
+
+        fn init(children: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+            Card {
+                children,
+                __status: Rc::new(RefCell::new(status)),
+            }
+        }
+
+        fn update(&mut self, children: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            if self.children == children {
+                return None;
+            }
+            self.__status.borrow_mut().set_props_dirty(true);
+            Some(mem::replace(&mut self.children, children))
+        }
+
+        fn refresh_state(&mut self) {
+            unreachable!()
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            Some(&self.__status)
+        }
+
+        fn set_state(&self, _: impl FnMut(&mut Self::State)) {
+            unreachable!()
+        }
+    }
+
+    impl Render for Card {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VElement::new(
+                "section",
+                vec![],
+                vec![],
+                VNode::from(&self.children),
+            ))
+        }
+    }
+
+    struct Page {
+        label: &'static str,
+        __status: Shared<Status<()>>,
+    }
+
+    impl Lifecycle for Page {}
+
+    impl Component for Page {
+        type Props = &'static str;
+        type Events = ();
+        type State = ();
+
+        fn init(label: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+            Page {
+                label,
+                __status: Rc::new(RefCell::new(status)),
+            }
+        }
+
+        fn update(&mut self, label: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            self.__status.borrow_mut().set_props_dirty(true);
+            Some(mem::replace(&mut self.label, label))
+        }
+
+        fn refresh_state(&mut self) {
+            unreachable!()
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            Some(&self.__status)
+        }
+
+        fn set_state(&self, _: impl FnMut(&mut Self::State)) {
+            unreachable!()
+        }
+    }
+
+    impl Render for Page {
+        fn render(&self) -> Markup<Self> {
+            let children = Children::new(VNode::from(VElement::new(
+                "p",
+                vec![],
+                vec![EventListener::new("click", Box::new(|_: &Page, _| {}))],
+                VNode::from(VText::text(self.label)),
+            )));
+            VNode::from(VComponent::new::<Card>(children, ()))
+        }
+    }
+
+    #[test]
+    fn should_render_the_children_within_the_component() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut wrapper = ComponentWrapper::<Page, ()>::new("Hello", ());
+        wrapper
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.inner_html(&parent), "<section><p>Hello</p></section>");
+
+        dom.reset_mutations();
+        wrapper.update("World", (), root_render_ctx());
+        wrapper
+            .render_walk(&parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.inner_html(&parent), "<section><p>World</p></section>");
+        assert_eq!(dom.mutations().created, 0);
+    }
+
+    #[test]
+    fn should_compare_children_by_identity() {
+        let children = Children::new(VNode::<()>::from(VText::text("Hello")));
+        assert!(children == children.clone());
+        assert!(children != Children::new(VNode::<()>::from(VText::text("Hello"))));
+        assert!(Children::new(VNode::<()>::None).is_empty());
+    }
+}
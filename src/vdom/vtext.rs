@@ -1,12 +1,18 @@
 //! Representation of text/comment in virtual dom tree.
 
-use crate::{component::Render, dom::DOMPatch, vdom::VNode, MessageSender, Shared};
+use crate::{
+    component::Render,
+    dom::{DOMPatch, Node, NodeKind, Runtime},
+    error::{Operation, RenderError},
+    hydrate::{self, Hydrate},
+    ssr::{escape_comment, escape_text, SSRWalk},
+    vdom::VNode,
+    MessageSender, Shared,
+};
 use std::{
     fmt::{self, Display, Formatter},
     marker::PhantomData,
 };
-use wasm_bindgen::prelude::JsValue;
-use web_sys::{window, Node};
 
 /// The representation of text/comment in virtual dom tree.
 pub struct VText<RCTX: Render> {
@@ -51,31 +57,28 @@ impl<RCTX: Render> From<VText<RCTX>> for VNode<RCTX> {
 impl<RCTX: Render> Display for VText<RCTX> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         if self.is_comment {
-            write!(f, "<!--{}-->", self.content)
+            write!(f, "<!--{}-->", escape_comment(&self.content))
         } else {
-            write!(f, "{}", self.content)
+            write!(f, "{}", escape_text(&self.content))
         }
     }
 }
 
 impl<RCTX: Render> VText<RCTX> {
-    fn patch_new(&mut self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
-        let node: Node = if self.is_comment {
-            window()
-                .unwrap()
-                .document()
-                .unwrap()
-                .create_comment(&self.content)
-                .into()
+    fn patch_new(
+        &mut self,
+        parent: &Node,
+        next: Option<&Node>,
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
+        let node = if self.is_comment {
+            rt.dom.create_comment(&self.content)
         } else {
-            window()
-                .unwrap()
-                .document()
-                .unwrap()
-                .create_text_node(&self.content)
-                .into()
-        };
-        parent.insert_before(&node, next)?;
+            rt.dom.create_text_node(&self.content)
+        }.map_err(RenderError::js(Operation::Create))?;
+        rt.dom
+            .insert_before(parent, &node, next)
+            .map_err(RenderError::js(Operation::Insert))?;
         self.node = Some(node);
         Ok(())
     }
@@ -90,8 +93,8 @@ impl<RCTX: Render> DOMPatch for VText<RCTX> {
         _: &Node,
         _: Option<&Node>,
         _: Shared<Self::RenderContext>,
-        _: MessageSender,
-    ) -> Result<(), JsValue> {
+        _: &Runtime,
+    ) -> Result<(), RenderError> {
         unreachable!("There is nothing to render in a VText");
     }
 
@@ -101,41 +104,48 @@ impl<RCTX: Render> DOMPatch for VText<RCTX> {
         parent: &Node,
         next: Option<&Node>,
         _: Shared<Self::RenderContext>,
-        _: MessageSender,
-    ) -> Result<(), JsValue> {
+        rt: &Runtime,
+    ) -> Result<(), RenderError> {
         if let Some(old) = old {
             if self.is_comment == old.is_comment {
                 let old_node = old
                     .node
                     .as_ref()
-                    .expect("The old node is expected to be attached to the DOM");
+                    .ok_or_else(|| RenderError::detached(Operation::Patch))?;
                 if self.content != old.content {
-                    old_node.set_text_content(Some(&self.content));
+                    rt.dom
+                        .set_text_content(old_node, &self.content)
+                        .map_err(RenderError::js(Operation::SetText))?;
                 }
                 self.node = Some(old_node.clone());
                 Ok(())
             } else {
-                old.remove(parent)?;
-                self.patch_new(parent, next)
+                old.remove(parent, rt)?;
+                self.patch_new(parent, next, rt)
             }
         } else {
-            self.patch_new(parent, next)
+            self.patch_new(parent, next, rt)
         }
     }
 
-    fn reorder(&self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
-        let node = self.node.as_ref().unwrap();
-        parent.insert_before(node, next)?;
-        Ok(())
+    fn reorder(&self, parent: &Node, next: Option<&Node>, rt: &Runtime) -> Result<(), RenderError> {
+        let node = self
+            .node
+            .as_ref()
+            .ok_or_else(|| RenderError::detached(Operation::Insert))?;
+        rt.dom
+            .insert_before(parent, node, next)
+            .map_err(RenderError::js(Operation::Insert))
     }
 
-    fn remove(&self, parent: &Node) -> Result<(), JsValue> {
-        parent.remove_child(
-            self.node
-                .as_ref()
-                .expect("The old node is expected to be attached to the DOM"),
-        )?;
-        Ok(())
+    fn remove(&self, parent: &Node, rt: &Runtime) -> Result<(), RenderError> {
+        let node = self
+            .node
+            .as_ref()
+            .ok_or_else(|| RenderError::detached(Operation::Remove))?;
+        rt.dom
+            .remove_child(parent, node)
+            .map_err(RenderError::js(Operation::Remove))
     }
 
     fn node(&self) -> Option<&Node> {
@@ -143,10 +153,82 @@ impl<RCTX: Render> DOMPatch for VText<RCTX> {
     }
 }
 
+impl<RCTX: Render> Hydrate for VText<RCTX> {
+    type RenderContext = RCTX;
+
+    fn hydrate(
+        &mut self,
+        parent: &Node,
+        existing: Option<Node>,
+        _: Shared<Self::RenderContext>,
+        rt: &Runtime,
+    ) -> Result<Option<Node>, RenderError> {
+        // An empty text is never rendered by the server.
+        if self.content.is_empty() && !self.is_comment {
+            self.patch_new(parent, existing.as_ref(), rt)?;
+            return Ok(existing);
+        }
+
+        let same_kind = existing.as_ref().and_then(|node| match rt.dom.node_kind(node) {
+            NodeKind::Text(content) if !self.is_comment => Some(content),
+            NodeKind::Comment(content) if self.is_comment => Some(content),
+            _ => None,
+        });
+        match (existing, same_kind) {
+            (Some(node), Some(content)) => {
+                let next = if content == self.content {
+                    rt.dom.next_sibling(&node)
+                } else if !self.is_comment && content.starts_with(&self.content) {
+                    // Adjacent texts are merged into a single text node by the
+                    // browser, so split off the rest for the next VText.
+                    let rest = rt
+                        .dom
+                        .split_text(&node, self.content.len())
+                        .map_err(RenderError::js(Operation::SplitText))?;
+                    Some(rest)
+                } else {
+                    hydrate::report_mismatch(
+                        &*rt.dom,
+                        &format!("{:?}", self.content),
+                        Some(&node),
+                    );
+                    rt.dom
+                        .set_text_content(&node, &self.content)
+                        .map_err(RenderError::js(Operation::SetText))?;
+                    rt.dom.next_sibling(&node)
+                };
+                self.node = Some(node);
+                Ok(next)
+            }
+            (existing, _) => {
+                let expected = if self.is_comment { "a comment" } else { "a text" };
+                hydrate::report_mismatch(&*rt.dom, expected, existing.as_ref());
+                self.patch_new(parent, existing.as_ref(), rt)?;
+                hydrate::discard(&*rt.dom, parent, existing)
+            }
+        }
+    }
+}
+
+impl<RCTX: Render> SSRWalk for VText<RCTX> {
+    type RenderContext = RCTX;
+
+    fn ssr_walk(&mut self, _: Shared<Self::RenderContext>, _: MessageSender) {
+        // There is nothing to walk on.
+    }
+}
+
 #[cfg(test)]
 pub mod test {
     use super::*;
-    use crate::{component::root_render_ctx, vdom::test::container};
+    use crate::{
+        component::root_render_ctx,
+        dom::{
+            test::{memory_runtime, web_runtime},
+            Node,
+        },
+        vdom::test::container,
+    };
     use wasm_bindgen_test::*;
 
     #[test]
@@ -164,6 +246,42 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn should_display_escaped_text() {
+        let text = VText::<()>::text("1 < 2 && 3 > 2");
+        assert_eq!(format!("{}", text), "1 &lt; 2 &amp;&amp; 3 &gt; 2");
+    }
+
+    #[test]
+    fn should_display_comment_without_early_termination() {
+        let comment = VText::<()>::comment("--><script>");
+        assert_eq!(format!("{}", comment), "<!--- -><script>-->");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_hydrate_merged_texts() {
+        let mut list = VNode::from(vec![
+            VNode::from(VText::text("Hello ")),
+            VNode::from(VText::text("World!")),
+        ]);
+        let div = container();
+        div.set_inner_html("Hello World!");
+        let existing = div.first_child();
+
+        let rest = list
+            .hydrate(
+                &Node::web(div.clone()),
+                existing.clone().map(Node::web),
+                root_render_ctx(),
+                &web_runtime(),
+            ).expect("To hydrate div");
+
+        assert!(rest.is_none());
+        assert_eq!(div.child_nodes().length(), 2);
+        assert!(list.node().unwrap().as_web().is_same_node(existing.as_ref()));
+        assert_eq!(div.inner_html(), "Hello World!");
+    }
+
     #[wasm_bindgen_test]
     fn should_patch_container_with_new_text() {
         let mut vtext = VText::text("Hello World! It is nice to render.");
@@ -171,10 +289,10 @@ pub mod test {
         vtext
             .patch(
                 None,
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch the div");
 
         assert_eq!(div.inner_html(), "Hello World! It is nice to render.");
@@ -187,10 +305,10 @@ pub mod test {
         vtext
             .patch(
                 None,
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(div.inner_html(), "Hello World! It is nice to render.");
@@ -199,10 +317,10 @@ pub mod test {
         updated
             .patch(
                 Some(&mut vtext),
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(div.inner_html(), "How you doing?");
@@ -215,10 +333,10 @@ pub mod test {
         comment
             .patch(
                 None,
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(div.inner_html(), "<!--This is a comment-->");
@@ -231,10 +349,10 @@ pub mod test {
         comment
             .patch(
                 None,
-                div.as_ref(),
+                &Node::web(div.clone()),
                 None,
                 root_render_ctx(),
-                crate::message_sender(),
+                &web_runtime(),
             ).expect("To patch div");
 
         assert_eq!(div.inner_html(), "<!--This is a comment-->");
@@ -242,13 +360,76 @@ pub mod test {
         let mut text = VText::text("This is a text");
         text.patch(
             Some(&mut comment),
-            div.as_ref(),
+            &Node::web(div.clone()),
             None,
             root_render_ctx(),
-            crate::message_sender(),
+            &web_runtime(),
         ).expect("To patch div");
 
         assert_eq!(div.inner_html(), "This is a text");
     }
 
+    #[test]
+    fn should_patch_only_changed_text_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let mut vtext = VText::text("Hello World!");
+        vtext
+            .patch(None, &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.inner_html(&parent), "Hello World!");
+
+        dom.reset_mutations();
+        let mut same = VText::text("Hello World!");
+        same.patch(Some(&mut vtext), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.mutations().total(), 0);
+
+        let mut updated = VText::text("How you doing?");
+        updated
+            .patch(Some(&mut same), &parent, None, root_render_ctx(), &rt)
+            .expect("To patch the container");
+        assert_eq!(dom.inner_html(&parent), "How you doing?");
+        assert_eq!(dom.mutations().texts_set, 1);
+        assert_eq!(dom.mutations().total(), 1);
+    }
+
+    #[test]
+    fn should_hydrate_merged_texts_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let existing = rt.dom.create_text_node("Hello World!").unwrap();
+        rt.dom.insert_before(&parent, &existing, None).unwrap();
+        let mut list = VNode::from(vec![
+            VNode::from(VText::text("Hello ")),
+            VNode::from(VText::text("World!")),
+        ]);
+
+        dom.reset_mutations();
+        let rest = list
+            .hydrate(&parent, Some(existing), root_render_ctx(), &rt)
+            .expect("To hydrate the container");
+
+        assert!(rest.is_none());
+        assert_eq!(dom.inner_html(&parent), "Hello World!");
+        // Only the text split off of the existing one is created.
+        assert_eq!(dom.mutations().created, 1);
+        assert!(dom.warnings().is_empty());
+    }
+
+    #[test]
+    fn should_warn_on_mismatched_text_natively() {
+        let (dom, rt) = memory_runtime();
+        let parent = dom.container();
+        let existing = rt.dom.create_comment("Hello").unwrap();
+        rt.dom.insert_before(&parent, &existing, None).unwrap();
+        let mut vtext = VText::text("Hello");
+
+        vtext
+            .hydrate(&parent, Some(existing), root_render_ctx(), &rt)
+            .expect("To hydrate the container");
+
+        assert_eq!(dom.inner_html(&parent), "Hello");
+        assert_eq!(dom.warnings().len(), 1);
+    }
 }
@@ -0,0 +1,52 @@
+#![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+
+use ruukh::prelude::*;
+use std::cell::Cell;
+use wasm_bindgen_test::*;
+use web_sys::window;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+thread_local! {
+    static RENDERS: Cell<usize> = Cell::new(0);
+}
+
+#[component]
+struct Counter {
+    #[state]
+    count: i32,
+}
+
+impl Lifecycle for Counter {
+    fn mounted(&self) {
+        for _ in 0..3 {
+            self.set_state(|state| state.count += 1);
+        }
+    }
+}
+
+impl Render for Counter {
+    fn render(&self) -> Markup<Self> {
+        RENDERS.with(|renders| renders.set(renders.get() + 1));
+        html! {
+            <p>{ self.count }</p>
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn should_flush_the_pending_updates_in_a_single_walk() {
+    let document = window().unwrap().document().unwrap();
+    let container = document.create_element("div").unwrap();
+    let handle = App::<Counter>::new().mount(container.clone());
+    assert_eq!(RENDERS.with(Cell::get), 1);
+
+    App::flush_sync();
+    assert_eq!(RENDERS.with(Cell::get), 2);
+    assert_eq!(container.inner_html(), "<p>3</p>");
+
+    // Nothing is left to be rendered by the scheduled walk.
+    App::flush_sync();
+    assert_eq!(RENDERS.with(Cell::get), 2);
+    handle.unmount();
+}
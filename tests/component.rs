@@ -1,4 +1,4 @@
-#![feature(proc_macro_gen, decl_macro)]
+#![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
 
 use ruukh::prelude::*;
 
@@ -166,3 +166,90 @@ fn should_build_a_component_with_default_option_props() {
     assert_eq!(props.prop_a, Some(false));
     assert_eq!(props.prop_b, Some(3));
 }
+
+#[test]
+fn should_build_a_boundary_component() {
+    #[component(boundary)]
+    struct Boundary {
+        #[state]
+        failed: bool,
+    }
+
+    #[component]
+    struct Button;
+
+    assert!(<Boundary as Component>::BOUNDARY);
+    assert!(!<Button as Component>::BOUNDARY);
+}
+
+#[test]
+fn should_build_a_generic_component() {
+    #[component]
+    #[events(
+        fn select(&self, item: T);
+    )]
+    struct List<T: Clone + PartialEq + 'static, S>
+    where
+        S: Default + Clone + PartialEq + 'static,
+    {
+        items: Vec<T>,
+        #[state]
+        selected: S,
+    }
+
+    let props: ListProps<u32> = ListProps!(items: vec![1, 2]);
+    assert_eq!(props.items, vec![1, 2]);
+
+    let state = ListState::<Option<usize>>::default();
+    assert_eq!(state.selected, None);
+}
+
+#[test]
+fn should_build_a_function_component() {
+    #[component]
+    fn Badge(text: &str, color: u32) -> Markup<Self> {
+        html! {
+            <span class={ color.to_string() }>{ text }</span>
+        }
+    }
+
+    let props = BadgeProps!(color: 1, text: "New");
+    assert_eq!(props.color, 1);
+    assert_eq!(props.text, "New");
+
+    assert!(!<Badge as Component>::BOUNDARY);
+}
+
+#[test]
+fn should_build_a_component_with_bound_inputs() {
+    use ruukh::bind::BindError;
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Profile {
+        #[state]
+        name: String,
+        #[state]
+        age: Option<u8>,
+        #[state]
+        subscribed: bool,
+    }
+
+    impl Render for Profile {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <input bind:value={name}/>
+                <input bind:value={age} bind:error={Self::on_invalid_age}/>
+                <input bind:checked={subscribed}/>
+            }
+        }
+    }
+
+    impl Profile {
+        fn on_invalid_age(&self, _: BindError) {}
+    }
+
+    let state = ProfileState::default();
+    assert_eq!(state.age, None);
+}
+
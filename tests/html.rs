@@ -1,7 +1,10 @@
-#![feature(proc_macro_non_items)]
+#![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
 
-use ruukh::prelude::*;
-use web_sys::Event;
+use ruukh::{
+    prelude::*,
+    vdom::{vslot::Children, Key, VNode},
+};
+use ruukh::event::{Event, KeyboardEvent, MouseEvent};
 
 #[test]
 fn should_expand_single_element() {
@@ -41,7 +44,9 @@ fn should_expand_element_with_props() {
     };
 }
 
-fn on_click(_: &(), _: Event) {}
+fn on_click(_: &(), _: MouseEvent) {}
+
+fn on_event(_: &(), _: Event) {}
 
 #[test]
 fn should_expand_element_with_event_listener() {
@@ -56,9 +61,234 @@ fn should_expand_element_with_attributes() {
         <button
             disabled={true}
             @click={on_click}
-            @doubleclick={on_click}
+            @doubleclick={on_event}
             name={"btn"}
         >"Click"
         </button>
     };
 }
+
+#[component]
+#[derive(Lifecycle)]
+struct Card {
+    #[prop(default)]
+    children: Children,
+    #[prop(default)]
+    header: Children,
+}
+
+impl Render for Card {
+    fn render(&self) -> Markup<Self> {
+        html! {
+            <section>
+                { &self.header }
+                <div>{ &self.children }</div>
+            </section>
+        }
+    }
+}
+
+#[test]
+fn should_expand_component_with_children() {
+    let _: Markup<()> = html! {
+        <Card>
+            <button @click={on_click}>"Click"</button>
+        </Card>
+    };
+}
+
+#[test]
+fn should_expand_component_with_named_slots() {
+    let _: Markup<()> = html! {
+        <Card>
+            <header slot>"Title"</header>
+            "Body"
+        </Card>
+        <Card>
+            <header slot>"Only a title"</header>
+        </Card>
+    };
+}
+
+#[component]
+#[derive(Lifecycle)]
+struct Rows<T: ToString + PartialEq + 'static> {
+    items: Vec<T>,
+}
+
+impl<T: ToString + PartialEq + 'static> Render for Rows<T> {
+    fn render(&self) -> Markup<Self> {
+        let rows: Vec<_> = self.items.iter().map(|item| html! {
+            <li>{ item.to_string() }</li>
+        }).collect();
+        html! {
+            <ul>{ rows }</ul>
+        }
+    }
+}
+
+#[test]
+fn should_expand_generic_component() {
+    let _: Markup<()> = html! {
+        <Rows<u32> items={vec![1, 2]}></Rows>
+        <Rows items={vec!["inferred"]}></Rows>
+    };
+}
+
+#[test]
+fn should_expand_conditionals() {
+    let render = |count: u32, name: Option<&str>| -> Markup<()> {
+        html! {
+            if count == 0 {
+                <p>"None"</p>
+            } else if let Some(name) = name {
+                <p>{ name }</p>
+            } else {
+                <p>"Many"</p>
+            }
+            if count > 10 {
+                <p>"Too many"</p>
+            }
+        }
+    };
+    assert_eq!(render(0, None).to_string(), "<p>None</p>");
+    assert_eq!(render(1, Some("Tom")).to_string(), "<p>Tom</p>");
+    assert_eq!(render(11, None).to_string(), "<p>Many</p><p>Too many</p>");
+}
+
+#[test]
+fn should_expand_loops() {
+    let items = vec![(1, "One"), (2, "Two")];
+    let keyed: Markup<()> = html! {
+        for &(id, name) in items.iter() {
+            <li key={id}>{ name }</li>
+        }
+    };
+    match keyed {
+        ruukh::vdom::VNode::List(_) => (),
+        _ => panic!("Expected a list"),
+    }
+    assert_eq!(keyed.to_string(), "<li>One</li><li>Two</li>");
+
+    let unkeyed: Markup<()> = html! {
+        <ul>
+            for &(_, name) in items.iter() {
+                <li>{ name }</li>
+            }
+        </ul>
+    };
+    assert_eq!(unkeyed.to_string(), "<ul><li>One</li><li>Two</li></ul>");
+}
+
+#[test]
+fn should_expand_matches() {
+    let render = |status: Result<u32, &str>| -> Markup<()> {
+        html! {
+            match status {
+                Ok(0) => {}
+                Ok(count) if count > 1 => <p>{ count }" items"</p>,
+                Ok(_) => <p>"An item"</p>,
+                Err(err) => {
+                    <p>"Failed: "{ err }</p>
+                }
+            }
+        }
+    };
+    assert_eq!(render(Ok(0)).to_string(), "");
+    assert_eq!(render(Ok(1)).to_string(), "<p>An item</p>");
+    assert_eq!(render(Err("Oops")).to_string(), "<p>Failed: Oops</p>");
+}
+
+#[test]
+fn should_expand_iterators_in_expression_blocks() {
+    let rows = vec![(1, "One"), (2, "Two")];
+    let keyed: Markup<()> = html! {
+        <table>
+            { rows.iter().map(|&(id, name)| html! {
+                <tr key={id}>{ name }</tr>
+            }) }
+        </table>
+    };
+    assert_eq!(
+        keyed.to_string(),
+        "<table><tr>One</tr><tr>Two</tr></table>"
+    );
+
+    let rows_only: Markup<()> = html! {
+        { rows.iter().map(|&(id, name)| html! {
+            <tr key={id}>{ name }</tr>
+        }) }
+    };
+    assert_keyed_by(&rows_only, &[1, 2]);
+
+    let pairs: Markup<()> = html! {
+        { rows.iter().rev().map(|&(id, name)| (id, html! { <p>{ name }</p> })) }
+    };
+    assert_eq!(pairs.to_string(), "<p>Two</p><p>One</p>");
+    assert_keyed_by(&pairs, &[2, 1]);
+}
+
+fn assert_keyed_by(markup: &Markup<()>, keys: &[i32]) {
+    match *markup {
+        VNode::List(ref list) => {
+            assert!(list.is_keyed());
+            let expected: Vec<_> = keys.iter().map(|&key| Key::new(key)).collect();
+            assert_eq!(list.keys().cloned().collect::<Vec<_>>(), expected);
+        }
+        _ => panic!("Expected a list"),
+    }
+}
+
+#[test]
+fn should_expand_typed_event_listeners() {
+    let _: Markup<()> = html! {
+        <input
+            @keydown={|_: &(), event: KeyboardEvent| {
+                let _ = event.key();
+            }}
+            @dblclick={on_click}
+            @submit={on_event}
+            @my-event={on_event}/>
+    };
+}
+
+#[test]
+fn should_expand_event_listeners_with_modifiers() {
+    let _: Markup<()> = html! {
+        <form @submit.prevent.stop={on_event}>
+            <input @keydown.enter={|_: &(), _: KeyboardEvent| {}}/>
+            <button @click.once.capture={on_click}>"Send"</button>
+        </form>
+        <div @scroll.passive={on_event}></div>
+    };
+}
+
+#[test]
+fn should_expand_property_bindings() {
+    let name = "Ruukh".to_string();
+    let _: Markup<()> = html! {
+        <input .value={&name} @input={on_event}/>
+        <input name={"done"} .checked={true} .indeterminate={false}/>
+        <select .selectedIndex={1}>
+            <option>"First"</option>
+            <option>"Second"</option>
+        </select>
+    };
+}
+
+#[test]
+fn should_expand_svg_and_mathml_elements() {
+    let radius = 4;
+    let _: Markup<()> = html! {
+        <svg viewBox={"0 0 10 10"} xmlns:xlink={"http://www.w3.org/1999/xlink"}>
+            <defs>
+                <linearGradient id={"fade"} gradientUnits={"userSpaceOnUse"}>
+                    <stop offset={"0"}/>
+                </linearGradient>
+                <circle id={"dot"} r={radius.to_string()}/>
+            </defs>
+            <use xlink:href={"#dot"} fill={"url(#fade)"}/>
+        </svg>
+        <math><mi>"x"</mi></math>
+    };
+}
@@ -0,0 +1,66 @@
+#![feature(proc_macro_non_items, proc_macro_gen, decl_macro)]
+
+use ruukh::prelude::*;
+use std::cell::Cell;
+
+thread_local! {
+    static CREATED: Cell<u32> = Cell::new(0);
+    static MOUNTED: Cell<u32> = Cell::new(0);
+}
+
+#[component]
+struct Greeting {
+    name: String,
+}
+
+impl Lifecycle for Greeting {
+    fn created(&self) {
+        CREATED.with(|c| c.set(c.get() + 1));
+    }
+
+    fn mounted(&self) {
+        MOUNTED.with(|c| c.set(c.get() + 1));
+    }
+}
+
+impl Render for Greeting {
+    fn render(&self) -> Markup<Self> {
+        html! {
+            <p title={format!("Hi {}", self.name)}>"Hello "{ &self.name }"!"</p>
+        }
+    }
+}
+
+#[component]
+struct Page {
+    #[state(default = "<Tom & \"Jerry\">".to_string())]
+    name: String,
+}
+
+impl Lifecycle for Page {
+    fn created(&self) {
+        CREATED.with(|c| c.set(c.get() + 1));
+    }
+}
+
+impl Render for Page {
+    fn render(&self) -> Markup<Self> {
+        html! {
+            <h1>"Greetings"</h1>
+            <Greeting name={self.name.clone()}></Greeting>
+        }
+    }
+}
+
+#[test]
+fn should_render_app_to_escaped_string() {
+    let html = App::<Page>::new().render_to_string();
+
+    assert_eq!(
+        html,
+        "<h1>Greetings</h1><p title=\"Hi &lt;Tom &amp; &quot;Jerry&quot;&gt;\">\
+         Hello &lt;Tom &amp; \"Jerry\"&gt;!</p>"
+    );
+    assert_eq!(CREATED.with(|c| c.get()), 2);
+    assert_eq!(MOUNTED.with(|c| c.get()), 0);
+}